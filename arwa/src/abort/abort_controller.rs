@@ -0,0 +1,62 @@
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
+
+use crate::abort::AbortSignal;
+use crate::{impl_common_wrapper_traits, impl_js_cast};
+
+/// Controls an [AbortSignal] that can be used to abort one or more operations.
+#[derive(Clone)]
+pub struct AbortController {
+    inner: web_sys::AbortController,
+}
+
+impl AbortController {
+    pub fn new() -> Self {
+        AbortController {
+            // No indication in the spec that this can fail.
+            inner: web_sys::AbortController::new().unwrap_throw(),
+        }
+    }
+
+    /// Returns the signal controlled by this controller.
+    pub fn signal(&self) -> AbortSignal {
+        self.inner.signal().into()
+    }
+
+    /// Aborts the operations that observe the [signal](AbortController::signal).
+    ///
+    /// Does nothing if the signal was already aborted.
+    pub fn abort(&self) {
+        self.inner.abort()
+    }
+
+    /// Aborts the operations that observe the [signal](AbortController::signal) with a custom
+    /// `reason`, which will be reported as [AbortReason::Custom].
+    ///
+    /// Does nothing if the signal was already aborted.
+    ///
+    /// [AbortReason::Custom]: crate::abort::AbortReason::Custom
+    pub fn abort_with_reason(&self, reason: &JsValue) {
+        self.inner.abort_with_reason(reason)
+    }
+}
+
+impl Default for AbortController {
+    fn default() -> Self {
+        AbortController::new()
+    }
+}
+
+impl AsRef<web_sys::AbortController> for AbortController {
+    fn as_ref(&self) -> &web_sys::AbortController {
+        &self.inner
+    }
+}
+
+impl From<web_sys::AbortController> for AbortController {
+    fn from(inner: web_sys::AbortController) -> Self {
+        AbortController { inner }
+    }
+}
+
+impl_common_wrapper_traits!(AbortController);
+impl_js_cast!(AbortController);
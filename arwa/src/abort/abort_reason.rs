@@ -0,0 +1,34 @@
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The reason an [AbortSignal] was aborted.
+///
+/// [AbortSignal]: crate::abort::AbortSignal
+#[derive(Clone, Debug)]
+pub enum AbortReason {
+    /// Aborted without an explicit reason, e.g. by [AbortController::abort].
+    ///
+    /// [AbortController::abort]: crate::abort::AbortController::abort
+    Aborted,
+    /// Aborted because a timeout expired, e.g. by a signal created with [AbortSignal::timeout].
+    ///
+    /// [AbortSignal::timeout]: crate::abort::AbortSignal::timeout
+    TimedOut,
+    /// Aborted with a custom reason, e.g. by [AbortController::abort_with_reason].
+    ///
+    /// [AbortController::abort_with_reason]: crate::abort::AbortController::abort_with_reason
+    Custom(JsValue),
+}
+
+impl AbortReason {
+    pub(crate) fn from_js(reason: JsValue) -> Self {
+        if let Some(exception) = reason.dyn_ref::<web_sys::DomException>() {
+            match exception.name().as_str() {
+                "AbortError" => return AbortReason::Aborted,
+                "TimeoutError" => return AbortReason::TimedOut,
+                _ => (),
+            }
+        }
+
+        AbortReason::Custom(reason)
+    }
+}
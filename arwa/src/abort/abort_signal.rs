@@ -0,0 +1,67 @@
+use js_sys::Array;
+
+use crate::abort::{AbortController, AbortReason};
+use crate::event::{impl_event_target_traits, impl_try_from_event_target};
+use crate::timer::Duration;
+
+/// Signals that an operation should be aborted, see [AbortController].
+///
+/// [AbortController]: crate::abort::AbortController
+#[derive(Clone)]
+pub struct AbortSignal {
+    inner: web_sys::AbortSignal,
+}
+
+impl AbortSignal {
+    /// Returns a signal that is aborted with [AbortReason::TimedOut] once the `duration` has
+    /// passed.
+    ///
+    /// A signal with a duration of [Duration::Infinity] is never aborted.
+    pub fn timeout(duration: Duration) -> Self {
+        match duration {
+            Duration::Milliseconds(milliseconds) => {
+                web_sys::AbortSignal::timeout_with_u32(milliseconds).into()
+            }
+            Duration::Infinity => AbortController::new().signal(),
+        }
+    }
+
+    /// Returns a signal that is aborted as soon as any of the `signals` is aborted, with the same
+    /// reason.
+    ///
+    /// If any of the `signals` is already aborted, the returned signal is also already aborted.
+    pub fn any(signals: &[AbortSignal]) -> Self {
+        let array: Array = signals.iter().map(|signal| signal.inner.clone()).collect();
+
+        web_sys::AbortSignal::any(array.as_ref()).into()
+    }
+
+    /// Whether the operations that observe this signal have been aborted.
+    pub fn aborted(&self) -> bool {
+        self.inner.aborted()
+    }
+
+    /// Returns the reason this signal was aborted, or `None` if it has not been aborted.
+    pub fn reason(&self) -> Option<AbortReason> {
+        if self.inner.aborted() {
+            Some(AbortReason::from_js(self.inner.reason()))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<web_sys::AbortSignal> for AbortSignal {
+    fn from(inner: web_sys::AbortSignal) -> Self {
+        AbortSignal { inner }
+    }
+}
+
+impl AsRef<web_sys::AbortSignal> for AbortSignal {
+    fn as_ref(&self) -> &web_sys::AbortSignal {
+        &self.inner
+    }
+}
+
+impl_event_target_traits!(AbortSignal);
+impl_try_from_event_target!(AbortSignal);
@@ -0,0 +1,8 @@
+mod abort_controller;
+pub use self::abort_controller::*;
+
+mod abort_reason;
+pub use self::abort_reason::*;
+
+mod abort_signal;
+pub use self::abort_signal::*;
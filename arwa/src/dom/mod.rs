@@ -49,7 +49,7 @@
 //! Certain manipulations of the DOM tree can result in invalid DOM hierarchies. See
 //! [HierarchyRequestError] for details on how an operation might produce an invalid hierarchy.
 
-pub use arwa_macro::{name, non_colon_name, qualified_name, selector, token};
+pub use arwa_macro::{name, non_colon_name, pseudo_element, qualified_name, selector, token};
 
 mod attribute;
 pub use self::attribute::*;
@@ -114,6 +114,9 @@ pub use self::pointer_lock_error_event::*;
 mod processing_instruction;
 pub use self::processing_instruction::*;
 
+mod pseudo_element;
+pub use self::pseudo_element::*;
+
 mod qualified_name;
 pub use self::qualified_name::*;
 
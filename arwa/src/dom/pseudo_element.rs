@@ -0,0 +1,92 @@
+use std::fmt;
+
+pub use arwa_parse::selector::InvalidPseudoElement;
+use arwa_parse::selector::PseudoElement as DynamicallyParsedPseudoElement;
+
+use crate::console::{Argument, ToArgument};
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct StaticallyParsedPseudoElement {
+    #[doc(hidden)]
+    pub pseudo_element: &'static str,
+}
+
+impl AsRef<str> for StaticallyParsedPseudoElement {
+    fn as_ref(&self) -> &str {
+        self.pseudo_element
+    }
+}
+
+#[derive(Clone)]
+enum PseudoElementInternal {
+    Static(StaticallyParsedPseudoElement),
+    Dynamic(DynamicallyParsedPseudoElement),
+}
+
+/// A pseudo-element, e.g. `::before`, `::marker` or `::part(label)`.
+///
+/// Pseudo-elements can be validated at compile time with the [pseudo_element] macro.
+///
+/// [pseudo_element]: crate::dom::pseudo_element
+#[derive(Clone)]
+pub struct PseudoElement {
+    internal: PseudoElementInternal,
+}
+
+impl PseudoElement {
+    pub fn parse(pseudo_element: &str) -> Result<Self, InvalidPseudoElement> {
+        DynamicallyParsedPseudoElement::parse(pseudo_element).map(|pseudo_element| PseudoElement {
+            internal: PseudoElementInternal::Dynamic(pseudo_element),
+        })
+    }
+
+    #[doc(hidden)]
+    pub const fn from_statically_parsed(pseudo_element: StaticallyParsedPseudoElement) -> Self {
+        PseudoElement {
+            internal: PseudoElementInternal::Static(pseudo_element),
+        }
+    }
+}
+
+impl AsRef<str> for PseudoElement {
+    fn as_ref(&self) -> &str {
+        match &self.internal {
+            PseudoElementInternal::Static(pseudo_element) => pseudo_element.as_ref(),
+            PseudoElementInternal::Dynamic(pseudo_element) => pseudo_element.as_ref(),
+        }
+    }
+}
+
+impl ToArgument for PseudoElement {
+    fn to_argument(&self) -> Argument<'_> {
+        let as_str: &str = self.as_ref();
+
+        ToArgument::to_argument(as_str)
+    }
+}
+
+impl PartialEq for PseudoElement {
+    fn eq(&self, other: &Self) -> bool {
+        let self_as_str: &str = self.as_ref();
+        let other_as_str: &str = other.as_ref();
+
+        self_as_str == other_as_str
+    }
+}
+
+impl fmt::Display for PseudoElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str: &str = self.as_ref();
+
+        fmt::Display::fmt(as_str, f)
+    }
+}
+
+impl fmt::Debug for PseudoElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str: &str = self.as_ref();
+
+        fmt::Debug::fmt(as_str, f)
+    }
+}
@@ -1,9 +1,13 @@
 use std::fmt;
+use std::fmt::Write;
 
 pub use arwa_parse::selector::InvalidSelector;
-use arwa_parse::selector::Selector as DynamicallyParsedSelector;
+use arwa_parse::selector::{
+    serialize_identifier, serialize_string, Selector as DynamicallyParsedSelector,
+};
 
 use crate::console::{Argument, ToArgument};
+use crate::dom::{Name, Token};
 
 #[doc(hidden)]
 #[derive(Clone)]
@@ -22,6 +26,8 @@ impl AsRef<str> for StaticallyParsedSelector {
 enum SelectorInternal {
     Static(StaticallyParsedSelector),
     Dynamic(DynamicallyParsedSelector),
+    // Serialized by a `SelectorBuilder`, which only produces valid selectors.
+    Built(String),
 }
 
 #[derive(Clone)]
@@ -36,6 +42,16 @@ impl Selector {
         })
     }
 
+    /// Returns a builder for constructing a selector programmatically.
+    ///
+    /// Identifiers and attribute values passed to the builder are escaped as necessary. For
+    /// example, `ul.menu > li[data-x="y"]` can be built with
+    /// `Selector::builder().tag(&ul).class(&menu).child(item).build()`, where `item` is
+    /// `Selector::builder().tag(&li).attr_eq(&data_x, "y")`.
+    pub fn builder() -> SelectorBuilder {
+        SelectorBuilder::new()
+    }
+
     #[doc(hidden)]
     pub const fn from_statically_parsed(selector: StaticallyParsedSelector) -> Self {
         Selector {
@@ -49,6 +65,7 @@ impl AsRef<str> for Selector {
         match &self.internal {
             SelectorInternal::Static(selector) => selector.as_ref(),
             SelectorInternal::Dynamic(selector) => selector.as_ref(),
+            SelectorInternal::Built(selector) => selector,
         }
     }
 }
@@ -115,3 +132,269 @@ impl fmt::Debug for Selector {
         fmt::Debug::fmt(as_str, f)
     }
 }
+
+/// Builds a [Selector] programmatically, see [Selector::builder].
+///
+/// Simple selectors (e.g. [tag](SelectorBuilder::tag) or [class](SelectorBuilder::class)) are
+/// added to the last compound selector. Combinator methods (e.g. [child](SelectorBuilder::child))
+/// append the compound selectors of another builder; simple selectors added afterwards are added
+/// to the last compound selector of that builder. A compound selector without any simple selectors
+/// matches any element.
+///
+/// All input is validated by its type (e.g. [Name] or [Token]) and escaped as necessary, so any
+/// builder produces a valid selector.
+#[derive(Clone, Debug)]
+pub struct SelectorBuilder {
+    // Serialized compound selectors and combinators preceding the last compound selector.
+    preceding: String,
+    type_selector: Option<String>,
+    subclass_selectors: String,
+}
+
+impl SelectorBuilder {
+    fn new() -> Self {
+        SelectorBuilder {
+            preceding: String::new(),
+            type_selector: None,
+            subclass_selectors: String::new(),
+        }
+    }
+
+    /// Matches elements with the given local `name`, replacing any prior type selector for the
+    /// last compound selector.
+    pub fn tag(mut self, name: &Name) -> Self {
+        let mut type_selector = String::new();
+
+        serialize_identifier(name.as_ref(), &mut type_selector).unwrap();
+
+        self.type_selector = Some(type_selector);
+
+        self
+    }
+
+    /// Matches any element, replacing any prior type selector for the last compound selector.
+    pub fn universal(mut self) -> Self {
+        self.type_selector = Some("*".to_string());
+
+        self
+    }
+
+    /// Matches elements with the given `id`.
+    pub fn id(mut self, id: &Token) -> Self {
+        self.subclass_selectors.push('#');
+        serialize_identifier(id.as_ref(), &mut self.subclass_selectors).unwrap();
+
+        self
+    }
+    /// Matches elements that have the given `class`.
+    pub fn class(mut self, class: &Token) -> Self {
+        self.subclass_selectors.push('.');
+        serialize_identifier(class.as_ref(), &mut self.subclass_selectors).unwrap();
+
+        self
+    }
+
+    /// Matches elements that have an attribute with the given `name`.
+    pub fn attr(mut self, name: &Name) -> Self {
+        self.subclass_selectors.push('[');
+        serialize_identifier(name.as_ref(), &mut self.subclass_selectors).unwrap();
+        self.subclass_selectors.push(']');
+
+        self
+    }
+
+    /// Matches elements that have an attribute with the given `name` with a value that is exactly
+    /// `value`.
+    pub fn attr_eq(self, name: &Name, value: &str) -> Self {
+        self.attr_with_value(name, "=", value)
+    }
+
+    /// Matches elements that have an attribute with the given `name` with a value that is a
+    /// whitespace-separated list of words, one of which is exactly `value`.
+    pub fn attr_includes(self, name: &Name, value: &str) -> Self {
+        self.attr_with_value(name, "~=", value)
+    }
+
+    /// Matches elements that have an attribute with the given `name` with a value that starts
+    /// with `value`.
+    pub fn attr_starts_with(self, name: &Name, value: &str) -> Self {
+        self.attr_with_value(name, "^=", value)
+    }
+
+    /// Matches elements that have an attribute with the given `name` with a value that ends with
+    /// `value`.
+    pub fn attr_ends_with(self, name: &Name, value: &str) -> Self {
+        self.attr_with_value(name, "$=", value)
+    }
+
+    /// Matches elements that have an attribute with the given `name` with a value that contains
+    /// `value`.
+    pub fn attr_contains(self, name: &Name, value: &str) -> Self {
+        self.attr_with_value(name, "*=", value)
+    }
+
+    /// Matches elements that have an attribute with the given `name` with a value that is exactly
+    /// `value` or that starts with `value` immediately followed by `-`.
+    pub fn attr_hyphenated(self, name: &Name, value: &str) -> Self {
+        self.attr_with_value(name, "|=", value)
+    }
+
+    fn attr_with_value(mut self, name: &Name, matcher: &str, value: &str) -> Self {
+        self.subclass_selectors.push('[');
+        serialize_identifier(name.as_ref(), &mut self.subclass_selectors).unwrap();
+        self.subclass_selectors.push_str(matcher);
+        serialize_string(value, &mut self.subclass_selectors).unwrap();
+        self.subclass_selectors.push(']');
+
+        self
+    }
+
+    /// Matches elements that match any of the `selectors`.
+    ///
+    /// If `selectors` is empty, then no elements match.
+    pub fn is<I>(self, selectors: I) -> Self
+    where
+        I: IntoIterator<Item = SelectorBuilder>,
+    {
+        match self.function("is", selectors) {
+            Ok(builder) => builder,
+            Err(builder) => builder.pseudo_class("not(*)"),
+        }
+    }
+
+    /// Matches elements that do not match any of the `selectors`.
+    ///
+    /// If `selectors` is empty, then all elements match.
+    pub fn not<I>(self, selectors: I) -> Self
+    where
+        I: IntoIterator<Item = SelectorBuilder>,
+    {
+        self.function("not", selectors)
+            .unwrap_or_else(|builder| builder)
+    }
+
+    // Returns the builder unchanged as an error if the selector list is empty, as the functional
+    // pseudo-classes do not accept an empty list.
+    fn function<I>(mut self, name: &str, selectors: I) -> Result<Self, Self>
+    where
+        I: IntoIterator<Item = SelectorBuilder>,
+    {
+        let mut selectors = selectors.into_iter();
+
+        let Some(first) = selectors.next() else {
+            return Err(self);
+        };
+
+        write!(self.subclass_selectors, ":{}({}", name, first.serialize()).unwrap();
+
+        for selector in selectors {
+            write!(self.subclass_selectors, ", {}", selector.serialize()).unwrap();
+        }
+
+        self.subclass_selectors.push(')');
+
+        Ok(self)
+    }
+
+    /// Matches elements that are the first child of their parent.
+    pub fn first_child(self) -> Self {
+        self.pseudo_class("first-child")
+    }
+
+    /// Matches elements that are the last child of their parent.
+    pub fn last_child(self) -> Self {
+        self.pseudo_class("last-child")
+    }
+
+    /// Matches elements that are the only child of their parent.
+    pub fn only_child(self) -> Self {
+        self.pseudo_class("only-child")
+    }
+
+    /// Matches elements that are the `a`n+`b`-th child of their parent, for some non-negative
+    /// integer `n`, counting from 1.
+    pub fn nth_child(mut self, a: i32, b: i32) -> Self {
+        write!(self.subclass_selectors, ":nth-child({}n{:+})", a, b).unwrap();
+
+        self
+    }
+
+    /// Matches elements that are the `a`n+`b`-th child of their parent, for some non-negative
+    /// integer `n`, counting backwards from the last child.
+    pub fn nth_last_child(mut self, a: i32, b: i32) -> Self {
+        write!(self.subclass_selectors, ":nth-last-child({}n{:+})", a, b).unwrap();
+
+        self
+    }
+
+    /// Matches elements that have no children.
+    pub fn empty(self) -> Self {
+        self.pseudo_class("empty")
+    }
+
+    /// Matches the root element of the document.
+    pub fn root(self) -> Self {
+        self.pseudo_class("root")
+    }
+
+    fn pseudo_class(mut self, name: &str) -> Self {
+        self.subclass_selectors.push(':');
+        self.subclass_selectors.push_str(name);
+
+        self
+    }
+
+    /// Appends the compound selectors of `other` with a descendant combinator (` `).
+    pub fn descendant(self, other: SelectorBuilder) -> Self {
+        self.combine(" ", other)
+    }
+
+    /// Appends the compound selectors of `other` with a child combinator (`>`).
+    pub fn child(self, other: SelectorBuilder) -> Self {
+        self.combine(" > ", other)
+    }
+
+    /// Appends the compound selectors of `other` with a next-sibling combinator (`+`).
+    pub fn next_sibling(self, other: SelectorBuilder) -> Self {
+        self.combine(" + ", other)
+    }
+
+    /// Appends the compound selectors of `other` with a subsequent-sibling combinator (`~`).
+    pub fn subsequent_sibling(self, other: SelectorBuilder) -> Self {
+        self.combine(" ~ ", other)
+    }
+
+    fn combine(self, combinator: &str, other: SelectorBuilder) -> Self {
+        let mut preceding = self.serialize();
+
+        preceding.push_str(combinator);
+        preceding.push_str(&other.preceding);
+
+        SelectorBuilder {
+            preceding,
+            type_selector: other.type_selector,
+            subclass_selectors: other.subclass_selectors,
+        }
+    }
+
+    fn serialize(self) -> String {
+        let mut serialized = self.preceding;
+
+        match self.type_selector {
+            Some(type_selector) => serialized.push_str(&type_selector),
+            None if self.subclass_selectors.is_empty() => serialized.push('*'),
+            None => (),
+        }
+
+        serialized.push_str(&self.subclass_selectors);
+
+        serialized
+    }
+
+    /// Builds the selector.
+    pub fn build(self) -> Selector {
+        Selector {
+            internal: SelectorInternal::Built(self.serialize()),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::Stream;
+use pin_project::pin_project;
+
+use crate::timer::{Duration, Timeout};
+use crate::window::{window, RequestAnimationFrame};
+
+/// Rate-limiting adapters for event streams.
+///
+/// These adapters are intended for high-frequency events, such as pointer, scroll and wheel events,
+/// but may be used with any [Stream].
+pub trait EventStreamExt: Stream + Sized {
+    /// Only yields an item after the stream has not produced a new item for the given `duration`.
+    ///
+    /// Each new item resets the timer; only the most recent item is yielded when the timer expires.
+    /// If the stream ends while an item is pending, the pending item is yielded immediately.
+    ///
+    /// The timer runs in the current global scope, so this adapter may be used both on the main
+    /// thread and inside workers.
+    fn debounce(self, duration: Duration) -> Debounce<Self> {
+        Debounce {
+            stream: self,
+            duration,
+            pending: None,
+            timeout: None,
+            stream_done: false,
+        }
+    }
+
+    /// Yields at most one item per `duration`.
+    ///
+    /// The first item is yielded immediately, after which items are held back until the `duration`
+    /// has passed. When it has, the most recent item that arrived in the meantime (if any) is
+    /// yielded and a new period starts; all other items that arrived during the period are dropped.
+    ///
+    /// The timer runs in the current global scope, so this adapter may be used both on the main
+    /// thread and inside workers.
+    fn throttle(self, duration: Duration) -> Throttle<Self> {
+        Throttle {
+            stream: self,
+            duration,
+            pending: None,
+            timeout: None,
+            stream_done: false,
+        }
+    }
+
+    /// Yields at most one item per animation frame.
+    ///
+    /// When an item arrives, an animation frame is requested; when the animation frame occurs, the
+    /// most recent item is yielded and all other items that arrived in the meantime are dropped.
+    ///
+    /// Animation frames are requested with [Window::request_animation_frame], this adapter can
+    /// only be used on the main thread.
+    ///
+    /// [Window::request_animation_frame]: crate::window::Window::request_animation_frame
+    fn coalesce_per_frame(self) -> CoalescePerFrame<Self> {
+        CoalescePerFrame {
+            stream: self,
+            pending: None,
+            frame: None,
+            stream_done: false,
+        }
+    }
+}
+
+impl<S> EventStreamExt for S where S: Stream {}
+
+#[pin_project]
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct Debounce<S>
+where
+    S: Stream,
+{
+    #[pin]
+    stream: S,
+    duration: Duration,
+    pending: Option<S::Item>,
+    timeout: Option<Pin<Box<Timeout>>>,
+    stream_done: bool,
+}
+
+impl<S> Stream for Debounce<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        while !*this.stream_done {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    *this.pending = Some(item);
+                    *this.timeout = Some(Box::pin(Timeout::global_context(*this.duration)));
+                }
+                Poll::Ready(None) => *this.stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if *this.stream_done {
+            *this.timeout = None;
+
+            return Poll::Ready(this.pending.take());
+        }
+
+        if let Some(timeout) = this.timeout.as_mut() {
+            if timeout.as_mut().poll(cx).is_ready() {
+                *this.timeout = None;
+
+                return Poll::Ready(this.pending.take());
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+#[pin_project]
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct Throttle<S>
+where
+    S: Stream,
+{
+    #[pin]
+    stream: S,
+    duration: Duration,
+    pending: Option<S::Item>,
+    timeout: Option<Pin<Box<Timeout>>>,
+    stream_done: bool,
+}
+
+impl<S> Stream for Throttle<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        while !*this.stream_done {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.timeout.is_some() {
+                        *this.pending = Some(item);
+                    } else {
+                        start_timeout(this.timeout, *this.duration, cx);
+
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                Poll::Ready(None) => *this.stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if *this.stream_done {
+            *this.timeout = None;
+
+            return Poll::Ready(this.pending.take());
+        }
+
+        if let Some(timeout) = this.timeout.as_mut() {
+            if timeout.as_mut().poll(cx).is_ready() {
+                *this.timeout = None;
+
+                if let Some(item) = this.pending.take() {
+                    start_timeout(this.timeout, *this.duration, cx);
+
+                    return Poll::Ready(Some(item));
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+fn start_timeout(timeout: &mut Option<Pin<Box<Timeout>>>, duration: Duration, cx: &mut Context) {
+    let mut new_timeout = Box::pin(Timeout::global_context(duration));
+
+    // A timeout does not start running until it is first polled.
+    let _ = new_timeout.as_mut().poll(cx);
+
+    *timeout = Some(new_timeout);
+}
+
+#[pin_project]
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct CoalescePerFrame<S>
+where
+    S: Stream,
+{
+    #[pin]
+    stream: S,
+    pending: Option<S::Item>,
+    frame: Option<Pin<Box<RequestAnimationFrame>>>,
+    stream_done: bool,
+}
+
+impl<S> Stream for CoalescePerFrame<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        while !*this.stream_done {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    *this.pending = Some(item);
+
+                    if this.frame.is_none() {
+                        *this.frame = Some(Box::pin(window().request_animation_frame()));
+                    }
+                }
+                Poll::Ready(None) => *this.stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if *this.stream_done {
+            *this.frame = None;
+
+            return Poll::Ready(this.pending.take());
+        }
+
+        if let Some(frame) = this.frame.as_mut() {
+            if frame.as_mut().poll(cx).is_ready() {
+                *this.frame = None;
+
+                return Poll::Ready(this.pending.take());
+            }
+        }
+
+        Poll::Pending
+    }
+}
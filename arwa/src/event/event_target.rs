@@ -9,8 +9,10 @@ use js_sys::{Promise, Uint8Array};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
+use crate::dom::Selector;
 use crate::event::event::{DynamicEvent, TypedEvent};
-use crate::event::on_event::OnEvent;
+use crate::event::listener::{ListenerHandle, ListenerOptions};
+use crate::event::on_event::{OnDelegated, OnEvent};
 use crate::event::type_id_event_name::type_id_to_event_name;
 use crate::event::CustomEventData;
 use crate::finalization_registry::FinalizationRegistry;
@@ -177,6 +179,64 @@ pub trait EventTarget: event_target_seal::Seal + Sized {
         )
     }
 
+    /// Adds a listener that synchronously invokes `f` for every event of type `T` that reaches
+    /// this target.
+    ///
+    /// Unlike the event streams returned by [on_typed_event](EventTarget::on_typed_event), `f` is
+    /// invoked while the event is being dispatched, so it can reliably cancel the event's default
+    /// action with `prevent_default`. The listener is removed when the returned handle is dropped.
+    ///
+    /// See [add_listener_with_options](EventTarget::add_listener_with_options) for adding a
+    /// listener for the capture phase, a listener that is only invoked once, or a listener that is
+    /// removed when an [AbortSignal](crate::abort::AbortSignal) is aborted.
+    fn add_listener<T, F>(&self, f: F) -> ListenerHandle
+    where
+        T: TypedEvent<CurrentTarget = Self> + 'static,
+        F: FnMut(&T) + 'static,
+    {
+        self.add_listener_with_options(ListenerOptions::default(), f)
+    }
+
+    /// Adds a listener that synchronously invokes `f` for every event of type `T` that reaches
+    /// this target, with the given `options`.
+    ///
+    /// See [add_listener](EventTarget::add_listener) for details.
+    fn add_listener_with_options<T, F>(&self, options: ListenerOptions, mut f: F) -> ListenerHandle
+    where
+        T: TypedEvent<CurrentTarget = Self> + 'static,
+        F: FnMut(&T) + 'static,
+    {
+        ListenerHandle::new(
+            self.as_web_sys_event_target().clone(),
+            T::EVENT_TYPE.to_cow(),
+            options,
+            move |event| f(&T::from_web_sys_event_unchecked(event)),
+        )
+    }
+
+    /// Returns a stream of events of type `T` that occur on descendants of this target that match
+    /// the `selector`, along with the matching element.
+    ///
+    /// For every event that reaches this target, the composed path of the event is walked from
+    /// the event's target up to (but not including) this target; the first element on that path
+    /// that matches the `selector` is yielded along with the event. Events for which no element
+    /// matches are skipped. Because the composed path is used, an element inside a shadow tree is
+    /// only matched if the shadow tree is visible from this target; otherwise its shadow host is
+    /// matched instead.
+    ///
+    /// Note that events that don't bubble only reach this target in the capture phase, see
+    /// [OnDelegated::with_options].
+    fn on_delegated<T>(&self, selector: &Selector) -> OnDelegated<T>
+    where
+        T: TypedEvent<CurrentTarget = Self>,
+    {
+        OnDelegated::new(
+            self.as_web_sys_event_target().clone(),
+            T::EVENT_TYPE.to_cow(),
+            selector.clone(),
+        )
+    }
+
     fn dispatch_event(&self, event_type: &str, event_data: &JsValue, options: EventOptions) {
         let EventOptions {
             cancelable,
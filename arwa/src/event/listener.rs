@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+use crate::abort::AbortSignal;
+use crate::event::Phase;
+
+/// Options for a listener added with [EventTarget::add_listener_with_options].
+///
+/// [EventTarget::add_listener_with_options]: crate::event::EventTarget::add_listener_with_options
+#[derive(Clone)]
+pub struct ListenerOptions {
+    /// The phase in which the listener is invoked.
+    pub phase: Phase,
+    /// If `true`, the listener promises not to call `prevent_default`; calls to `prevent_default`
+    /// from a passive listener are ignored.
+    pub passive: bool,
+    /// If `true`, the listener is removed after it is invoked for the first time.
+    pub once: bool,
+    /// If set, the listener is removed when the signal is aborted.
+    pub signal: Option<AbortSignal>,
+}
+
+impl Default for ListenerOptions {
+    fn default() -> Self {
+        ListenerOptions {
+            phase: Phase::Bubble,
+            passive: false,
+            once: false,
+            signal: None,
+        }
+    }
+}
+
+/// Handle for a listener added with [EventTarget::add_listener].
+///
+/// The listener is removed from its target when the handle is dropped. Use
+/// [forget](ListenerHandle::forget) to keep the listener for as long as the target exists.
+///
+/// [EventTarget::add_listener]: crate::event::EventTarget::add_listener
+#[must_use = "the listener is removed when the handle is dropped"]
+pub struct ListenerHandle {
+    target: web_sys::EventTarget,
+    event_type: Cow<'static, str>,
+    callback: Option<Closure<dyn FnMut(web_sys::Event)>>,
+    use_capture: bool,
+}
+
+impl ListenerHandle {
+    pub(crate) fn new<F>(
+        target: web_sys::EventTarget,
+        event_type: Cow<'static, str>,
+        options: ListenerOptions,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        let callback = Closure::wrap(Box::new(f) as Box<dyn FnMut(web_sys::Event)>);
+
+        let use_capture = match options.phase {
+            Phase::Capture => true,
+            Phase::Bubble => false,
+        };
+
+        let mut add_event_listener_options = web_sys::AddEventListenerOptions::new();
+
+        add_event_listener_options.capture(use_capture);
+        add_event_listener_options.passive(options.passive);
+        add_event_listener_options.once(options.once);
+
+        if let Some(signal) = &options.signal {
+            add_event_listener_options.signal(signal.as_ref());
+        }
+
+        target
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                &event_type,
+                callback.as_ref().unchecked_ref(),
+                &add_event_listener_options,
+            )
+            .unwrap_throw();
+
+        ListenerHandle {
+            target,
+            event_type,
+            callback: Some(callback),
+            use_capture,
+        }
+    }
+
+    /// Drops the handle without removing the listener.
+    ///
+    /// The listener remains active until it is removed by the target (see [ListenerOptions::once]
+    /// and [ListenerOptions::signal]). Note that the memory held by the listener is leaked.
+    pub fn forget(mut self) {
+        if let Some(callback) = self.callback.take() {
+            callback.forget();
+        }
+    }
+}
+
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        if let Some(callback) = &self.callback {
+            // Note: removing a listener that was already removed (e.g. because it was a `once`
+            // listener that was invoked, or because its signal was aborted) is a no-op.
+            self.target
+                .remove_event_listener_with_callback_and_bool(
+                    &self.event_type,
+                    callback.as_ref().unchecked_ref(),
+                    self.use_capture,
+                )
+                .unwrap_throw();
+        }
+    }
+}
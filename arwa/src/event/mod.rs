@@ -4,9 +4,15 @@ pub use self::custom_event::*;
 mod event;
 pub use self::event::*;
 
+mod event_stream_ext;
+pub use self::event_stream_ext::*;
+
 mod event_target;
 pub use self::event_target::*;
 
+mod listener;
+pub use self::listener::*;
+
 mod on_event;
 pub use self::on_event::*;
 
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::pin::Pin;
@@ -9,6 +10,7 @@ use js_sys::Uint8Array;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
+use crate::dom::{DynamicElement, Selector};
 use crate::event::Event;
 use crate::finalization_registry::FinalizationRegistry;
 use crate::js_serialize::{js_deserialize, js_serialize};
@@ -50,6 +52,7 @@ struct Internal<T> {
     callback: Option<Closure<dyn FnMut(web_sys::Event)>>,
     use_capture: bool, // We need this to drop properly
     terminated: bool,
+    delegate: Option<Selector>,
     state: CallbackState<T>,
 }
 
@@ -61,10 +64,23 @@ impl<T> Internal<T> {
             callback: None,
             use_capture: false,
             terminated: false,
+            delegate: None,
             state: CallbackState::uninitialized(),
         }
     }
 
+    fn uninitialized_delegated(
+        target: web_sys::EventTarget,
+        event_type: Cow<'static, str>,
+        selector: Selector,
+    ) -> Self {
+        let mut internal = Internal::uninitialized(target, event_type);
+
+        internal.delegate = Some(selector);
+
+        internal
+    }
+
     fn is_uninitialized(&self) -> bool {
         self.callback.is_none()
     }
@@ -90,33 +106,36 @@ where
             return;
         }
 
+        internal.state.buffer_policy = options.buffer;
+
         let state_ptr = (&mut internal.state) as *mut CallbackState<T>;
+        let delegate = internal.delegate.clone();
 
-        let callback = move |event| {
-            // We should not be dropping events here if this gets run with wasm-bindgen-futures
-            // `spawn_local`, as invoking the waker will immediately queue running the task as a
-            // micro-task on the current thread/workers event queue, whereas all user events get
-            // queued as macro tasks: this means that there's always a call to `poll_next`
-            // before the next event gets processed and we get away with only buffering 1 event.
-            //
-            // This implementation is not meant to be tied to wasm-bindgen-futures specifically,
-            // however, as this stream is not `Send`, potential executor implementations should be
-            // limited to the following 3 execution patterns:
-            //
-            // 1.  Immediately poll the task synchronously when the waker is called.
-            // 2.  Schedule a micro-task on the event loop to poll later (wasm-bindgen-futures
-            //     current approach).
-            // 3.  Schedule a macro-task on the event loop to poll later.
-            //
-            // Only 3. would be problematic and I think it can be argued that any reasonable
-            // implementation would favor 2. over 3.
+        let callback = move |event: web_sys::Event| {
+            // Events are buffered according to the buffer policy, regardless of whether or not the
+            // consumer is currently waiting on the stream. This means that the events a consumer
+            // observes do not depend on when the executor gets around to polling the stream
+            // again after the waker is invoked; only the buffer policy decides which events are
+            // dropped.
 
             // We know the state_ptr will always deref successfully because of Pin's guarantees.
-            let CallbackState { next, waker } = unsafe { &mut *state_ptr };
+            let state = unsafe { &mut *state_ptr };
+
+            // The composed path is only available while the event is being dispatched, so the
+            // delegate must be matched from within the listener.
+            let matched = if let Some(selector) = &delegate {
+                if let Some(element) = delegated_match(&event, selector) {
+                    Some(element)
+                } else {
+                    return;
+                }
+            } else {
+                None
+            };
 
-            if let Some(waker) = waker.take() {
-                next.replace(T::from_web_sys_event_unchecked(event));
+            state.push(T::from_web_sys_event_unchecked(event), matched);
 
+            if let Some(waker) = state.waker.take() {
                 waker.wake();
             }
         };
@@ -175,10 +194,39 @@ where
     }
 
     fn next(&mut self) -> Option<T> {
-        self.state.next.take()
+        self.state.buffer.pop_front().map(|(event, _)| event)
+    }
+
+    fn next_delegated(&mut self) -> Option<(T, DynamicElement)> {
+        self.state
+            .buffer
+            .pop_front()
+            .and_then(|(event, matched)| matched.map(|element| (event, element)))
     }
 }
 
+// Finds the first element on the composed path of the event that matches the selector, starting at
+// the event's target and stopping before the event's current target (the delegating target). Note
+// that the composed path does not include nodes in shadow trees that are hidden from the current
+// target, so for such nodes the shadow host is matched instead.
+fn delegated_match(event: &web_sys::Event, selector: &Selector) -> Option<DynamicElement> {
+    let current_target: JsValue = event.current_target()?.into();
+
+    for target in event.composed_path().iter() {
+        if target == current_target {
+            break;
+        }
+
+        if let Some(element) = target.dyn_ref::<web_sys::Element>() {
+            if element.matches(selector.as_ref()).unwrap_throw() {
+                return Some(element.clone().into());
+            }
+        }
+    }
+
+    None
+}
+
 impl<T> Drop for Internal<T> {
     fn drop(&mut self) {
         let target = self.target.deref();
@@ -203,15 +251,44 @@ impl<T> Drop for Internal<T> {
 
 struct CallbackState<T> {
     waker: Option<Waker>,
-    next: Option<T>,
+    buffer: VecDeque<(T, Option<DynamicElement>)>,
+    buffer_policy: BufferPolicy,
+    dropped: u64,
 }
 
 impl<T> CallbackState<T> {
     fn uninitialized() -> Self {
         CallbackState {
             waker: None,
-            next: None,
+            buffer: VecDeque::new(),
+            buffer_policy: BufferPolicy::default(),
+            dropped: 0,
+        }
+    }
+
+    fn push(&mut self, event: T, matched: Option<DynamicElement>) {
+        let (capacity, overflow) = match self.buffer_policy {
+            BufferPolicy::Latest => (1, Overflow::DropOldest),
+            BufferPolicy::Bounded { capacity, overflow } => (capacity.max(1), overflow),
+            BufferPolicy::Unbounded => {
+                self.buffer.push_back((event, matched));
+
+                return;
+            }
+        };
+
+        if self.buffer.len() >= capacity {
+            self.dropped += 1;
+
+            match overflow {
+                Overflow::DropOldest => {
+                    self.buffer.pop_front();
+                }
+                Overflow::DropNewest => return,
+            }
         }
+
+        self.buffer.push_back((event, matched));
     }
 }
 
@@ -230,6 +307,11 @@ impl<T> OnEvent<T> {
     pub fn with_options(self, options: EventIteratorOptions) -> OnEventWithOptions<T> {
         OnEventWithOptions::new(self, options)
     }
+
+    /// Returns the number of dropped events, see [BufferPolicy].
+    pub fn dropped_events(&self) -> u64 {
+        self.internal.state.dropped
+    }
 }
 
 impl<T> Stream for OnEvent<T>
@@ -259,7 +341,7 @@ where
         // initialized).
         on_event.internal.refresh_waker(cx.waker().clone());
 
-        // Return the most recent event, if any.
+        // Return the next buffered event, if any.
         if let Some(event) = on_event.internal.next() {
             Poll::Ready(Some(event))
         } else {
@@ -274,10 +356,38 @@ pub enum Phase {
     Capture,
 }
 
+/// Decides what happens to events that arrive while earlier events have not yet been consumed.
+///
+/// An event is dropped when it arrives while the buffer is full (either the new event or the
+/// oldest buffered event, depending on the policy). Each event stream counts the events it
+/// dropped this way; the count is returned by the stream's `dropped_events` method.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum BufferPolicy {
+    /// Only keep the most recent event; an unconsumed event is dropped when a new event arrives.
+    #[default]
+    Latest,
+    /// Keep up to `capacity` events; when the buffer is full, the `overflow` strategy decides
+    /// which event is dropped. A `capacity` of `0` is treated as a `capacity` of `1`.
+    Bounded { capacity: usize, overflow: Overflow },
+    /// Keep all events until they are consumed.
+    Unbounded,
+}
+
+/// Decides which event is dropped when an event arrives while a [BufferPolicy::Bounded] buffer is
+/// full.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overflow {
+    /// Drop the oldest buffered event to make room for the new event.
+    DropOldest,
+    /// Drop the new event.
+    DropNewest,
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct EventIteratorOptions {
     pub phase: Phase,
     pub passive: bool,
+    pub buffer: BufferPolicy,
 }
 
 impl Default for EventIteratorOptions {
@@ -285,6 +395,7 @@ impl Default for EventIteratorOptions {
         EventIteratorOptions {
             phase: Phase::Bubble,
             passive: true,
+            buffer: BufferPolicy::Latest,
         }
     }
 }
@@ -302,6 +413,11 @@ impl<T> OnEventWithOptions<T> {
             options,
         }
     }
+
+    /// Returns the number of dropped events, see [BufferPolicy].
+    pub fn dropped_events(&self) -> u64 {
+        self.internal.state.dropped
+    }
 }
 
 impl<T> Stream for OnEventWithOptions<T>
@@ -330,7 +446,7 @@ where
         // initialized).
         on_event.internal.refresh_waker(cx.waker().clone());
 
-        // Return the most recent event, if any.
+        // Return the next buffered event, if any.
         if let Some(event) = on_event.internal.next() {
             Poll::Ready(Some(event))
         } else {
@@ -339,6 +455,122 @@ where
     }
 }
 
+/// Stream of delegated events, returned by [EventTarget::on_delegated].
+///
+/// [EventTarget::on_delegated]: crate::event::EventTarget::on_delegated
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct OnDelegated<T> {
+    internal: Internal<T>,
+}
+
+impl<T> OnDelegated<T> {
+    pub(crate) fn new(
+        target: web_sys::EventTarget,
+        event_type: Cow<'static, str>,
+        selector: Selector,
+    ) -> Self {
+        OnDelegated {
+            internal: Internal::uninitialized_delegated(target, event_type, selector),
+        }
+    }
+
+    pub fn with_options(self, options: EventIteratorOptions) -> OnDelegatedWithOptions<T> {
+        OnDelegatedWithOptions {
+            internal: self.internal,
+            options,
+        }
+    }
+
+    /// Returns the number of dropped events, see [BufferPolicy].
+    pub fn dropped_events(&self) -> u64 {
+        self.internal.state.dropped
+    }
+}
+
+impl<T> Stream for OnDelegated<T>
+where
+    T: Event + 'static,
+{
+    type Item = (T, DynamicElement);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Use `get_unchecked_mut` here to avoid having to restrict `T` to `Unpin`
+        let on_delegated = unsafe { self.get_unchecked_mut() };
+
+        if on_delegated.internal.terminated {
+            return Poll::Ready(None);
+        }
+
+        // If the stream hasn't been initialized yet, initialize it
+        if on_delegated.internal.is_uninitialized() {
+            // We dont move internal, so this is safe.
+            unsafe {
+                Pin::new_unchecked(&mut on_delegated.internal)
+                    .initialize(&EventIteratorOptions::default());
+            };
+        }
+
+        // Set a new waker to keep the async iterator alive (or set the initial waker if we've just
+        // initialized).
+        on_delegated.internal.refresh_waker(cx.waker().clone());
+
+        // Return the next buffered event, if any.
+        if let Some(next) = on_delegated.internal.next_delegated() {
+            Poll::Ready(Some(next))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct OnDelegatedWithOptions<T> {
+    internal: Internal<T>,
+    options: EventIteratorOptions,
+}
+
+impl<T> OnDelegatedWithOptions<T> {
+    /// Returns the number of dropped events, see [BufferPolicy].
+    pub fn dropped_events(&self) -> u64 {
+        self.internal.state.dropped
+    }
+}
+
+impl<T> Stream for OnDelegatedWithOptions<T>
+where
+    T: Event + 'static,
+{
+    type Item = (T, DynamicElement);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Use `get_unchecked_mut` here to avoid having to restrict `T` to `Unpin`
+        let on_delegated = unsafe { self.get_unchecked_mut() };
+
+        if on_delegated.internal.terminated {
+            return Poll::Ready(None);
+        }
+
+        // If the stream hasn't been initialized yet, initialize it
+        if on_delegated.internal.is_uninitialized() {
+            let options = on_delegated.options;
+
+            // We dont move internal, so this is safe.
+            unsafe { Pin::new_unchecked(&mut on_delegated.internal).initialize(&options) }
+        }
+
+        // Set a new waker to keep the async iterator alive (or set the initial waker if we've just
+        // initialized).
+        on_delegated.internal.refresh_waker(cx.waker().clone());
+
+        // Return the next buffered event, if any.
+        if let Some(next) = on_delegated.internal.next_delegated() {
+            Poll::Ready(Some(next))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
 macro_rules! typed_event_iterator {
     ($iterator:ident, $iterator_with_options:ident, $event:ident, $name:tt) => {
         #[must_use = "streams do nothing unless polled or spawned"]
@@ -364,6 +596,11 @@ macro_rules! typed_event_iterator {
                     inner: $crate::event::OnEventWithOptions::new(self.inner, options),
                 }
             }
+
+            /// Returns the number of dropped events, see [crate::event::BufferPolicy].
+            pub fn dropped_events(&self) -> u64 {
+                self.inner.dropped_events()
+            }
         }
 
         impl<T> futures::stream::Stream for $iterator<T>
@@ -385,6 +622,13 @@ macro_rules! typed_event_iterator {
             inner: $crate::event::OnEventWithOptions<$event<T>>,
         }
 
+        impl<T> $iterator_with_options<T> {
+            /// Returns the number of dropped events, see [crate::event::BufferPolicy].
+            pub fn dropped_events(&self) -> u64 {
+                self.inner.dropped_events()
+            }
+        }
+
         impl<T> futures::stream::Stream for $iterator_with_options<T>
         where
             T: $crate::event::EventTarget + 'static,
@@ -5,14 +5,22 @@ use std::task::{Context, Poll};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 use wasm_bindgen_futures::JsFuture;
 
+use crate::fetch::{FormData, UploadBody};
 use crate::file::Blob;
 use crate::type_error_wrapper;
+use crate::url::UrlSearchParams;
 
 #[derive(Clone, Copy)]
 pub enum BodySource<'a> {
     String(&'a str),
     Blob(&'a Blob),
     Bytes(&'a [u8]),
+    /// Encoded as `multipart/form-data`.
+    FormData(&'a FormData),
+    /// Encoded as `application/x-www-form-urlencoded`.
+    UrlSearchParams(&'a UrlSearchParams),
+    /// Streamed in chunks, see [UploadBody].
+    Upload(&'a UploadBody),
 }
 
 type_error_wrapper!(ConsumeBodyError);
@@ -76,8 +84,17 @@ impl Body {
         }
     }
 
-    // TODO: FormData. Also curious if calling `json` would return a `FormData` object if the
-    // response has a form-data mime-type.
+    /// Decodes the body as a [FormData] set.
+    ///
+    /// The body must be encoded as `multipart/form-data` or as
+    /// `application/x-www-form-urlencoded`, as indicated by its `Content-Type` header; the future
+    /// resolves with an error otherwise.
+    pub fn to_form_data(&self) -> BodyToFormData {
+        BodyToFormData {
+            init: Some(self.internal.clone()),
+            inner: None,
+        }
+    }
 
     // pub fn to_byte_stream(&self) -> Option<BodyToByteStream> {
     //     todo!()
@@ -211,3 +228,34 @@ impl Future for BodyToBlob {
             .map_err(|err| ConsumeBodyError::new(err.unchecked_into()))
     }
 }
+
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct BodyToFormData {
+    init: Option<Internal>,
+    inner: Option<JsFuture>,
+}
+
+impl Future for BodyToFormData {
+    type Output = Result<FormData, ConsumeBodyError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Initialize
+        if let Some(init) = self.init.take() {
+            let res = match init {
+                Internal::Request(r) => r.form_data(),
+                Internal::Response(r) => r.form_data(),
+            };
+
+            // Note: no indication in the spec that this can actually fail at this step, instead
+            // the promise will reject if the body has already been "disturbed".
+            self.inner = Some(res.unwrap_throw().into());
+        }
+
+        let inner = Pin::new(self.inner.as_mut().unwrap_throw());
+
+        inner
+            .poll(cx)
+            .map_ok(|v| FormData::from(v.unchecked_into::<web_sys::FormData>()))
+            .map_err(|err| ConsumeBodyError::new(err.unchecked_into()))
+    }
+}
@@ -0,0 +1,344 @@
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use futures::future::LocalBoxFuture;
+use wasm_bindgen::JsValue;
+
+use crate::fetch::{
+    BodySource, FetchContext, FetchError, FetchOptions, FormData, Headers, Request, RequestCache,
+    RequestCredentials, RequestDescriptor, RequestInitError, RequestMethod, RequestMode,
+    RequestRedirect, RequestReferrer, Response,
+};
+use crate::file::Blob;
+use crate::security::ReferrerPolicy;
+use crate::url::{InvalidUrl, Url, UrlSearchParams};
+
+/// An owned request body for a [ClientRequest].
+///
+/// Unlike a [BodySource], a client body may be sent more than once (e.g. when a request is
+/// retried).
+#[derive(Clone)]
+pub enum ClientBody {
+    String(String),
+    Blob(Blob),
+    Bytes(Vec<u8>),
+    /// Encoded as `multipart/form-data`.
+    FormData(FormData),
+    /// Encoded as `application/x-www-form-urlencoded`.
+    UrlSearchParams(UrlSearchParams),
+}
+
+impl ClientBody {
+    fn as_body_source(&self) -> BodySource<'_> {
+        match self {
+            ClientBody::String(string) => BodySource::String(string),
+            ClientBody::Blob(blob) => BodySource::Blob(blob),
+            ClientBody::Bytes(bytes) => BodySource::Bytes(bytes),
+            ClientBody::FormData(form_data) => BodySource::FormData(form_data),
+            ClientBody::UrlSearchParams(params) => BodySource::UrlSearchParams(params),
+        }
+    }
+}
+
+/// A request sent through a [Client].
+///
+/// The request is only turned into a [Request] after it has passed through all of the client's
+/// [Middleware], which may modify it. The `url` may be relative; relative URLs are resolved against
+/// the URL of the current global scope, unless a middleware (such as [BaseUrl]) resolves it first.
+///
+/// [BaseUrl]: crate::fetch::BaseUrl
+pub struct ClientRequest {
+    pub method: RequestMethod,
+    pub url: String,
+    pub headers: Headers,
+    pub body: Option<ClientBody>,
+    pub mode: RequestMode,
+    pub credentials: RequestCredentials,
+    pub cache: RequestCache,
+    pub redirect: RequestRedirect,
+    pub referrer_policy: ReferrerPolicy,
+    pub integrity: Option<String>,
+    pub options: FetchOptions,
+}
+
+impl ClientRequest {
+    pub fn new(method: RequestMethod, url: &str) -> Self {
+        ClientRequest {
+            method,
+            url: url.to_string(),
+            headers: Headers::new(),
+            body: None,
+            mode: RequestMode::default(),
+            credentials: RequestCredentials::default(),
+            cache: RequestCache::default(),
+            redirect: RequestRedirect::default(),
+            referrer_policy: ReferrerPolicy::default(),
+            integrity: None,
+            options: FetchOptions::default(),
+        }
+    }
+
+    pub fn get(url: &str) -> Self {
+        ClientRequest::new(RequestMethod::GET, url)
+    }
+
+    pub fn post(url: &str, body: ClientBody) -> Self {
+        let mut request = ClientRequest::new(RequestMethod::POST, url);
+
+        request.body = Some(body);
+
+        request
+    }
+}
+
+impl Clone for ClientRequest {
+    fn clone(&self) -> Self {
+        // Copy the headers, rather than sharing the underlying headers object, so that changes
+        // made to one request (e.g. by middleware) do not affect the other.
+        let headers = Headers::new();
+
+        for (name, value) in &self.headers {
+            headers.append(&name, &value);
+        }
+
+        ClientRequest {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            headers,
+            body: self.body.clone(),
+            mode: self.mode,
+            credentials: self.credentials,
+            cache: self.cache,
+            redirect: self.redirect,
+            referrer_policy: self.referrer_policy,
+            integrity: self.integrity.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+/// The error returned by a [ClientFetch] that did not produce a response.
+#[derive(Clone)]
+pub enum ClientError {
+    /// The URL of the request could not be resolved.
+    InvalidUrl(InvalidUrl),
+    /// The request could not be created, e.g. because it specifies a body for a `GET` request.
+    InvalidRequest(RequestInitError),
+    /// The request was sent, but the fetch failed.
+    Fetch(FetchError),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::InvalidUrl(err) => fmt::Display::fmt(err, f),
+            ClientError::InvalidRequest(err) => fmt::Display::fmt(err, f),
+            ClientError::Fetch(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl fmt::Debug for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::InvalidUrl(err) => f.debug_tuple("InvalidUrl").field(err).finish(),
+            ClientError::InvalidRequest(err) => f.debug_tuple("InvalidRequest").field(err).finish(),
+            ClientError::Fetch(err) => f.debug_tuple("Fetch").field(err).finish(),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<FetchError> for ClientError {
+    fn from(err: FetchError) -> Self {
+        ClientError::Fetch(err)
+    }
+}
+
+/// A layer in the middleware stack of a [Client].
+///
+/// A middleware receives each request before it is sent, along with a [Next] handle that passes
+/// the request on to the remainder of the stack. A middleware may modify the request before passing
+/// it on, inspect or replace the response, pass the request on more than once (e.g. to retry it),
+/// or not pass it on at all.
+pub trait Middleware: 'static {
+    fn handle<'a>(
+        &'a self,
+        request: ClientRequest,
+        next: Next,
+    ) -> LocalBoxFuture<'a, Result<Response, ClientError>>;
+}
+
+/// The remainder of a [Client]'s middleware stack, see [Middleware].
+#[derive(Clone)]
+pub struct Next {
+    context: Rc<dyn FetchContext>,
+    middleware: Rc<[Rc<dyn Middleware>]>,
+    index: usize,
+}
+
+impl Next {
+    /// Passes the `request` on to the next middleware in the stack, or sends it if this is the
+    /// end of the stack.
+    pub fn run(
+        self,
+        request: ClientRequest,
+    ) -> LocalBoxFuture<'static, Result<Response, ClientError>> {
+        Box::pin(async move {
+            if let Some(middleware) = self.middleware.get(self.index).cloned() {
+                let next = Next {
+                    index: self.index + 1,
+                    ..self
+                };
+
+                middleware.handle(request, next).await
+            } else {
+                send(self.context.as_ref(), request).await
+            }
+        })
+    }
+}
+
+fn global_base_url() -> Option<Url> {
+    let location = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("location")).ok()?;
+    let href = js_sys::Reflect::get(&location, &JsValue::from_str("href")).ok()?;
+
+    Url::parse(&href.as_string()?).ok()
+}
+
+async fn send(context: &dyn FetchContext, request: ClientRequest) -> Result<Response, ClientError> {
+    let ClientRequest {
+        method,
+        url,
+        headers,
+        body,
+        mode,
+        credentials,
+        cache,
+        redirect,
+        referrer_policy,
+        integrity,
+        options,
+    } = request;
+
+    let url = match Url::parse(&url) {
+        Ok(url) => url,
+        Err(err) => match global_base_url() {
+            Some(base) => Url::parse_with_base(&url, &base).map_err(ClientError::InvalidUrl)?,
+            None => return Err(ClientError::InvalidUrl(err)),
+        },
+    };
+
+    let request = Request::try_init(
+        &url,
+        RequestDescriptor {
+            method,
+            headers: Some(&headers),
+            body: body.as_ref().map(|body| body.as_body_source()),
+            mode,
+            credentials,
+            cache,
+            redirect,
+            referrer: RequestReferrer::default(),
+            referrer_policy,
+            integrity: integrity.as_deref(),
+        },
+    )
+    .map_err(ClientError::InvalidRequest)?;
+
+    let response = context.fetch(&request).with_options(options).await?;
+
+    Ok(response)
+}
+
+/// Sends requests through a stack of [Middleware].
+///
+/// Middleware is added with [with](Client::with); the middleware that was added first is the
+/// outermost layer, it sees requests first and responses last. For example:
+///
+/// ```rust,ignore
+/// let client = Client::new(window)
+///     .with(Retry::exponential(3, Duration::Milliseconds(200)))
+///     .with(DefaultHeaders(headers))
+///     .with(BaseUrl(base_url));
+///
+/// let response = client.send(ClientRequest::get("users/1")).await?;
+/// ```
+///
+/// Here, a retried request passes through the [DefaultHeaders] and [BaseUrl] layers again on
+/// every attempt.
+///
+/// A client behaves identically for any [FetchContext], so the same middleware stack may be used
+/// on the main thread and inside workers.
+///
+/// [DefaultHeaders]: crate::fetch::DefaultHeaders
+/// [BaseUrl]: crate::fetch::BaseUrl
+#[derive(Clone)]
+pub struct Client {
+    context: Rc<dyn FetchContext>,
+    middleware: Rc<[Rc<dyn Middleware>]>,
+}
+
+impl Client {
+    pub fn new<C>(context: C) -> Self
+    where
+        C: FetchContext + 'static,
+    {
+        Client {
+            context: Rc::new(context),
+            middleware: Rc::new([]),
+        }
+    }
+
+    /// Adds the `middleware` as the innermost layer of this client's middleware stack.
+    pub fn with<M>(self, middleware: M) -> Self
+    where
+        M: Middleware,
+    {
+        let mut stack = self.middleware.to_vec();
+
+        stack.push(Rc::new(middleware));
+
+        Client {
+            context: self.context,
+            middleware: stack.into(),
+        }
+    }
+
+    pub fn send(&self, request: ClientRequest) -> ClientFetch {
+        let next = Next {
+            context: self.context.clone(),
+            middleware: self.middleware.clone(),
+            index: 0,
+        };
+
+        ClientFetch {
+            inner: next.run(request),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> ClientFetch {
+        self.send(ClientRequest::get(url))
+    }
+}
+
+/// Future returned by [Client::send].
+///
+/// Dropping this future before it completes aborts the request.
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct ClientFetch {
+    inner: LocalBoxFuture<'static, Result<Response, ClientError>>,
+}
+
+impl Future for ClientFetch {
+    type Output = Result<Response, ClientError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.as_mut().poll(cx)
+    }
+}
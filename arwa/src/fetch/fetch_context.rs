@@ -1,11 +1,17 @@
+use std::error::Error;
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use js_sys::Object;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use wasm_bindgen_futures::JsFuture;
 
+use crate::abort::{AbortReason, AbortSignal};
 use crate::fetch::{Request, Response};
+use crate::timer::Duration;
 use crate::type_error_wrapper;
 
 pub(crate) mod fetch_context_seal {
@@ -29,8 +35,7 @@ impl ContextInternal {
     ) -> JsFuture {
         // Per the spec, this should make a copy of the request except for the body. The beef of
         // the cost is probably in copying the headers. For the time being, I'm considering this
-        // worth it, as in return we get to ignore AbortController as part of the API and have
-        // drop-cancelled fetch requests.
+        // worth it, as in return we get drop-cancelled fetch requests.
         let mut init = web_sys::RequestInit::new();
 
         init.signal(Some(&abort_controller.signal()));
@@ -46,12 +51,117 @@ impl ContextInternal {
 
 type_error_wrapper!(NetworkError);
 
+/// The error returned by a [Fetch] that did not produce a response.
+#[derive(Clone)]
+pub enum FetchError {
+    /// The fetch was aborted by the [signal](FetchOptions::signal) in its options.
+    Aborted(AbortReason),
+    /// No response was received within the [timeout](FetchOptions::timeout) in its options.
+    TimedOut,
+    /// The fetch failed because of a network error, or because the request was blocked (e.g. by
+    /// CORS or a content security policy).
+    Network(NetworkError),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Aborted(_) => f.write_str("the fetch was aborted"),
+            FetchError::TimedOut => f.write_str("the fetch timed out"),
+            FetchError::Network(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl fmt::Debug for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Aborted(reason) => f.debug_tuple("Aborted").field(reason).finish(),
+            FetchError::TimedOut => f.write_str("TimedOut"),
+            FetchError::Network(err) => f.debug_tuple("Network").field(err).finish(),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Options for a [Fetch], see [Fetch::with_options].
+#[derive(Clone)]
+pub struct FetchOptions {
+    /// Aborts the fetch with [FetchError::TimedOut] if the response has not been received within
+    /// this duration.
+    ///
+    /// Defaults to [Duration::Infinity].
+    pub timeout: Duration,
+
+    /// Aborts the fetch with [FetchError::Aborted] when this signal is aborted.
+    ///
+    /// Defaults to `None`.
+    pub signal: Option<AbortSignal>,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        FetchOptions {
+            timeout: Duration::Infinity,
+            signal: None,
+        }
+    }
+}
+
+// Aborts a fetch's internal abort controller when a source signal is aborted.
+struct AbortForward {
+    source: web_sys::AbortSignal,
+    callback: Closure<dyn FnMut()>,
+}
+
+impl AbortForward {
+    fn new(source: &web_sys::AbortSignal, target: &web_sys::AbortController) -> Option<Self> {
+        if source.aborted() {
+            target.abort_with_reason(&source.reason());
+
+            return None;
+        }
+
+        let source = source.clone();
+        let target = target.clone();
+        let callback = {
+            let source = source.clone();
+
+            Closure::wrap(Box::new(move || {
+                target.abort_with_reason(&source.reason());
+            }) as Box<dyn FnMut()>)
+        };
+
+        source
+            .add_event_listener_with_callback("abort", callback.as_ref().unchecked_ref())
+            .unwrap_throw();
+
+        Some(AbortForward { source, callback })
+    }
+}
+
+impl Drop for AbortForward {
+    fn drop(&mut self) {
+        self.source
+            .remove_event_listener_with_callback("abort", self.callback.as_ref().unchecked_ref())
+            .unwrap_throw();
+    }
+}
+
+fn aborted_with(signal: &web_sys::AbortSignal, reason: &JsValue) -> bool {
+    signal.aborted() && Object::is(&signal.reason(), reason)
+}
+
 #[must_use = "futures do nothing unless polled or spawned"]
 pub struct Fetch {
     context: ContextInternal,
     request: Option<web_sys::Request>,
     inner: Option<JsFuture>,
+    options: FetchOptions,
     abort_controller: Option<web_sys::AbortController>,
+    timeout_signal: Option<web_sys::AbortSignal>,
+    abort_forwards: Vec<AbortForward>,
 }
 
 impl Fetch {
@@ -60,7 +170,10 @@ impl Fetch {
             context: ContextInternal::Window(context),
             request: Some(request),
             inner: None,
+            options: FetchOptions::default(),
             abort_controller: None,
+            timeout_signal: None,
+            abort_forwards: Vec::new(),
         }
     }
 
@@ -72,18 +185,74 @@ impl Fetch {
             context: ContextInternal::Worker(context),
             request: Some(request),
             inner: None,
+            options: FetchOptions::default(),
             abort_controller: None,
+            timeout_signal: None,
+            abort_forwards: Vec::new(),
         }
     }
+
+    /// Sets the options for this fetch.
+    ///
+    /// The options only take effect if they are set before the fetch is first polled.
+    pub fn with_options(mut self, options: FetchOptions) -> Self {
+        self.options = options;
+
+        self
+    }
+
+    fn error(&self, error: JsValue) -> FetchError {
+        if let Some(abort_controller) = &self.abort_controller {
+            let signal = abort_controller.signal();
+
+            if signal.aborted() {
+                let reason = signal.reason();
+
+                if let Some(timeout_signal) = &self.timeout_signal {
+                    if aborted_with(timeout_signal, &reason) {
+                        return FetchError::TimedOut;
+                    }
+                }
+
+                if let Some(user_signal) = &self.options.signal {
+                    if aborted_with(user_signal.as_ref(), &reason) {
+                        return FetchError::Aborted(AbortReason::from_js(reason));
+                    }
+                }
+            }
+        }
+
+        FetchError::Network(NetworkError::new(error.unchecked_into()))
+    }
 }
 
 impl Future for Fetch {
-    type Output = Result<Response, NetworkError>;
+    type Output = Result<Response, FetchError>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         // Initialize if uninitialized
         if let Some(request) = self.request.take() {
             let abort_controller = web_sys::AbortController::new().unwrap_throw();
+
+            // Rather than combining signals with `AbortSignal.any`, which is not yet supported
+            // everywhere, forward the user signal and the timeout signal to the internal abort
+            // controller.
+            if let Some(signal) = &self.options.signal {
+                let forward = AbortForward::new(signal.as_ref(), &abort_controller);
+
+                self.abort_forwards.extend(forward);
+            }
+
+            if let Duration::Milliseconds(_) = self.options.timeout {
+                let timeout_signal = AbortSignal::timeout(self.options.timeout);
+                let timeout_signal: &web_sys::AbortSignal = timeout_signal.as_ref();
+                let timeout_signal = timeout_signal.clone();
+                let forward = AbortForward::new(&timeout_signal, &abort_controller);
+
+                self.abort_forwards.extend(forward);
+                self.timeout_signal = Some(timeout_signal);
+            }
+
             let inner = self.context.fetch(&request, &abort_controller);
 
             self.inner = Some(inner);
@@ -94,13 +263,16 @@ impl Future for Fetch {
 
         let poll_result = inner.poll(cx);
 
+        let poll_result = poll_result
+            .map_ok(|v| Response::from(v.unchecked_into::<web_sys::Response>()))
+            .map_err(|e| self.error(e));
+
         if poll_result.is_ready() {
             self.abort_controller = None;
+            self.abort_forwards.clear();
         }
 
         poll_result
-            .map_ok(|v| Response::from(v.unchecked_into::<web_sys::Response>()))
-            .map_err(|e| NetworkError::new(e.unchecked_into()))
     }
 }
 
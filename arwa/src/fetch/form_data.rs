@@ -0,0 +1,209 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+use crate::file::{Blob, File};
+use crate::html::{FormSubmitterElement, HtmlFormElement};
+use crate::{impl_common_wrapper_traits, impl_js_cast};
+
+// The `submitter` argument to the `FormData` constructor is not yet available in web-sys.
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::FormData, js_name = FormData)]
+    type FormDataWithSubmitter;
+
+    #[wasm_bindgen(constructor, js_class = "FormData", catch)]
+    fn new(
+        form: &web_sys::HtmlFormElement,
+        submitter: &web_sys::HtmlElement,
+    ) -> Result<FormDataWithSubmitter, JsValue>;
+}
+
+/// A value in a [FormData] set.
+#[derive(Clone)]
+pub enum FormDataValue {
+    String(String),
+    File(File),
+}
+
+impl FormDataValue {
+    fn from_js(value: JsValue) -> Self {
+        if let Some(string) = value.as_string() {
+            FormDataValue::String(string)
+        } else {
+            FormDataValue::File(File::from(value.unchecked_into::<web_sys::File>()))
+        }
+    }
+}
+
+/// A set of name/value pairs representing form fields, which may be used as the body of a request
+/// or response (see [BodySource::FormData]), in which case it is encoded as `multipart/form-data`.
+///
+/// [BodySource::FormData]: crate::fetch::BodySource::FormData
+#[derive(Clone)]
+pub struct FormData {
+    inner: web_sys::FormData,
+}
+
+impl FormData {
+    pub fn new() -> Self {
+        FormData::from(web_sys::FormData::new().unwrap_throw())
+    }
+
+    /// Creates a new form data set from the current values of the fields in the `form`.
+    pub fn from_form(form: &HtmlFormElement) -> Self {
+        FormData::from(web_sys::FormData::new_with_form(form.as_ref()).unwrap_throw())
+    }
+
+    /// Creates a new form data set from the current values of the fields in the `form`, as if the
+    /// form was submitted by the `submitter`.
+    ///
+    /// If the `submitter` has a name, then its name and value are included in the form data set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `submitter` is not a submit button of the `form`.
+    pub fn from_form_with_submitter<S>(form: &HtmlFormElement, submitter: &S) -> Self
+    where
+        S: FormSubmitterElement,
+    {
+        let inner = FormDataWithSubmitter::new(form.as_ref(), submitter.as_web_sys_html_element())
+            .expect_throw("submitter is not a submit button of the form");
+
+        FormData::from(inner.unchecked_into::<web_sys::FormData>())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.inner.has(name)
+    }
+
+    /// Returns the first value associated with the `name`, or `None` if there is no such value.
+    pub fn get(&self, name: &str) -> Option<FormDataValue> {
+        let value = self.inner.get(name);
+
+        if value.is_null() {
+            None
+        } else {
+            Some(FormDataValue::from_js(value))
+        }
+    }
+
+    /// Returns all values associated with the `name`.
+    pub fn get_all(&self, name: &str) -> Vec<FormDataValue> {
+        self.inner
+            .get_all(name)
+            .iter()
+            .map(FormDataValue::from_js)
+            .collect()
+    }
+
+    pub fn append(&self, name: &str, value: &str) {
+        self.inner.append_with_str(name, value).unwrap_throw();
+    }
+
+    /// Appends the `blob` as a file with the given `file_name`.
+    ///
+    /// If no `file_name` is given, the file name will be `"blob"`.
+    pub fn append_blob(&self, name: &str, blob: &Blob, file_name: Option<&str>) {
+        if let Some(file_name) = file_name {
+            self.inner
+                .append_with_blob_and_filename(name, blob.as_ref(), file_name)
+                .unwrap_throw();
+        } else {
+            self.inner
+                .append_with_blob(name, blob.as_ref())
+                .unwrap_throw();
+        }
+    }
+
+    pub fn append_file(&self, name: &str, file: &File) {
+        let file: &web_sys::File = file.as_ref();
+
+        self.inner.append_with_blob(name, file).unwrap_throw();
+    }
+
+    /// Replaces all values associated with the `name` with the given `value`.
+    pub fn set(&self, name: &str, value: &str) {
+        self.inner.set_with_str(name, value).unwrap_throw();
+    }
+
+    /// Replaces all values associated with the `name` with the `blob`, as a file with the given
+    /// `file_name`.
+    ///
+    /// If no `file_name` is given, the file name will be `"blob"`.
+    pub fn set_blob(&self, name: &str, blob: &Blob, file_name: Option<&str>) {
+        if let Some(file_name) = file_name {
+            self.inner
+                .set_with_blob_and_filename(name, blob.as_ref(), file_name)
+                .unwrap_throw();
+        } else {
+            self.inner.set_with_blob(name, blob.as_ref()).unwrap_throw();
+        }
+    }
+
+    /// Replaces all values associated with the `name` with the `file`.
+    pub fn set_file(&self, name: &str, file: &File) {
+        let file: &web_sys::File = file.as_ref();
+
+        self.inner.set_with_blob(name, file).unwrap_throw();
+    }
+
+    /// Removes all values associated with the `name`.
+    pub fn remove(&self, name: &str) {
+        self.inner.delete(name);
+    }
+
+    /// Returns an iterator over all name/value pairs, in insertion order.
+    pub fn iter(&self) -> FormDataIter {
+        FormDataIter {
+            inner: self.inner.entries().into_iter(),
+        }
+    }
+}
+
+impl Default for FormData {
+    fn default() -> Self {
+        FormData::new()
+    }
+}
+
+impl From<web_sys::FormData> for FormData {
+    fn from(inner: web_sys::FormData) -> Self {
+        FormData { inner }
+    }
+}
+
+impl AsRef<web_sys::FormData> for FormData {
+    fn as_ref(&self) -> &web_sys::FormData {
+        &self.inner
+    }
+}
+
+impl_common_wrapper_traits!(FormData);
+impl_js_cast!(FormData);
+
+impl IntoIterator for &FormData {
+    type Item = (String, FormDataValue);
+    type IntoIter = FormDataIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct FormDataIter {
+    inner: js_sys::IntoIter,
+}
+
+impl Iterator for FormDataIter {
+    type Item = (String, FormDataValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| {
+            let entry: Array = entry.unwrap_throw().unchecked_into();
+            let name = entry.get(0).as_string().unwrap_throw();
+
+            (name, FormDataValue::from_js(entry.get(1)))
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::console::{Argument, ToArgument};
+use crate::fetch::InvalidHeaderName;
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct StaticallyParsedHeaderName {
+    #[doc(hidden)]
+    pub header_name: &'static str,
+}
+
+impl AsRef<str> for StaticallyParsedHeaderName {
+    fn as_ref(&self) -> &str {
+        self.header_name
+    }
+}
+
+#[derive(Clone)]
+enum HeaderNameInternal {
+    Static(StaticallyParsedHeaderName),
+    Dynamic(arwa_parse::header::HeaderName),
+}
+
+/// The name of an HTTP header.
+///
+/// Header names are case-insensitive and are always normalized to lower case.
+#[derive(Clone)]
+pub struct HeaderName {
+    internal: HeaderNameInternal,
+}
+
+impl HeaderName {
+    pub const ACCEPT: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "accept",
+        }),
+    };
+
+    pub const ACCEPT_LANGUAGE: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "accept-language",
+        }),
+    };
+
+    pub const AUTHORIZATION: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "authorization",
+        }),
+    };
+
+    pub const CACHE_CONTROL: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "cache-control",
+        }),
+    };
+
+    pub const CONTENT_ENCODING: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "content-encoding",
+        }),
+    };
+
+    pub const CONTENT_LENGTH: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "content-length",
+        }),
+    };
+
+    pub const CONTENT_TYPE: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "content-type",
+        }),
+    };
+
+    pub const ETAG: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "etag",
+        }),
+    };
+
+    pub const IF_MODIFIED_SINCE: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "if-modified-since",
+        }),
+    };
+
+    pub const IF_NONE_MATCH: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "if-none-match",
+        }),
+    };
+
+    pub const LAST_MODIFIED: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "last-modified",
+        }),
+    };
+
+    pub const RETRY_AFTER: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "retry-after",
+        }),
+    };
+
+    pub const SET_COOKIE: HeaderName = HeaderName {
+        internal: HeaderNameInternal::Static(StaticallyParsedHeaderName {
+            header_name: "set-cookie",
+        }),
+    };
+
+    pub fn parse(header_name: &str) -> Result<Self, InvalidHeaderName> {
+        arwa_parse::header::HeaderName::parse(header_name)
+            .map(|header_name| HeaderName {
+                internal: HeaderNameInternal::Dynamic(header_name),
+            })
+            .map_err(|err| InvalidHeaderName::new(js_sys::TypeError::new(&err.to_string())))
+    }
+
+    #[doc(hidden)]
+    pub const fn from_statically_parsed(header_name: StaticallyParsedHeaderName) -> Self {
+        HeaderName {
+            internal: HeaderNameInternal::Static(header_name),
+        }
+    }
+
+    pub(crate) fn trusted(header_name: String) -> Self {
+        HeaderName {
+            internal: HeaderNameInternal::Dynamic(arwa_parse::header::HeaderName::trusted(
+                header_name,
+            )),
+        }
+    }
+}
+
+impl AsRef<str> for HeaderName {
+    fn as_ref(&self) -> &str {
+        match &self.internal {
+            HeaderNameInternal::Static(header_name) => header_name.as_ref(),
+            HeaderNameInternal::Dynamic(header_name) => header_name.as_ref(),
+        }
+    }
+}
+
+impl ToArgument for HeaderName {
+    fn to_argument(&self) -> Argument<'_> {
+        let as_str: &str = self.as_ref();
+
+        ToArgument::to_argument(as_str)
+    }
+}
+
+impl PartialEq for HeaderName {
+    fn eq(&self, other: &Self) -> bool {
+        let self_as_str: &str = self.as_ref();
+        let other_as_str: &str = other.as_ref();
+
+        self_as_str == other_as_str
+    }
+}
+
+impl PartialEq<str> for HeaderName {
+    fn eq(&self, other: &str) -> bool {
+        let self_as_str: &str = self.as_ref();
+
+        self_as_str.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&'_ str> for HeaderName {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<HeaderName> for &'_ str {
+    #[inline]
+    fn eq(&self, other: &HeaderName) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<HeaderName> for str {
+    fn eq(&self, other: &HeaderName) -> bool {
+        other == self
+    }
+}
+
+impl fmt::Display for HeaderName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str: &str = self.as_ref();
+
+        fmt::Display::fmt(as_str, f)
+    }
+}
+
+impl fmt::Debug for HeaderName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str: &str = self.as_ref();
+
+        fmt::Debug::fmt(as_str, f)
+    }
+}
@@ -1,7 +1,21 @@
+pub use arwa_parse::header::{CacheControl, EntityTag};
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
 use wasm_bindgen::{throw_val, JsCast, UnwrapThrowExt};
 
+use crate::fetch::HeaderName;
+use crate::media_type::MediaType;
 use crate::{impl_common_wrapper_traits, impl_js_cast, type_error_wrapper};
 
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::Headers)]
+    type HeadersWithSetCookie;
+
+    #[wasm_bindgen(method, js_name = getSetCookie)]
+    fn get_set_cookie(this: &HeadersWithSetCookie) -> Array;
+}
+
 type_error_wrapper!(InvalidHeaderName);
 
 #[derive(Clone)]
@@ -14,51 +28,192 @@ impl Headers {
         Headers::from(web_sys::Headers::new().unwrap_throw())
     }
 
-    pub fn contains_key(&self, header_name: &str) -> bool {
-        self.inner.has(header_name).unwrap_or(false)
+    pub fn contains_key(&self, header_name: &HeaderName) -> bool {
+        self.inner.has(header_name.as_ref()).unwrap_or(false)
     }
 
-    pub fn get(&self, header_name: &str) -> Option<String> {
-        self.inner.get(header_name).ok().flatten()
+    pub fn get(&self, header_name: &HeaderName) -> Option<String> {
+        self.inner.get(header_name.as_ref()).ok().flatten()
     }
 
-    pub fn set(&self, header_name: &str, value: &str) {
-        if let Err(err) = self.inner.set(header_name, value) {
+    pub fn set(&self, header_name: &HeaderName, value: &str) {
+        if let Err(err) = self.inner.set(header_name.as_ref(), value) {
             throw_val(err)
         }
     }
 
-    pub fn try_set(&self, header_name: &str, value: &str) -> Result<(), InvalidHeaderName> {
+    pub fn try_set(&self, header_name: &HeaderName, value: &str) -> Result<(), InvalidHeaderName> {
         self.inner
-            .set(header_name, value)
+            .set(header_name.as_ref(), value)
             .map_err(|err| InvalidHeaderName::new(err.unchecked_into()))
     }
 
-    pub fn append(&self, header_name: &str, value: &str) {
-        if let Err(err) = self.inner.append(header_name, value) {
+    pub fn append(&self, header_name: &HeaderName, value: &str) {
+        if let Err(err) = self.inner.append(header_name.as_ref(), value) {
             throw_val(err)
         }
     }
 
-    pub fn try_append(&self, header_name: &str, value: &str) -> Result<(), InvalidHeaderName> {
+    pub fn try_append(
+        &self,
+        header_name: &HeaderName,
+        value: &str,
+    ) -> Result<(), InvalidHeaderName> {
         self.inner
-            .append(header_name, value)
+            .append(header_name.as_ref(), value)
             .map_err(|err| InvalidHeaderName::new(err.unchecked_into()))
     }
 
-    pub fn remove(&self, header_name: &str) {
-        if let Err(err) = self.inner.delete(header_name) {
+    pub fn remove(&self, header_name: &HeaderName) {
+        if let Err(err) = self.inner.delete(header_name.as_ref()) {
             throw_val(err)
         }
     }
 
-    pub fn try_remove(&self, header_name: &str) -> Result<(), InvalidHeaderName> {
+    pub fn try_remove(&self, header_name: &HeaderName) -> Result<(), InvalidHeaderName> {
         self.inner
-            .delete(header_name)
+            .delete(header_name.as_ref())
             .map_err(|err| InvalidHeaderName::new(err.unchecked_into()))
     }
 
-    // TODO: iterators currently not in web-sys
+    /// Returns an iterator over all header names and values, ordered by name.
+    ///
+    /// Names are lower case. Values for headers that occur more than once are combined into a
+    /// single comma-separated value, except for `Set-Cookie` headers, which are returned
+    /// separately.
+    pub fn iter(&self) -> HeadersIter {
+        HeadersIter {
+            inner: js_sys::try_iter(self.inner.as_ref())
+                .unwrap_throw()
+                .unwrap_throw(),
+        }
+    }
+
+    /// Returns an iterator over all header names, ordered by name.
+    ///
+    /// See also [iter](Headers::iter).
+    pub fn keys(&self) -> HeadersKeys {
+        HeadersKeys { inner: self.iter() }
+    }
+
+    /// Returns an iterator over all header values, ordered by name.
+    ///
+    /// See also [iter](Headers::iter).
+    pub fn values(&self) -> HeadersValues {
+        HeadersValues { inner: self.iter() }
+    }
+
+    /// Returns all values for the given header.
+    ///
+    /// The browser combines multiple values for the same header into a single comma-separated
+    /// value. For headers that are defined as comma-separated lists (e.g. `Accept-Encoding` or
+    /// `Vary`), the combined value is split on commas that are not inside a quoted string. Values
+    /// for all other headers may themselves contain commas (e.g. the date in an `Expires` header),
+    /// so the combined value is returned as a single element. `Set-Cookie` headers are handled as
+    /// with [get_set_cookie](Headers::get_set_cookie).
+    pub fn get_all(&self, header_name: &HeaderName) -> Vec<String> {
+        if header_name == &HeaderName::SET_COOKIE {
+            return self.get_set_cookie();
+        }
+
+        let Some(value) = self.get(header_name) else {
+            return Vec::new();
+        };
+
+        if arwa_parse::header::is_list_header(header_name.as_ref()) {
+            arwa_parse::header::split_list(&value)
+                .into_iter()
+                .map(|value| value.to_string())
+                .collect()
+        } else {
+            vec![value]
+        }
+    }
+
+    /// Returns the values of all `Set-Cookie` headers.
+    ///
+    /// Note that `Set-Cookie` headers are never exposed on the headers of a response received by
+    /// a fetch; these headers are only present on headers that were constructed locally.
+    ///
+    /// Browsers that do not support retrieving the individual `Set-Cookie` values only expose the
+    /// combined value, which cannot reliably be split (cookie values may contain commas); in such
+    /// browsers the combined value is returned as a single element.
+    pub fn get_set_cookie(&self) -> Vec<String> {
+        let supports_get_set_cookie =
+            Reflect::has(self.inner.as_ref(), &"getSetCookie".into()).unwrap_or(false);
+
+        if !supports_get_set_cookie {
+            return self.get(&HeaderName::SET_COOKIE).into_iter().collect();
+        }
+
+        self.inner
+            .unchecked_ref::<HeadersWithSetCookie>()
+            .get_set_cookie()
+            .iter()
+            .map(|value| value.as_string().unwrap_throw())
+            .collect()
+    }
+
+    /// Returns the value of the `Content-Type` header, or `None` if the header is not present or
+    /// is not a valid media type.
+    pub fn content_type(&self) -> Option<MediaType> {
+        let value = self.get(&HeaderName::CONTENT_TYPE)?;
+
+        MediaType::parse(&value).ok()
+    }
+
+    /// Returns the value of the `Content-Length` header, or `None` if the header is not present or
+    /// is not a valid length.
+    pub fn content_length(&self) -> Option<u64> {
+        self.get(&HeaderName::CONTENT_LENGTH)?.trim().parse().ok()
+    }
+
+    /// Returns the directives of the `Cache-Control` header, or `None` if the header is not
+    /// present.
+    pub fn cache_control(&self) -> Option<CacheControl> {
+        self.get(&HeaderName::CACHE_CONTROL)
+            .map(|value| CacheControl::parse(&value))
+    }
+
+    /// Returns the value of the `ETag` header, or `None` if the header is not present or is not a
+    /// valid entity tag.
+    pub fn etag(&self) -> Option<EntityTag> {
+        EntityTag::parse(&self.get(&HeaderName::ETAG)?)
+    }
+
+    /// Returns the value of the `Last-Modified` header as the number of milliseconds since the
+    /// Unix epoch, or `None` if the header is not present or is not a valid date.
+    pub fn last_modified(&self) -> Option<u64> {
+        let value = self.get(&HeaderName::LAST_MODIFIED)?;
+        let time = js_sys::Date::parse(&value);
+
+        if time.is_nan() || time < 0.0 {
+            None
+        } else {
+            Some(time as u64)
+        }
+    }
+
+    /// Returns the media ranges in the `Accept` header with their quality values, ordered by
+    /// descending quality.
+    ///
+    /// Media ranges with equal quality keep the order in which they occur in the header. Media
+    /// ranges that are not valid media types, or that have an invalid quality value, are ignored.
+    /// Returns an empty `Vec` if the header is not present.
+    pub fn accept(&self) -> Vec<(MediaType, f32)> {
+        let Some(value) = self.get(&HeaderName::ACCEPT) else {
+            return Vec::new();
+        };
+
+        arwa_parse::header::parse_quality_list(&value)
+            .into_iter()
+            .filter_map(|item| {
+                MediaType::parse(&item.value)
+                    .ok()
+                    .map(|media_type| (media_type, item.quality))
+            })
+            .collect()
+    }
 }
 
 impl From<web_sys::Headers> for Headers {
@@ -75,3 +230,55 @@ impl AsRef<web_sys::Headers> for Headers {
 
 impl_common_wrapper_traits!(Headers);
 impl_js_cast!(Headers);
+
+impl IntoIterator for &Headers {
+    type Item = (HeaderName, String);
+    type IntoIter = HeadersIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct HeadersIter {
+    inner: js_sys::IntoIter,
+}
+
+impl Iterator for HeadersIter {
+    type Item = (HeaderName, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| {
+            let entry: Array = entry.unwrap_throw().unchecked_into();
+            let name = entry.get(0).as_string().unwrap_throw();
+            let value = entry.get(1).as_string().unwrap_throw();
+
+            // Header names are returned in lower case.
+            (HeaderName::trusted(name), value)
+        })
+    }
+}
+
+pub struct HeadersKeys {
+    inner: HeadersIter,
+}
+
+impl Iterator for HeadersKeys {
+    type Item = HeaderName;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(name, _)| name)
+    }
+}
+
+pub struct HeadersValues {
+    inner: HeadersIter,
+}
+
+impl Iterator for HeadersValues {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
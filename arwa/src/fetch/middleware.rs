@@ -0,0 +1,255 @@
+use futures::future::LocalBoxFuture;
+
+use crate::fetch::{
+    ClientError, ClientRequest, FetchError, HeaderName, Headers, Middleware, Next, Response, Status,
+};
+use crate::timer::{Duration, Timeout};
+use crate::url::Url;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Backoff {
+    Fixed,
+    Exponential,
+}
+
+/// Middleware that retries requests that failed with a network error or a timeout, or that
+/// received a response with a retryable status.
+///
+/// By default, responses with the statuses `408 Request Timeout`, `429 Too Many Requests`,
+/// `502 Bad Gateway`, `503 Service Unavailable` and `504 Gateway Timeout` are retried. If such a
+/// response has a `Retry-After` header, then the next attempt is delayed by the duration the
+/// header specifies (but never longer than the [max_delay](Retry::max_delay)), rather than by the
+/// backoff delay. The body of a response that is retried is cancelled.
+///
+/// Requests that were aborted by their [FetchOptions::signal] are never retried. By default, only
+/// requests with an idempotent method (`GET`, `HEAD`, `OPTIONS`, `TRACE`, `PUT` and `DELETE`) are
+/// retried, see [idempotent_only](Retry::idempotent_only).
+///
+/// [FetchOptions::signal]: crate::fetch::FetchOptions::signal
+#[derive(Clone, Debug)]
+pub struct Retry {
+    max_retries: u32,
+    backoff: Backoff,
+    delay: Duration,
+    max_delay: Duration,
+    statuses: Vec<Status>,
+    idempotent_only: bool,
+}
+
+impl Retry {
+    /// Retries a request at most `max_retries` times, waiting `delay` before the first retry and
+    /// doubling the delay for each subsequent retry.
+    pub fn exponential(max_retries: u32, delay: Duration) -> Self {
+        Retry::new(max_retries, Backoff::Exponential, delay)
+    }
+
+    /// Retries a request at most `max_retries` times, waiting `delay` before each retry.
+    pub fn fixed(max_retries: u32, delay: Duration) -> Self {
+        Retry::new(max_retries, Backoff::Fixed, delay)
+    }
+
+    fn new(max_retries: u32, backoff: Backoff, delay: Duration) -> Self {
+        Retry {
+            max_retries,
+            backoff,
+            delay,
+            max_delay: Duration::Milliseconds(30_000),
+            statuses: vec![
+                Status::REQUEST_TIMEOUT,
+                Status::TOO_MANY_REQUESTS,
+                Status::BAD_GATEWAY,
+                Status::SERVICE_UNAVAILABLE,
+                Status::GATEWAY_TIMEOUT,
+            ],
+            idempotent_only: true,
+        }
+    }
+
+    /// Sets the maximum delay between two attempts.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+
+        self
+    }
+
+    /// Replaces the response statuses that are retried.
+    pub fn statuses<I>(mut self, statuses: I) -> Self
+    where
+        I: IntoIterator<Item = Status>,
+    {
+        self.statuses = statuses.into_iter().collect();
+
+        self
+    }
+
+    /// Sets whether only requests with an idempotent method are retried.
+    ///
+    /// Defaults to `true`. Note that retrying a request with a non-idempotent method (e.g. `POST`)
+    /// may cause its effects to be applied more than once.
+    pub fn idempotent_only(mut self, idempotent_only: bool) -> Self {
+        self.idempotent_only = idempotent_only;
+
+        self
+    }
+
+    fn is_retryable_status(&self, status: u16) -> bool {
+        self.statuses
+            .iter()
+            .any(|retryable| u16::from(*retryable) == status)
+    }
+
+    fn backoff_delay(&self, retry: u32) -> Duration {
+        let delay = match (self.backoff, self.delay) {
+            (_, Duration::Infinity) => Duration::Infinity,
+            (Backoff::Fixed, delay) => delay,
+            (Backoff::Exponential, Duration::Milliseconds(milliseconds)) => {
+                let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+
+                Duration::Milliseconds(milliseconds.saturating_mul(factor))
+            }
+        };
+
+        min_duration(delay, self.max_delay)
+    }
+}
+
+fn min_duration(a: Duration, b: Duration) -> Duration {
+    match (a, b) {
+        (Duration::Infinity, b) => b,
+        (a, Duration::Infinity) => a,
+        (Duration::Milliseconds(a), Duration::Milliseconds(b)) => Duration::Milliseconds(a.min(b)),
+    }
+}
+
+fn is_idempotent(method: &str) -> bool {
+    matches!(
+        method.to_ascii_uppercase().as_str(),
+        "GET" | "HEAD" | "OPTIONS" | "TRACE" | "PUT" | "DELETE"
+    )
+}
+
+fn cancel_body(response: &Response) {
+    let response: &web_sys::Response = response.as_ref();
+
+    if let Some(body) = response.body() {
+        if !body.locked() {
+            // The stream is not locked, so cancelling it cannot fail.
+            let _ = body.cancel();
+        }
+    }
+}
+
+// Returns the delay specified by a `Retry-After` header, which is either a number of seconds or an
+// HTTP date.
+fn retry_after(headers: &Headers) -> Option<Duration> {
+    let value = headers.get(&HeaderName::RETRY_AFTER)?;
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u32>() {
+        return Some(Duration::Milliseconds(seconds.saturating_mul(1000)));
+    }
+
+    let time = js_sys::Date::parse(value);
+
+    if time.is_nan() {
+        None
+    } else {
+        let delay = (time - js_sys::Date::now()).max(0.0).ceil();
+
+        Some(Duration::Milliseconds(delay.min(u32::MAX as f64) as u32))
+    }
+}
+
+impl Middleware for Retry {
+    fn handle<'a>(
+        &'a self,
+        request: ClientRequest,
+        next: Next,
+    ) -> LocalBoxFuture<'a, Result<Response, ClientError>> {
+        Box::pin(async move {
+            if self.idempotent_only && !is_idempotent(request.method.as_ref()) {
+                return next.run(request).await;
+            }
+
+            let mut retry = 0;
+
+            loop {
+                let result = next.clone().run(request.clone()).await;
+
+                if retry >= self.max_retries {
+                    return result;
+                }
+
+                let delay = match &result {
+                    Ok(response) if self.is_retryable_status(response.status()) => {
+                        // The response is discarded, so release its connection rather than leaving
+                        // the body stream open.
+                        cancel_body(response);
+
+                        match retry_after(&response.headers()) {
+                            Some(delay) => min_duration(delay, self.max_delay),
+                            None => self.backoff_delay(retry),
+                        }
+                    }
+                    Err(ClientError::Fetch(FetchError::Network(_) | FetchError::TimedOut)) => {
+                        self.backoff_delay(retry)
+                    }
+                    _ => return result,
+                };
+
+                Timeout::global_context(delay).await;
+
+                retry += 1;
+            }
+        })
+    }
+}
+
+/// Middleware that adds headers to every request that does not already specify them.
+#[derive(Clone)]
+pub struct DefaultHeaders(pub Headers);
+
+impl Middleware for DefaultHeaders {
+    fn handle<'a>(
+        &'a self,
+        request: ClientRequest,
+        next: Next,
+    ) -> LocalBoxFuture<'a, Result<Response, ClientError>> {
+        let DefaultHeaders(defaults) = self;
+
+        for (name, value) in defaults {
+            if !request.headers.contains_key(&name) {
+                request.headers.append(&name, &value);
+            }
+        }
+
+        next.run(request)
+    }
+}
+
+/// Middleware that resolves the URL of every request against a base URL.
+///
+/// Absolute request URLs are left unchanged.
+#[derive(Clone)]
+pub struct BaseUrl(pub Url);
+
+impl Middleware for BaseUrl {
+    fn handle<'a>(
+        &'a self,
+        mut request: ClientRequest,
+        next: Next,
+    ) -> LocalBoxFuture<'a, Result<Response, ClientError>> {
+        let BaseUrl(base) = self;
+
+        match Url::parse_with_base(&request.url, base) {
+            Ok(url) => {
+                request.url = url.to_string();
+
+                next.run(request)
+            }
+            Err(err) => Box::pin(async move { Err(ClientError::InvalidUrl(err)) }),
+        }
+    }
+}
@@ -2,18 +2,35 @@
 // currently sure how that interacts with potential WASM threads. Might have to move these to
 // FetchContext.
 
+pub use arwa_macro::header_name;
+
 mod body;
 pub use self::body::*;
 
 mod cache_context;
 pub use self::cache_context::*;
 
+mod client;
+pub use self::client::*;
+
 mod fetch_context;
 pub use self::fetch_context::*;
 
+mod form_data;
+pub use self::form_data::*;
+
+mod header_name;
+pub use self::header_name::*;
+
 mod headers;
 pub use self::headers::*;
 
+mod middleware;
+pub use self::middleware::*;
+
+mod progress;
+pub use self::progress::*;
+
 mod request_method;
 pub use self::request_method::*;
 
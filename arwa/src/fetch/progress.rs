@@ -0,0 +1,244 @@
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures::Stream;
+use js_sys::Uint8Array;
+use wasm_bindgen::JsValue;
+
+use crate::stream::{
+    default_readable_stream_start, readable_stream_seal, CustomReadableStream, QueuingStrategy,
+    ReadableStream, ReadableStreamChunks, ReadableStreamController,
+    ReadableStreamDefaultController, ReadableStreamError, ReadableStreamSource,
+};
+
+// The size of the chunks into which an upload body is split.
+const UPLOAD_CHUNK_SIZE: u32 = 64 * 1024;
+
+/// The progress of a body transfer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Progress {
+    /// The number of bytes transferred so far.
+    pub transferred: u64,
+
+    /// The total number of bytes in the body, or `None` if the total is not known.
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// Returns the fraction of the body that has been transferred, in the range `0.0..=1.0`, or
+    /// `None` if the total is not known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total.map(|total| {
+            if total == 0 {
+                1.0
+            } else {
+                (self.transferred as f64 / total as f64).min(1.0)
+            }
+        })
+    }
+}
+
+struct ResponseBodyReadableStream {
+    inner: web_sys::ReadableStream,
+}
+
+impl readable_stream_seal::Seal for ResponseBodyReadableStream {
+    fn as_web_sys(&self) -> &web_sys::ReadableStream {
+        &self.inner
+    }
+
+    fn from_web_sys(inner: web_sys::ReadableStream) -> Self
+    where
+        Self: Sized,
+    {
+        ResponseBodyReadableStream { inner }
+    }
+}
+
+impl ReadableStream for ResponseBodyReadableStream {
+    type Chunk = Uint8Array;
+    type Error = JsValue;
+    type Reason = JsValue;
+}
+
+/// Streams the chunks of a response body, along with the [Progress] of the download.
+///
+/// See [Response::body_stream_with_progress].
+///
+/// [Response::body_stream_with_progress]: crate::fetch::Response::body_stream_with_progress
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct ResponseBodyProgress {
+    chunks: ReadableStreamChunks<Uint8Array, JsValue, JsValue>,
+    received: u64,
+    total: Option<u64>,
+}
+
+impl ResponseBodyProgress {
+    pub(crate) fn new(body: web_sys::ReadableStream, total: Option<u64>) -> Self {
+        ResponseBodyProgress {
+            chunks: ResponseBodyReadableStream { inner: body }.into_chunks(),
+            received: 0,
+            total,
+        }
+    }
+}
+
+impl Stream for ResponseBodyProgress {
+    type Item = Result<(Uint8Array, Progress), ReadableStreamError<JsValue>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        Pin::new(&mut this.chunks).poll_next(cx).map(|next| {
+            next.map(|result| {
+                result.map(|chunk| {
+                    this.received += chunk.length() as u64;
+
+                    let progress = Progress {
+                        transferred: this.received,
+                        total: this.total,
+                    };
+
+                    (chunk, progress)
+                })
+            })
+        })
+    }
+}
+
+struct UploadState {
+    sent: u64,
+    total: u64,
+    reported: Option<u64>,
+    cancelled: bool,
+    waker: Option<Waker>,
+}
+
+impl UploadState {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A request body that is streamed in chunks, such that the [Progress] of the upload can be
+/// observed.
+///
+/// Use as a body with [BodySource::Upload]; use [progress](UploadBody::progress) to observe how
+/// many bytes have been sent. Bytes count as sent when they are handed to the browser's network
+/// stack.
+///
+/// Note that streaming request bodies are not supported by all browsers, and typically require an
+/// HTTP/2 connection.
+///
+/// [BodySource::Upload]: crate::fetch::BodySource::Upload
+pub struct UploadBody {
+    stream: CustomReadableStream<Uint8Array>,
+    state: Rc<RefCell<UploadState>>,
+}
+
+impl UploadBody {
+    /// Creates a new upload body from a copy of the `bytes`.
+    pub fn new(bytes: &[u8]) -> Self {
+        let bytes = Uint8Array::from(bytes);
+        let len = bytes.length();
+        let state = Rc::new(RefCell::new(UploadState {
+            sent: 0,
+            total: len as u64,
+            reported: None,
+            cancelled: false,
+            waker: None,
+        }));
+
+        let mut offset = 0;
+        let pull_state = state.clone();
+        let pull = move |controller: ReadableStreamDefaultController<Uint8Array>| {
+            if offset < len {
+                let end = offset.saturating_add(UPLOAD_CHUNK_SIZE).min(len);
+
+                controller.enqueue(bytes.subarray(offset, end));
+
+                offset = end;
+
+                let mut state = pull_state.borrow_mut();
+
+                state.sent = offset as u64;
+                state.wake();
+            }
+
+            if offset == len {
+                controller.close();
+            }
+        };
+
+        let cancel_state = state.clone();
+        let cancel = move |_reason: JsValue| {
+            let mut state = cancel_state.borrow_mut();
+
+            state.cancelled = true;
+            state.wake();
+        };
+
+        // A high water mark of `0` ensures chunks are only pulled when the consumer reads them,
+        // rather than buffered ahead of time.
+        let stream = CustomReadableStream::from_source(
+            ReadableStreamSource {
+                start: default_readable_stream_start,
+                pull,
+                cancel,
+            },
+            QueuingStrategy::count(0),
+        );
+
+        UploadBody { stream, state }
+    }
+
+    /// Returns a stream of the [Progress] of this upload.
+    ///
+    /// The stream yields a new progress value whenever a chunk has been sent, and ends when the
+    /// whole body has been sent, or when the upload is cancelled. Only the most recently polled
+    /// progress stream is notified of new progress.
+    pub fn progress(&self) -> UploadProgress {
+        UploadProgress {
+            state: self.state.clone(),
+        }
+    }
+
+    pub(crate) fn as_web_sys_stream(&self) -> &web_sys::ReadableStream {
+        readable_stream_seal::Seal::as_web_sys(&self.stream)
+    }
+}
+
+/// Stream returned by [UploadBody::progress].
+#[must_use = "streams do nothing unless polled or spawned"]
+pub struct UploadProgress {
+    state: Rc<RefCell<UploadState>>,
+}
+
+impl Stream for UploadProgress {
+    type Item = Progress;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.borrow_mut();
+
+        if state.reported != Some(state.sent) {
+            state.reported = Some(state.sent);
+
+            return Poll::Ready(Some(Progress {
+                transferred: state.sent,
+                total: Some(state.total),
+            }));
+        }
+
+        if state.sent == state.total || state.cancelled {
+            return Poll::Ready(None);
+        }
+
+        state.waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
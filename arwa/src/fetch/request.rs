@@ -390,6 +390,20 @@ fn create_request_internal(url: &str, descriptor: RequestDescriptor) -> Result<R
                 // byte slice).
                 init.body(Some(js_sys::Uint8Array::view(bytes).as_ref()))
             },
+            BodySource::FormData(form_data) => init.body(Some(form_data.as_ref())),
+            BodySource::UrlSearchParams(params) => init.body(Some(
+                web_sys::UrlSearchParams::new_with_str(&params.to_string())
+                    .unwrap_throw()
+                    .as_ref(),
+            )),
+            BodySource::Upload(upload) => {
+                // Streaming request bodies require half-duplex mode, which web-sys does not yet
+                // expose on `RequestInit`.
+                js_sys::Reflect::set(&init, &JsValue::from_str("duplex"), &"half".into())
+                    .unwrap_throw();
+
+                init.body(Some(upload.as_web_sys_stream()))
+            }
         };
     }
 
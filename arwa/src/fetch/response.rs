@@ -3,7 +3,7 @@ use std::mem;
 use delegate::delegate;
 use wasm_bindgen::{throw_val, JsCast, JsValue, UnwrapThrowExt};
 
-use crate::fetch::{Body, BodySource, Headers, Status};
+use crate::fetch::{Body, BodySource, HeaderName, Headers, ResponseBodyProgress, Status};
 use crate::url::Url;
 use crate::{impl_common_wrapper_traits, impl_js_cast, type_error_wrapper};
 
@@ -111,6 +111,34 @@ impl Response {
     pub fn body(&self) -> Body {
         Body::response(Clone::clone(&self.inner))
     }
+
+    /// Returns a stream of the chunks of the body of this response, along with the [Progress] of
+    /// the download, or `None` if this response does not have a body.
+    ///
+    /// The total size of the body is taken from the `Content-Length` header. If the body has a
+    /// `Content-Encoding` (other than `identity`), then the `Content-Length` does not reflect the
+    /// size of the decoded body and the total is reported as `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body is already being read.
+    ///
+    /// [Progress]: crate::fetch::Progress
+    pub fn body_stream_with_progress(&self) -> Option<ResponseBodyProgress> {
+        let body = self.inner.body()?;
+        let headers = self.headers();
+        let encoded = headers
+            .get(&HeaderName::CONTENT_ENCODING)
+            .map(|encoding| !encoding.trim().eq_ignore_ascii_case("identity"))
+            .unwrap_or(false);
+        let total = if encoded {
+            None
+        } else {
+            headers.content_length()
+        };
+
+        Some(ResponseBodyProgress::new(body, total))
+    }
 }
 
 impl From<web_sys::Response> for Response {
@@ -162,6 +190,18 @@ fn create_response_internal(descriptor: ResponseDescriptor) -> Result<Response,
 
                 web_sys::Response::new_with_opt_u8_array_and_init(Some(bytes), &init)
             }
+            BodySource::FormData(form_data) => {
+                web_sys::Response::new_with_opt_form_data_and_init(Some(form_data.as_ref()), &init)
+            }
+            BodySource::UrlSearchParams(params) => {
+                let params = web_sys::UrlSearchParams::new_with_str(&params.to_string())?;
+
+                web_sys::Response::new_with_opt_url_search_params_and_init(Some(&params), &init)
+            }
+            BodySource::Upload(upload) => web_sys::Response::new_with_opt_readable_stream_and_init(
+                Some(upload.as_web_sys_stream()),
+                &init,
+            ),
         }
     } else {
         web_sys::Response::new_with_opt_str_and_init(None, &init)
@@ -1,6 +1,11 @@
 use wasm_bindgen::{throw_val, JsCast, JsValue};
 
 use crate::security::SecurityError;
+use crate::structured_clone::{
+    deserialize, new_data_clone_error, serialize, DeserializeError, FromStructuredClone,
+    StructuredClone,
+};
+use crate::url::Url;
 use crate::{dom_exception_wrapper, impl_common_wrapper_traits, impl_js_cast};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -67,6 +72,24 @@ impl History {
             .map_err(|err| SecurityError::new(err.unchecked_into()))
     }
 
+    /// Deserializes the state of the current session history entry as a `S`.
+    ///
+    /// See [FromStructuredClone] for details. Entries that were not given a state have a `null`
+    /// state; use an [Option] for `S` if the current entry may not have a state.
+    ///
+    /// Returns a [DeserializeError] if the state does not match the representation expected by
+    /// `S`. If the document is not fully active, then the browser's `SecurityError` exception is
+    /// rethrown; see [try_get_state](History::try_get_state) to handle this case.
+    pub fn get_state_as<S>(&self) -> Result<S, DeserializeError>
+    where
+        S: FromStructuredClone,
+    {
+        match self.inner.state() {
+            Ok(state) => deserialize(state),
+            Err(err) => throw_val(err),
+        }
+    }
+
     pub fn get_scroll_restoration(&self) -> ScrollRestoration {
         let scroll_restoration = match self.inner.scroll_restoration() {
             Ok(scroll_restoration) => scroll_restoration,
@@ -140,24 +163,73 @@ impl History {
             .map_err(|e| SecurityError::new(e.unchecked_into()))
     }
 
-    // TODO: same as with `postMessage`. There's arguably potential to leverage the type system to
-    // statically avoid serialization errors at runtime. Should we?
-    //
-    // fn push_state(&self, state: &JsValue, url: Option<&Url>) {
-    //     self.inner.push_state_with_url(state, "", url.map(|url| url.as_ref())).unwrap_throw()
-    // }
-    //
-    // fn try_push_state(&self, state: &JsValue, url: Option<&Url>) -> Result<(), StateError> {
-    //     self.inner.push_state_with_url(state, "", url.map(|url| url.as_ref())).map_err(|err| StateError::new(err.unchecked_into()))
-    // }
-    //
-    // fn replace_state(&self, state: &JsValue, url: Option<&Url>) {
-    //     self.inner.replace_state_with_url(state, "", url.map(|url| url.as_ref())).unwrap_throw()
-    // }
-    //
-    // fn try_replace_state(&self, state: &JsValue, url: Option<&Url>) -> Result<(), StateError> {
-    //     self.inner.replace_state_with_url(state, "", url.map(|url| url.as_ref())).map_err(|err| StateError::new(err.unchecked_into()))
-    // }
+    /// Pushes a new entry onto the session history with the given `state` and `url`.
+    ///
+    /// The `state` is serialized with the [StructuredClone] data model; it may be retrieved again
+    /// with [get_state_as](History::get_state_as) or from the [PopStateEvent] that is dispatched
+    /// when the user navigates to the entry. The `url` must be same-origin with the document's
+    /// current URL.
+    ///
+    /// Throws a [StateError] if the state could not be serialized or the URL is not same-origin.
+    /// See [try_push_state](History::try_push_state) for a fallible alternative.
+    ///
+    /// [PopStateEvent]: crate::window::PopStateEvent
+    pub fn push_state<S>(&self, state: S, url: &Url)
+    where
+        S: StructuredClone,
+    {
+        if let Err(err) = self.push_state_internal(&state, url) {
+            throw_val(err)
+        }
+    }
+
+    pub fn try_push_state<S>(&self, state: S, url: &Url) -> Result<(), StateError>
+    where
+        S: StructuredClone,
+    {
+        self.push_state_internal(&state, url)
+            .map_err(|err| StateError::new(err.unchecked_into()))
+    }
+
+    /// Replaces the current entry in the session history with the given `state` and `url`.
+    ///
+    /// See [push_state](History::push_state) for details on the `state` and `url`.
+    pub fn replace_state<S>(&self, state: S, url: &Url)
+    where
+        S: StructuredClone,
+    {
+        if let Err(err) = self.replace_state_internal(&state, url) {
+            throw_val(err)
+        }
+    }
+
+    pub fn try_replace_state<S>(&self, state: S, url: &Url) -> Result<(), StateError>
+    where
+        S: StructuredClone,
+    {
+        self.replace_state_internal(&state, url)
+            .map_err(|err| StateError::new(err.unchecked_into()))
+    }
+
+    fn push_state_internal<S>(&self, state: &S, url: &Url) -> Result<(), JsValue>
+    where
+        S: StructuredClone,
+    {
+        let state = serialize_state(state)?;
+
+        self.inner
+            .push_state_with_url(&state, "", Some(url.as_ref()))
+    }
+
+    fn replace_state_internal<S>(&self, state: &S, url: &Url) -> Result<(), JsValue>
+    where
+        S: StructuredClone,
+    {
+        let state = serialize_state(state)?;
+
+        self.inner
+            .replace_state_with_url(&state, "", Some(url.as_ref()))
+    }
 }
 
 impl From<web_sys::History> for History {
@@ -175,6 +247,53 @@ impl AsRef<web_sys::History> for History {
 impl_common_wrapper_traits!(History);
 impl_js_cast!(History);
 
+fn serialize_state<S>(state: &S) -> Result<JsValue, JsValue>
+where
+    S: StructuredClone,
+{
+    let result = serialize(state).and_then(|message| {
+        // The session history has no transfer list; objects can only be copied.
+        if message.transfer_list.length() > 0 {
+            message.restore_transferred();
+
+            Err(new_data_clone_error(
+                "history state cannot transfer objects",
+            ))
+        } else {
+            Ok(message.data)
+        }
+    });
+
+    result.map_err(|err| {
+        let err: &web_sys::DomException = err.as_ref();
+
+        err.clone().into()
+    })
+}
+
 dom_exception_wrapper!(StateError);
 
-dom_exception_wrapper!(StateSerializationError);
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StateErrorKind {
+    /// The URL is not same-origin with the document's current URL, or the document is not fully
+    /// active.
+    Security,
+    /// The state could not be serialized.
+    DataClone,
+    /// Any other exception thrown by the browser.
+    Unknown,
+}
+
+impl StateError {
+    pub fn kind(&self) -> StateErrorKind {
+        match self.inner.name().as_str() {
+            "SecurityError" => StateErrorKind::Security,
+            "DataCloneError" => StateErrorKind::DataClone,
+            _ => StateErrorKind::Unknown,
+        }
+    }
+}
+
+/// Error returned when a state could not be serialized.
+#[deprecated(note = "use `StateError` instead, which also covers state serialization errors")]
+pub type StateSerializationError = StateError;
@@ -2,7 +2,10 @@ use crate::html::{FormEncoding, FormMethod};
 use crate::url::Url;
 
 pub(crate) mod form_submitter_element_seal {
-    pub trait Seal {}
+    pub trait Seal {
+        #[doc(hidden)]
+        fn as_web_sys_html_element(&self) -> &web_sys::HtmlElement;
+    }
 }
 
 pub trait FormSubmitterElement: form_submitter_element_seal::Seal {
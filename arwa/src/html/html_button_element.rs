@@ -94,7 +94,11 @@ impl TryFrom<DynamicFormListedElement> for HtmlButtonElement {
     }
 }
 
-impl form_submitter_element_seal::Seal for HtmlButtonElement {}
+impl form_submitter_element_seal::Seal for HtmlButtonElement {
+    fn as_web_sys_html_element(&self) -> &web_sys::HtmlElement {
+        self.inner.as_ref()
+    }
+}
 
 impl FormSubmitterElement for HtmlButtonElement {
     delegate! {
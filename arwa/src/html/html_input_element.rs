@@ -332,7 +332,11 @@ impl TryFrom<DynamicFormListedElement> for HtmlInputElement {
     }
 }
 
-impl form_submitter_element_seal::Seal for HtmlInputElement {}
+impl form_submitter_element_seal::Seal for HtmlInputElement {
+    fn as_web_sys_html_element(&self) -> &web_sys::HtmlElement {
+        self.inner.as_ref()
+    }
+}
 
 impl FormSubmitterElement for HtmlInputElement {
     delegate! {
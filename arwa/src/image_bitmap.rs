@@ -10,7 +10,10 @@ use wasm_bindgen_futures::JsFuture;
 
 use crate::file::Blob;
 use crate::html::{HtmlCanvasElement, HtmlImgElement, HtmlVideoElement};
-use crate::{dom_exception_wrapper, impl_common_wrapper_traits, impl_js_cast};
+use crate::{
+    dom_exception_wrapper, impl_common_wrapper_traits, impl_js_cast, transferable_seal,
+    Transferable,
+};
 
 #[derive(Clone)]
 pub struct ImageBitmap {
@@ -29,6 +32,24 @@ impl ImageBitmap {
     }
 }
 
+impl transferable_seal::Seal for ImageBitmap {
+    fn as_transferable(&self) -> &JsValue {
+        self.inner.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<web_sys::ImageBitmap>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        ImageBitmap {
+            inner: value.unchecked_into(),
+        }
+    }
+}
+
+impl Transferable for ImageBitmap {}
+
 impl AsRef<web_sys::ImageBitmap> for ImageBitmap {
     fn as_ref(&self) -> &web_sys::ImageBitmap {
         &self.inner
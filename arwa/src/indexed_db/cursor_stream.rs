@@ -0,0 +1,578 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::marker;
+use std::ops::Bound;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures::{Stream, TryFutureExt};
+use js_sys::Array;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+use crate::dom_exception_wrapper;
+use crate::indexed_db::error_kind::idb_error_kind;
+use crate::indexed_db::request_future::RequestFuture;
+use crate::indexed_db::transaction_capability::Write;
+use crate::indexed_db::{
+    decode_record, key_from_js, key_query_to_js, key_to_js, CursorDeleteError, CursorDirection,
+    CursorUpdateError, DecodeRecordError, IdbRecord, Key, KeyQuery, KeyRange,
+    RequestCursorDeleteError, RequestCursorUpdateError,
+};
+
+dom_exception_wrapper!(CursorStreamError);
+
+idb_error_kind!(CursorStreamError => CursorStreamErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+    /// The transaction was aborted before the next record was read.
+    Abort => "AbortError",
+});
+
+pub(crate) enum CursorSource {
+    Store(web_sys::IdbObjectStore),
+    Index(web_sys::IdbIndex),
+}
+
+enum State {
+    // The stream has not been polled yet.
+    Initial,
+    // Waiting for the cursor to (re)position.
+    Cursor(web_sys::IdbRequest),
+    // The entry at the cursor's current position was yielded; the cursor is continued when the
+    // next entry is requested, so that the entry may still be updated or deleted.
+    Yielded(web_sys::IdbCursorWithValue, web_sys::IdbRequest),
+    // Waiting for a batch of values and the corresponding keys.
+    Batch {
+        values: web_sys::IdbRequest,
+        keys: web_sys::IdbRequest,
+    },
+    // The previous batch was full, the next batch starts after the given key.
+    NextBatch(Key),
+    Done,
+}
+
+/// A [Stream] over the records in an object store or index, see [ObjectStore::iter] and
+/// [Index::iter].
+///
+/// The stream does not move on to the next record until the next entry is requested. This means
+/// that a [CursorEntry] may be updated or deleted until the stream is polled again; it also means
+/// that the transaction may auto-commit if the stream is not polled again promptly (e.g. because
+/// other, unrelated futures are awaited in between).
+///
+/// [ObjectStore::iter]: crate::indexed_db::ObjectStore::iter
+/// [Index::iter]: crate::indexed_db::Index::iter
+#[must_use = "streams do nothing unless polled"]
+pub struct CursorStream<Capabilities> {
+    source: CursorSource,
+    query: KeyQuery,
+    direction: CursorDirection,
+    skip: u32,
+    take: Option<u32>,
+    batch_size: Option<u32>,
+    yielded: u32,
+    state: State,
+    buffer: VecDeque<(Key, JsValue)>,
+    position: Rc<Cell<u64>>,
+    waker: Rc<RefCell<Option<Waker>>>,
+    wake_up_callback: Option<Closure<dyn FnMut(web_sys::Event)>>,
+    _capabilities: marker::PhantomData<Capabilities>,
+}
+
+impl<Capabilities> CursorStream<Capabilities> {
+    pub(crate) fn new(source: CursorSource, query: KeyQuery, direction: CursorDirection) -> Self {
+        CursorStream {
+            source,
+            query,
+            direction,
+            skip: 0,
+            take: None,
+            batch_size: None,
+            yielded: 0,
+            state: State::Initial,
+            buffer: VecDeque::new(),
+            position: Rc::new(Cell::new(0)),
+            waker: Rc::new(RefCell::new(None)),
+            wake_up_callback: None,
+            _capabilities: Default::default(),
+        }
+    }
+
+    /// Skips the first `count` records.
+    ///
+    /// Unlike [StreamExt::skip](futures::StreamExt::skip), the skipped records are never read when
+    /// the stream iterates a cursor; the cursor advances past them instead. When the stream reads
+    /// records in batches (see [batch_size](CursorStream::batch_size)), the skipped records are
+    /// still fetched as part of the first batches and then dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream was already polled.
+    pub fn skip(mut self, count: u32) -> Self {
+        self.assert_initial();
+        self.skip = count;
+
+        self
+    }
+
+    /// Ends the stream after at most `count` entries.
+    ///
+    /// Unlike [StreamExt::take](futures::StreamExt::take), no further records are requested once
+    /// the last entry was yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream was already polled.
+    pub fn take(mut self, count: u32) -> Self {
+        self.assert_initial();
+        self.take = Some(count);
+
+        self
+    }
+
+    /// Reads records in batches of `batch_size`, rather than one at a time.
+    ///
+    /// Batching is only supported when iterating an object store in the [CursorDirection::Next] or
+    /// [CursorDirection::NextUnique] direction; otherwise this setting is ignored. The entries of a
+    /// batched stream are updated and deleted through the object store, rather than through a
+    /// cursor, and therefore remain valid while the stream advances.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `batch_size` is `0` or if the stream was already polled.
+    pub fn batch_size(mut self, batch_size: u32) -> Self {
+        assert!(batch_size > 0, "batch size must not be zero");
+        self.assert_initial();
+        self.batch_size = Some(batch_size);
+
+        self
+    }
+
+    fn assert_initial(&self) {
+        assert!(
+            matches!(self.state, State::Initial),
+            "cannot configure a cursor stream after it was polled"
+        );
+    }
+
+    fn is_batched(&self) -> bool {
+        matches!(self.source, CursorSource::Store(_))
+            && matches!(
+                self.direction,
+                CursorDirection::Next | CursorDirection::NextUnique
+            )
+            && self.batch_size.is_some()
+    }
+
+    fn open(&mut self) -> Result<State, CursorStreamError> {
+        if self.is_batched() {
+            return self.request_batch(&self.query.clone());
+        }
+
+        let query = key_query_to_js(&self.query);
+        let direction = self.direction.to_web_sys();
+
+        let request = match &self.source {
+            CursorSource::Store(store) => {
+                store.open_cursor_with_range_and_direction(&query, direction)
+            }
+            CursorSource::Index(index) => {
+                index.open_cursor_with_range_and_direction(&query, direction)
+            }
+        }
+        .map_err(|err| CursorStreamError::new(err.unchecked_into()))?;
+
+        self.listen(&request);
+
+        Ok(State::Cursor(request))
+    }
+
+    fn request_batch(&mut self, query: &KeyQuery) -> Result<State, CursorStreamError> {
+        let CursorSource::Store(store) = &self.source else {
+            unreachable!()
+        };
+
+        let query = key_query_to_js(query);
+        let batch_size = self.batch_size.unwrap_throw();
+
+        let values = store
+            .get_all_with_key_and_limit(&query, batch_size)
+            .map_err(|err| CursorStreamError::new(err.unchecked_into()))?;
+        let keys = store
+            .get_all_keys_with_key_and_limit(&query, batch_size)
+            .map_err(|err| CursorStreamError::new(err.unchecked_into()))?;
+
+        self.listen(&values);
+        self.listen(&keys);
+
+        Ok(State::Batch { values, keys })
+    }
+
+    fn listen(&mut self, request: &web_sys::IdbRequest) {
+        let waker = self.waker.clone();
+
+        let callback = self.wake_up_callback.get_or_insert_with(|| {
+            Closure::wrap(Box::new(move |_: web_sys::Event| {
+                if let Some(waker) = waker.borrow_mut().take() {
+                    waker.wake();
+                }
+            }) as Box<dyn FnMut(web_sys::Event)>)
+        });
+
+        request.set_onsuccess(Some(callback.as_ref().unchecked_ref()));
+        request.set_onerror(Some(callback.as_ref().unchecked_ref()));
+    }
+
+    fn unlisten(&self) {
+        match &self.state {
+            State::Cursor(request) | State::Yielded(_, request) => unlisten(request),
+            State::Batch { values, keys } => {
+                unlisten(values);
+                unlisten(keys);
+            }
+            _ => (),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.unlisten();
+        self.state = State::Done;
+    }
+
+    fn store_entry(&self, key: Key, value: JsValue) -> CursorEntry<Capabilities> {
+        let CursorSource::Store(store) = &self.source else {
+            unreachable!()
+        };
+
+        CursorEntry {
+            key: key.clone(),
+            primary_key: key,
+            value,
+            target: EntryTarget::Store(store.clone()),
+            _capabilities: Default::default(),
+        }
+    }
+
+    fn cursor_entry(&self, cursor: &web_sys::IdbCursorWithValue) -> CursorEntry<Capabilities> {
+        // I can't find any indication in the spec that these can actually fail
+        let key = key_from_js(&cursor.key().unwrap_throw()).unwrap_throw();
+        let primary_key = key_from_js(&cursor.primary_key().unwrap_throw()).unwrap_throw();
+        let value = cursor.value().unwrap_throw();
+
+        CursorEntry {
+            key,
+            primary_key,
+            value,
+            target: EntryTarget::Cursor {
+                cursor: cursor.clone(),
+                position: self.position.get(),
+                current_position: self.position.clone(),
+            },
+            _capabilities: Default::default(),
+        }
+    }
+}
+
+fn unlisten(request: &web_sys::IdbRequest) {
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+}
+
+fn is_done(request: &web_sys::IdbRequest) -> bool {
+    request.ready_state() == web_sys::IdbRequestReadyState::Done
+}
+
+// The stream does not hold any self-references.
+impl<Capabilities> Unpin for CursorStream<Capabilities> {}
+
+impl<Capabilities> Stream for CursorStream<Capabilities> {
+    type Item = Result<CursorEntry<Capabilities>, CursorStreamError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if this.take == Some(this.yielded) {
+                this.finish();
+
+                return Poll::Ready(None);
+            }
+
+            if let Some((key, value)) = this.buffer.pop_front() {
+                if this.skip > 0 {
+                    this.skip -= 1;
+
+                    continue;
+                }
+
+                this.yielded += 1;
+
+                return Poll::Ready(Some(Ok(this.store_entry(key, value))));
+            }
+
+            match std::mem::replace(&mut this.state, State::Done) {
+                State::Initial => match this.open() {
+                    Ok(state) => this.state = state,
+                    Err(err) => return Poll::Ready(Some(Err(err))),
+                },
+                State::Cursor(request) => {
+                    if !is_done(&request) {
+                        *this.waker.borrow_mut() = Some(cx.waker().clone());
+                        this.state = State::Cursor(request);
+
+                        return Poll::Pending;
+                    }
+
+                    this.state = State::Cursor(request.clone());
+
+                    if let Some(error) = request.error().unwrap_throw() {
+                        this.finish();
+
+                        return Poll::Ready(Some(Err(CursorStreamError::new(error))));
+                    }
+
+                    let result = request.result().unwrap_throw();
+
+                    if result.is_null() {
+                        this.finish();
+
+                        return Poll::Ready(None);
+                    }
+
+                    let cursor: web_sys::IdbCursorWithValue = result.unchecked_into();
+
+                    if this.skip > 0 {
+                        let skip = std::mem::take(&mut this.skip);
+
+                        if let Err(err) = cursor.advance(skip) {
+                            this.finish();
+
+                            return Poll::Ready(Some(Err(CursorStreamError::new(
+                                err.unchecked_into(),
+                            ))));
+                        }
+
+                        continue;
+                    }
+
+                    this.yielded += 1;
+
+                    let entry = this.cursor_entry(&cursor);
+
+                    this.state = State::Yielded(cursor, request);
+
+                    return Poll::Ready(Some(Ok(entry)));
+                }
+                State::Yielded(cursor, request) => {
+                    this.position.set(this.position.get() + 1);
+                    this.state = State::Cursor(request);
+
+                    if let Err(err) = cursor.continue_() {
+                        this.finish();
+
+                        return Poll::Ready(Some(Err(CursorStreamError::new(
+                            err.unchecked_into(),
+                        ))));
+                    }
+                }
+                State::Batch { values, keys } => {
+                    if !is_done(&values) || !is_done(&keys) {
+                        *this.waker.borrow_mut() = Some(cx.waker().clone());
+                        this.state = State::Batch { values, keys };
+
+                        return Poll::Pending;
+                    }
+
+                    unlisten(&values);
+                    unlisten(&keys);
+
+                    let error = values
+                        .error()
+                        .unwrap_throw()
+                        .or_else(|| keys.error().unwrap_throw());
+
+                    if let Some(error) = error {
+                        return Poll::Ready(Some(Err(CursorStreamError::new(error))));
+                    }
+
+                    let values: Array = values.result().unwrap_throw().unchecked_into();
+                    let keys: Array = keys.result().unwrap_throw().unchecked_into();
+
+                    for (key, value) in keys.iter().zip(values.iter()) {
+                        this.buffer
+                            .push_back((key_from_js(&key).unwrap_throw(), value));
+                    }
+
+                    if values.length() == this.batch_size.unwrap_throw() {
+                        if let Some((last_key, _)) = this.buffer.back() {
+                            this.state = State::NextBatch(last_key.clone());
+                        }
+                    }
+                }
+                State::NextBatch(last_key) => {
+                    // A single key query never matches more than one record.
+                    let KeyQuery::Range(query) = &this.query else {
+                        return Poll::Ready(None);
+                    };
+
+                    let Ok(range) =
+                        KeyRange::new(Bound::Excluded(last_key), query.upper().cloned())
+                    else {
+                        // The last key was the upper bound of the query.
+                        return Poll::Ready(None);
+                    };
+
+                    match this.request_batch(&KeyQuery::Range(range)) {
+                        Ok(state) => this.state = state,
+                        Err(err) => return Poll::Ready(Some(Err(err))),
+                    }
+                }
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<Capabilities> Drop for CursorStream<Capabilities> {
+    fn drop(&mut self) {
+        self.unlisten();
+    }
+}
+
+enum EntryTarget {
+    Cursor {
+        cursor: web_sys::IdbCursorWithValue,
+        position: u64,
+        current_position: Rc<Cell<u64>>,
+    },
+    Store(web_sys::IdbObjectStore),
+}
+
+fn cursor_moved_error() -> web_sys::DomException {
+    web_sys::DomException::new_with_message_and_name(
+        "The cursor has moved past this entry.",
+        "InvalidStateError",
+    )
+    .unwrap_throw()
+}
+
+/// A record yielded by a [CursorStream].
+pub struct CursorEntry<Capabilities> {
+    key: Key,
+    primary_key: Key,
+    value: JsValue,
+    target: EntryTarget,
+    _capabilities: marker::PhantomData<Capabilities>,
+}
+
+impl<Capabilities> CursorEntry<Capabilities> {
+    /// The key of the record in the object store or index that is being iterated.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// The key of the record in its object store.
+    ///
+    /// When iterating an object store, this is the same as the [key](CursorEntry::key).
+    pub fn primary_key(&self) -> &Key {
+        &self.primary_key
+    }
+
+    pub fn value(&self) -> &JsValue {
+        &self.value
+    }
+
+    pub fn into_value(self) -> JsValue {
+        self.value
+    }
+
+    /// Decodes the value as a record of type `T`.
+    pub fn record<T>(&self) -> Result<T, DecodeRecordError>
+    where
+        T: IdbRecord,
+    {
+        decode_record(self.value.clone())
+    }
+
+    /// Replaces the value of this record.
+    ///
+    /// Returns an `InvalidStateError` if the stream that yielded this entry has since moved on to
+    /// the next record (does not apply to batched streams, see [CursorStream::batch_size]).
+    pub fn update<V>(
+        &self,
+        value: &V,
+    ) -> Result<impl Future<Output = Result<(), RequestCursorUpdateError>>, CursorUpdateError>
+    where
+        Capabilities: Write,
+        V: AsRef<JsValue>,
+    {
+        let request = match &self.target {
+            EntryTarget::Cursor {
+                cursor,
+                position,
+                current_position,
+            } => {
+                if current_position.get() != *position {
+                    return Err(CursorUpdateError::new(cursor_moved_error()));
+                }
+
+                cursor.update(value.as_ref())
+            }
+            EntryTarget::Store(store) => {
+                if store.key_path().map(|path| path.is_null()).unwrap_or(true) {
+                    store.put_with_key(value.as_ref(), &key_to_js(&self.primary_key))
+                } else {
+                    store.put(value.as_ref())
+                }
+            }
+        };
+
+        request
+            .map(|request| {
+                RequestFuture::from_request(request)
+                    .map_ok(|_| ())
+                    .map_err(RequestCursorUpdateError::new)
+            })
+            .map_err(|err| CursorUpdateError::new(err.unchecked_into()))
+    }
+
+    /// Deletes this record.
+    ///
+    /// Returns an `InvalidStateError` if the stream that yielded this entry has since moved on to
+    /// the next record (does not apply to batched streams, see [CursorStream::batch_size]).
+    pub fn delete(
+        &self,
+    ) -> Result<impl Future<Output = Result<(), RequestCursorDeleteError>>, CursorDeleteError>
+    where
+        Capabilities: Write,
+    {
+        let request = match &self.target {
+            EntryTarget::Cursor {
+                cursor,
+                position,
+                current_position,
+            } => {
+                if current_position.get() != *position {
+                    return Err(CursorDeleteError::new(cursor_moved_error()));
+                }
+
+                cursor.delete()
+            }
+            EntryTarget::Store(store) => store.delete(&key_to_js(&self.primary_key)),
+        };
+
+        request
+            .map(|request| {
+                RequestFuture::from_request(request)
+                    .map_ok(|_| ())
+                    .map_err(RequestCursorDeleteError::new)
+            })
+            .map_err(|err| CursorDeleteError::new(err.unchecked_into()))
+    }
+}
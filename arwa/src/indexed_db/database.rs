@@ -5,10 +5,20 @@ use wasm_bindgen::{JsCast, JsValue};
 
 use crate::dom_exception_wrapper;
 use crate::event::{impl_typed_event_traits, typed_event_iterator};
+use crate::indexed_db::error_kind::idb_error_kind;
 use crate::indexed_db::{ReadOnlyTransaction, ReadWriteTransaction, Transaction};
 
 dom_exception_wrapper!(CreateTransactionError);
 
+idb_error_kind!(CreateTransactionError => CreateTransactionErrorKind {
+    /// The database connection is closed, or an upgrade transaction is running.
+    InvalidState => "InvalidStateError",
+    /// One of the object stores does not exist.
+    NotFound => "NotFoundError",
+    /// No object store names were given.
+    InvalidAccess => "InvalidAccessError",
+});
+
 pub struct Database {
     pub(super) inner: web_sys::IdbDatabase,
 }
@@ -0,0 +1,28 @@
+// Declares an enum that disambiguates the exceptions that may occur for an IndexedDB operation, and
+// adds a `kind` method to the operation's error type that returns the error's kind based on the
+// name of the exception it wraps. The error type must be declared in the same module.
+//
+// Every kind enum has an `Unknown` kind, which covers exceptions that are not anticipated by the
+// spec (e.g. browser-specific I/O errors).
+macro_rules! idb_error_kind {
+    ($error:ident => $kind:ident { $($(#[$attr:meta])* $variant:ident => $name:literal,)* }) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        pub enum $kind {
+            $($(#[$attr])* $variant,)*
+            /// The operation failed for a reason that is not covered by the other kinds, e.g. an
+            /// I/O error.
+            Unknown,
+        }
+
+        impl $error {
+            pub fn kind(&self) -> $kind {
+                match self.inner.name().as_str() {
+                    $($name => $kind::$variant,)*
+                    _ => $kind::Unknown,
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use idb_error_kind;
@@ -7,6 +7,7 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{throw_str, JsCast, UnwrapThrowExt};
 use web_sys::DedicatedWorkerGlobalScope;
 
+use crate::indexed_db::error_kind::idb_error_kind;
 use crate::indexed_db::{transaction_capability, Database, Transaction, UpgradeTransaction};
 use crate::window::Window;
 use crate::worker::service::ServiceWorkerGlobalScope;
@@ -87,6 +88,35 @@ dom_exception_wrapper!(DeleteDatabaseError);
 dom_exception_wrapper!(RequestOpenDatabaseError);
 dom_exception_wrapper!(RequestDeleteDatabaseError);
 
+idb_error_kind!(OpenDatabaseError => OpenDatabaseErrorKind {
+    /// The requested version is not a valid version number.
+    Type => "TypeError",
+    /// The current context is not allowed to access IndexedDB storage (e.g. because it has an
+    /// opaque origin).
+    Security => "SecurityError",
+});
+
+idb_error_kind!(DeleteDatabaseError => DeleteDatabaseErrorKind {
+    /// The current context is not allowed to access IndexedDB storage (e.g. because it has an
+    /// opaque origin).
+    Security => "SecurityError",
+});
+
+idb_error_kind!(RequestOpenDatabaseError => RequestOpenDatabaseErrorKind {
+    /// The database already has a version greater than the requested version.
+    Version => "VersionError",
+    /// The upgrade transaction was aborted, or the connection was closed before the upgrade
+    /// completed.
+    Abort => "AbortError",
+    /// The storage quota was exceeded.
+    QuotaExceeded => "QuotaExceededError",
+});
+
+idb_error_kind!(RequestDeleteDatabaseError => RequestDeleteDatabaseErrorKind {
+    /// The request was aborted before the database was deleted.
+    Abort => "AbortError",
+});
+
 pub fn default_upgrade_needed_callback(_: UpgradeTransaction, _: u32, _: u32) {}
 pub fn default_blocked_callback() {}
 
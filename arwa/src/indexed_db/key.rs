@@ -0,0 +1,43 @@
+pub use arwa_parse::idb_key::{IntoKey, Key};
+use js_sys::{Array, ArrayBuffer, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+pub(crate) fn key_to_js(key: &Key) -> JsValue {
+    match key {
+        Key::Number(value) => JsValue::from_f64(*value),
+        Key::Date(value) => js_sys::Date::new(&JsValue::from_f64(*value)).into(),
+        Key::String(value) => JsValue::from_str(value),
+        Key::Binary(bytes) => Uint8Array::from(bytes.as_slice()).buffer().into(),
+        Key::Array(keys) => keys.iter().map(key_to_js).collect::<Array>().into(),
+    }
+}
+
+pub(crate) fn key_from_js(value: &JsValue) -> Option<Key> {
+    if let Some(number) = value.as_f64() {
+        Some(Key::Number(number))
+    } else if let Some(date) = value.dyn_ref::<js_sys::Date>() {
+        Some(Key::Date(date.get_time()))
+    } else if let Some(string) = value.as_string() {
+        Some(Key::String(string))
+    } else if let Some(buffer) = value.dyn_ref::<ArrayBuffer>() {
+        Some(Key::Binary(Uint8Array::new(buffer).to_vec()))
+    } else if ArrayBuffer::is_view(value) {
+        // Read the raw bytes of the view, regardless of the view's element type.
+        let get = |property: &str| js_sys::Reflect::get(value, &property.into()).unwrap_throw();
+        let buffer = get("buffer");
+        let byte_offset = get("byteOffset").as_f64().unwrap_throw() as u32;
+        let byte_length = get("byteLength").as_f64().unwrap_throw() as u32;
+        let bytes = Uint8Array::new_with_byte_offset_and_length(&buffer, byte_offset, byte_length);
+
+        Some(Key::Binary(bytes.to_vec()))
+    } else if Array::is_array(value) {
+        value
+            .unchecked_ref::<Array>()
+            .iter()
+            .map(|value| key_from_js(&value))
+            .collect::<Option<Vec<_>>>()
+            .map(Key::Array)
+    } else {
+        None
+    }
+}
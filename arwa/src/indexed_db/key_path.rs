@@ -3,6 +3,7 @@ use std::fmt;
 pub use arwa_macro::idb_key_path as key_path;
 use arwa_parse::idb_key_path::IdbKeyPath as DynamicallyParsedKeyPath;
 pub use arwa_parse::idb_key_path::InvalidKeyPath;
+use wasm_bindgen::{JsCast, JsValue};
 
 use crate::console::{Argument, ToArgument};
 
@@ -43,6 +44,12 @@ impl KeyPath {
             internal: KeyPathInternal::Static(key_path),
         }
     }
+
+    fn trusted(key_path: String) -> Self {
+        KeyPath {
+            internal: KeyPathInternal::Dynamic(DynamicallyParsedKeyPath::trusted(key_path)),
+        }
+    }
 }
 
 impl AsRef<str> for KeyPath {
@@ -116,3 +123,47 @@ impl fmt::Debug for KeyPath {
         fmt::Debug::fmt(as_str, f)
     }
 }
+
+/// The key path of an existing object store or index, see [ObjectStore::key_path] and
+/// [Index::key_path].
+///
+/// This crate only creates object stores and indexes with a [StoredKeyPath::Path] key path (or
+/// object stores without a key path), but databases created by other code may also use the empty
+/// key path or an array of key paths.
+///
+/// [ObjectStore::key_path]: crate::indexed_db::ObjectStore::key_path
+/// [Index::key_path]: crate::indexed_db::Index::key_path
+#[derive(Clone, PartialEq, Debug)]
+pub enum StoredKeyPath {
+    /// The empty key path: the key is the value itself.
+    Value,
+    /// The key is extracted from the value with the key path.
+    Path(KeyPath),
+    /// The key is an array that holds the keys extracted with each of the key paths, in order.
+    ///
+    /// The elements are never arrays themselves.
+    Array(Vec<StoredKeyPath>),
+}
+
+impl StoredKeyPath {
+    // Converts the key path reported by the browser, which is either a string or an array of
+    // strings. Returns `None` if the value is `null` (an object store with out-of-line keys).
+    pub(crate) fn from_js(value: &JsValue) -> Option<Self> {
+        if let Some(key_path) = value.as_string() {
+            if key_path.is_empty() {
+                Some(StoredKeyPath::Value)
+            } else {
+                Some(StoredKeyPath::Path(KeyPath::trusted(key_path)))
+            }
+        } else {
+            value.dyn_ref::<js_sys::Array>().map(|array| {
+                StoredKeyPath::Array(
+                    array
+                        .iter()
+                        .filter_map(|key_path| StoredKeyPath::from_js(&key_path))
+                        .collect(),
+                )
+            })
+        }
+    }
+}
@@ -1,90 +1,54 @@
-use std::convert::TryFrom;
-use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
-
-use wasm_bindgen::{JsCast, JsValue};
-
-use crate::{dom_exception_wrapper, impl_common_wrapper_traits};
-
-dom_exception_wrapper!(CreateKeyRangeError);
-
-pub struct KeyRange {
-    inner: web_sys::IdbKeyRange,
-}
-
-impl_common_wrapper_traits!(KeyRange);
-
-impl From<web_sys::IdbKeyRange> for KeyRange {
-    fn from(inner: web_sys::IdbKeyRange) -> Self {
-        KeyRange { inner }
-    }
-}
-
-impl<T> TryFrom<Range<T>> for KeyRange
-where
-    T: AsRef<JsValue>,
-{
-    type Error = CreateKeyRangeError;
-
-    fn try_from(value: Range<T>) -> Result<Self, Self::Error> {
-        web_sys::IdbKeyRange::bound_with_lower_open_and_upper_open(
-            value.start.as_ref(),
-            value.end.as_ref(),
-            false,
-            true,
-        )
-        .map(|inner| KeyRange { inner })
-        .map_err(|err| CreateKeyRangeError::new(err.unchecked_into()))
-    }
-}
-
-impl<T> TryFrom<RangeInclusive<T>> for KeyRange
-where
-    T: AsRef<JsValue>,
-{
-    type Error = CreateKeyRangeError;
-
-    fn try_from(value: RangeInclusive<T>) -> Result<Self, Self::Error> {
-        web_sys::IdbKeyRange::bound(value.start().as_ref(), value.end().as_ref())
-            .map(|inner| KeyRange { inner })
-            .map_err(|err| CreateKeyRangeError::new(err.unchecked_into()))
-    }
+use std::ops::Bound;
+
+pub use arwa_parse::idb_key::{IntoKeyQuery, InvalidKeyRange, KeyQuery, KeyRange};
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
+
+use crate::indexed_db::{key_to_js, Key};
+
+pub(crate) fn key_range_to_web_sys(range: &KeyRange) -> web_sys::IdbKeyRange {
+    // Note: a `KeyRange` is validated on construction, so creating the IDBKeyRange cannot fail.
+    let result = match (range.lower(), range.upper()) {
+        (Bound::Unbounded, Bound::Unbounded) => {
+            // IDBKeyRange does not have a representation for the unbounded range; the lowest
+            // possible key is the number -Infinity.
+            web_sys::IdbKeyRange::lower_bound(&JsValue::from_f64(f64::NEG_INFINITY))
+        }
+        (Bound::Included(lower), Bound::Unbounded) => {
+            web_sys::IdbKeyRange::lower_bound(&key_to_js(lower))
+        }
+        (Bound::Excluded(lower), Bound::Unbounded) => {
+            web_sys::IdbKeyRange::lower_bound_with_open(&key_to_js(lower), true)
+        }
+        (Bound::Unbounded, Bound::Included(upper)) => {
+            web_sys::IdbKeyRange::upper_bound(&key_to_js(upper))
+        }
+        (Bound::Unbounded, Bound::Excluded(upper)) => {
+            web_sys::IdbKeyRange::upper_bound_with_open(&key_to_js(upper), true)
+        }
+        (lower, upper) => {
+            let (lower, lower_open) = bound_to_js(lower);
+            let (upper, upper_open) = bound_to_js(upper);
+
+            web_sys::IdbKeyRange::bound_with_lower_open_and_upper_open(
+                &lower, &upper, lower_open, upper_open,
+            )
+        }
+    };
+
+    result.unwrap_throw()
 }
 
-impl<T> TryFrom<RangeFrom<T>> for KeyRange
-where
-    T: AsRef<JsValue>,
-{
-    type Error = CreateKeyRangeError;
-
-    fn try_from(value: RangeFrom<T>) -> Result<Self, Self::Error> {
-        web_sys::IdbKeyRange::lower_bound(value.start.as_ref())
-            .map(|inner| KeyRange { inner })
-            .map_err(|err| CreateKeyRangeError::new(err.unchecked_into()))
+fn bound_to_js(bound: Bound<&Key>) -> (JsValue, bool) {
+    match bound {
+        Bound::Included(key) => (key_to_js(key), false),
+        Bound::Excluded(key) => (key_to_js(key), true),
+        Bound::Unbounded => unreachable!(),
     }
 }
 
-impl<T> TryFrom<RangeTo<T>> for KeyRange
-where
-    T: AsRef<JsValue>,
-{
-    type Error = CreateKeyRangeError;
-
-    fn try_from(value: RangeTo<T>) -> Result<Self, Self::Error> {
-        web_sys::IdbKeyRange::upper_bound_with_open(value.end.as_ref(), true)
-            .map(|inner| KeyRange { inner })
-            .map_err(|err| CreateKeyRangeError::new(err.unchecked_into()))
-    }
-}
-
-impl<T> TryFrom<RangeToInclusive<T>> for KeyRange
-where
-    T: AsRef<JsValue>,
-{
-    type Error = CreateKeyRangeError;
-
-    fn try_from(value: RangeToInclusive<T>) -> Result<Self, Self::Error> {
-        web_sys::IdbKeyRange::upper_bound(value.end.as_ref())
-            .map(|inner| KeyRange { inner })
-            .map_err(|err| CreateKeyRangeError::new(err.unchecked_into()))
+pub(crate) fn key_query_to_js(query: &KeyQuery) -> JsValue {
+    match query {
+        KeyQuery::Key(key) => key_to_js(key),
+        KeyQuery::Range(range) => key_range_to_web_sys(range).into(),
     }
 }
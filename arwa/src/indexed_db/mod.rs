@@ -1,16 +1,29 @@
+mod cursor_stream;
+pub use self::cursor_stream::*;
+
 mod database;
 pub use self::database::*;
 
 mod factory;
 pub use self::factory::*;
 
+mod key;
+pub use self::key::*;
+
 mod key_path;
 pub use self::key_path::*;
 
 mod key_range;
 pub use self::key_range::*;
 
+mod record;
+pub use self::record::*;
+
+mod schema;
+pub use self::schema::*;
+
 mod transaction;
 pub use self::transaction::*;
 
+mod error_kind;
 mod request_future;
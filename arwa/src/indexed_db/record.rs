@@ -0,0 +1,361 @@
+use std::error::Error;
+use std::future::Future;
+use std::{fmt, marker};
+
+pub use arwa_macro::IdbRecord;
+use futures::FutureExt;
+use js_sys::Array;
+use wasm_bindgen::JsValue;
+
+use crate::indexed_db::transaction_capability::{Upgrade, Write};
+use crate::indexed_db::{
+    AddError, AddWithQueryError, CreateIndexError, CreateIndexOptions, GetAllError, GetError,
+    IntoKey, IntoKeyQuery, KeyConfig, KeyPath, ObjectStore, PutError, PutWithQueryError,
+    RequestAddError, RequestAddWithQueryError, RequestGetAllError, RequestGetError,
+    RequestPutError, RequestPutWithQueryError,
+};
+use crate::structured_clone::{
+    deserialize, new_data_clone_error, serialize, DataCloneError, DeserializeError,
+    FromStructuredClone, StructuredClone,
+};
+
+/// Describes an index that is created for an [IdbRecord] type by
+/// [TypedObjectStore::create_indexes].
+pub struct IndexConfig {
+    pub name: String,
+    pub key_path: KeyPath,
+    pub options: CreateIndexOptions,
+}
+
+/// A type that may be stored as a record in an IndexedDB object store, see [ObjectStore::typed].
+///
+/// Records are stored as structured clones. This trait is typically derived, along with
+/// [StructuredClone] and [FromStructuredClone]:
+///
+/// ```rust,ignore
+/// #[derive(StructuredClone, FromStructuredClone, IdbRecord)]
+/// #[idb(key_path = "isbn")]
+/// struct Book {
+///     isbn: String,
+///     title: String,
+///     #[idb(index = "by_author")]
+///     author: String,
+///     #[idb(index, multi_entry)]
+///     tags: Vec<String>,
+/// }
+/// ```
+///
+/// The `key_path` is validated at compile time, in the same way as for [key_path!], and must
+/// refer to one of the struct's fields. It may be combined with `auto_increment`. A field marked
+/// with `index` declares an index on that field, named after the field unless a name is given; an
+/// index may additionally be marked `unique` and/or `multi_entry`.
+///
+/// [key_path!]: crate::indexed_db::key_path
+pub trait IdbRecord: StructuredClone + FromStructuredClone {
+    /// The key configuration for object stores that hold records of this type, or `None` if the
+    /// records use out-of-line keys.
+    fn key_config() -> Option<KeyConfig>;
+
+    /// The indexes declared for records of this type.
+    fn indexes() -> Vec<IndexConfig>;
+}
+
+/// Error returned when a stored value could not be decoded as an [IdbRecord] type.
+#[derive(Clone)]
+pub struct DecodeRecordError {
+    record_type: &'static str,
+    inner: DeserializeError,
+}
+
+impl DecodeRecordError {
+    fn new<T>(inner: DeserializeError) -> Self {
+        DecodeRecordError {
+            record_type: std::any::type_name::<T>(),
+            inner,
+        }
+    }
+
+    /// The name of the type the value was decoded as.
+    pub fn record_type(&self) -> &str {
+        self.record_type
+    }
+
+    pub fn deserialize_error(&self) -> &DeserializeError {
+        &self.inner
+    }
+}
+
+impl fmt::Display for DecodeRecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not decode record as `{}`: {}",
+            self.record_type, self.inner
+        )
+    }
+}
+
+impl fmt::Debug for DecodeRecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for DecodeRecordError {}
+
+/// Error returned by a request that reads records from a [TypedObjectStore].
+#[derive(Clone)]
+pub enum ReadRecordError<E> {
+    /// The request failed.
+    Store(E),
+    /// The request succeeded, but a stored value could not be decoded as the record type.
+    Decode(DecodeRecordError),
+}
+
+impl<E> fmt::Display for ReadRecordError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadRecordError::Store(err) => fmt::Display::fmt(err, f),
+            ReadRecordError::Decode(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E> fmt::Debug for ReadRecordError<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadRecordError::Store(err) => f.debug_tuple("Store").field(err).finish(),
+            ReadRecordError::Decode(err) => f.debug_tuple("Decode").field(err).finish(),
+        }
+    }
+}
+
+impl<E> Error for ReadRecordError<E> where E: fmt::Display + fmt::Debug {}
+
+/// Error returned when a record could not be written to a [TypedObjectStore].
+#[derive(Clone)]
+pub enum WriteRecordError<E> {
+    /// The record could not be written to the store.
+    Store(E),
+    /// The record could not be serialized, e.g. because it contains a [TransferBox]: records
+    /// cannot transfer objects. Any transferred objects are put back into their boxes.
+    ///
+    /// [TransferBox]: crate::TransferBox
+    Encode(DataCloneError),
+}
+
+impl<E> fmt::Display for WriteRecordError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteRecordError::Store(err) => fmt::Display::fmt(err, f),
+            WriteRecordError::Encode(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E> fmt::Debug for WriteRecordError<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteRecordError::Store(err) => f.debug_tuple("Store").field(err).finish(),
+            WriteRecordError::Encode(err) => f.debug_tuple("Encode").field(err).finish(),
+        }
+    }
+}
+
+impl<E> Error for WriteRecordError<E> where E: fmt::Display + fmt::Debug {}
+
+pub(crate) fn decode_record<T>(value: JsValue) -> Result<T, DecodeRecordError>
+where
+    T: IdbRecord,
+{
+    deserialize(value).map_err(DecodeRecordError::new::<T>)
+}
+
+fn decode_records<T>(values: Array) -> Result<Vec<T>, DecodeRecordError>
+where
+    T: IdbRecord,
+{
+    values.iter().map(decode_record).collect()
+}
+
+fn encode_record<T>(record: &T) -> Result<JsValue, DataCloneError>
+where
+    T: IdbRecord,
+{
+    serialize(record).and_then(|serialized| {
+        // IndexedDB stores copies of values; objects can not be transferred into a store.
+        if serialized.transfer_list.length() > 0 {
+            serialized.restore_transferred();
+
+            Err(new_data_clone_error(
+                "IndexedDB records cannot transfer objects",
+            ))
+        } else {
+            Ok(serialized.data)
+        }
+    })
+}
+
+/// An [ObjectStore] that holds records of type `T`, see [ObjectStore::typed].
+pub struct TypedObjectStore<T, Capabilities> {
+    store: ObjectStore<Capabilities>,
+    _marker: marker::PhantomData<fn() -> T>,
+}
+
+impl<T, Capabilities> TypedObjectStore<T, Capabilities>
+where
+    T: IdbRecord,
+{
+    pub(crate) fn new(store: ObjectStore<Capabilities>) -> Self {
+        TypedObjectStore {
+            store,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns the untyped object store, e.g. to count or delete records.
+    pub fn as_object_store(&self) -> &ObjectStore<Capabilities> {
+        &self.store
+    }
+
+    pub fn into_object_store(self) -> ObjectStore<Capabilities> {
+        self.store
+    }
+
+    /// Creates the indexes declared by [IdbRecord::indexes].
+    pub fn create_indexes(&self) -> Result<(), CreateIndexError>
+    where
+        Capabilities: Upgrade,
+    {
+        for index in T::indexes() {
+            self.store
+                .create_index(&index.name, &index.key_path, &index.options)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get<Q>(
+        &self,
+        query: Q,
+    ) -> Result<impl Future<Output = Result<Option<T>, ReadRecordError<RequestGetError>>>, GetError>
+    where
+        Q: IntoKeyQuery,
+    {
+        self.store.get(query).map(|future| {
+            future.map(|result| match result {
+                Ok(Some(value)) => decode_record(value)
+                    .map(Some)
+                    .map_err(ReadRecordError::Decode),
+                Ok(None) => Ok(None),
+                Err(err) => Err(ReadRecordError::Store(err)),
+            })
+        })
+    }
+
+    pub fn get_all(
+        &self,
+    ) -> Result<
+        impl Future<Output = Result<Vec<T>, ReadRecordError<RequestGetAllError>>>,
+        GetAllError,
+    > {
+        self.store.get_all().map(|future| {
+            future.map(|result| match result {
+                Ok(values) => decode_records(values).map_err(ReadRecordError::Decode),
+                Err(err) => Err(ReadRecordError::Store(err)),
+            })
+        })
+    }
+
+    pub fn get_all_with_key<Q>(
+        &self,
+        query: Q,
+    ) -> Result<
+        impl Future<Output = Result<Vec<T>, ReadRecordError<RequestGetAllError>>>,
+        GetAllError,
+    >
+    where
+        Q: IntoKeyQuery,
+    {
+        self.store.get_all_with_key(query).map(|future| {
+            future.map(|result| match result {
+                Ok(values) => decode_records(values).map_err(ReadRecordError::Decode),
+                Err(err) => Err(ReadRecordError::Store(err)),
+            })
+        })
+    }
+
+    pub fn add(
+        &self,
+        record: &T,
+    ) -> Result<impl Future<Output = Result<(), RequestAddError>>, WriteRecordError<AddError>>
+    where
+        Capabilities: Write,
+    {
+        let value = encode_record(record).map_err(WriteRecordError::Encode)?;
+
+        self.store.add(&value).map_err(WriteRecordError::Store)
+    }
+
+    pub fn add_with_key<K>(
+        &self,
+        key: K,
+        record: &T,
+    ) -> Result<
+        impl Future<Output = Result<(), RequestAddWithQueryError>>,
+        WriteRecordError<AddWithQueryError>,
+    >
+    where
+        Capabilities: Write,
+        K: IntoKey,
+    {
+        let value = encode_record(record).map_err(WriteRecordError::Encode)?;
+
+        self.store
+            .add_with_key(key, &value)
+            .map_err(WriteRecordError::Store)
+    }
+
+    pub fn put(
+        &self,
+        record: &T,
+    ) -> Result<impl Future<Output = Result<(), RequestPutError>>, WriteRecordError<PutError>>
+    where
+        Capabilities: Write,
+    {
+        let value = encode_record(record).map_err(WriteRecordError::Encode)?;
+
+        self.store.put(&value).map_err(WriteRecordError::Store)
+    }
+
+    pub fn put_with_key<K>(
+        &self,
+        key: K,
+        record: &T,
+    ) -> Result<
+        impl Future<Output = Result<(), RequestPutWithQueryError>>,
+        WriteRecordError<PutWithQueryError>,
+    >
+    where
+        Capabilities: Write,
+        K: IntoKey,
+    {
+        let value = encode_record(record).map_err(WriteRecordError::Encode)?;
+
+        self.store
+            .put_with_key(key, &value)
+            .map_err(WriteRecordError::Store)
+    }
+}
@@ -0,0 +1,773 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+
+use wasm_bindgen::throw_str;
+
+use crate::indexed_db::{
+    CreateIndexError, CreateIndexOptions, CreateObjectStoreError, Database, DatabaseVersion,
+    DeleteIndexError, DeleteObjectStoreError, Factory, GetObjectStoreError, IdbRecord, IndexError,
+    KeyConfig, KeyPath, OpenDataBaseRequest, OpenDatabaseError, RequestOpenDatabaseError,
+    StoredKeyPath, UpgradeTransaction,
+};
+
+type MigrateData = Box<dyn FnOnce(&UpgradeTransaction) -> Result<(), Box<dyn Error>>>;
+
+enum MigrationStep {
+    CreateObjectStore {
+        name: String,
+        key_config: Option<KeyConfig>,
+    },
+    DeleteObjectStore {
+        name: String,
+    },
+    RenameObjectStore {
+        name: String,
+        new_name: String,
+    },
+    CreateIndex {
+        store: String,
+        name: String,
+        key_path: KeyPath,
+        options: CreateIndexOptions,
+    },
+    DeleteIndex {
+        store: String,
+        name: String,
+    },
+    RenameIndex {
+        store: String,
+        name: String,
+        new_name: String,
+    },
+    Custom(MigrateData),
+}
+
+impl MigrationStep {
+    fn apply(self, transaction: &mut UpgradeTransaction) -> Result<(), MigrationError> {
+        match self {
+            MigrationStep::CreateObjectStore { name, key_config } => {
+                transaction.create_object_store(&name, key_config)?;
+            }
+            MigrationStep::DeleteObjectStore { name } => {
+                transaction.delete_object_store(&name)?;
+            }
+            MigrationStep::RenameObjectStore { name, new_name } => {
+                transaction.object_store(&name)?.set_name(&new_name);
+            }
+            MigrationStep::CreateIndex {
+                store,
+                name,
+                key_path,
+                options,
+            } => {
+                transaction
+                    .object_store(&store)?
+                    .create_index(&name, &key_path, &options)?;
+            }
+            MigrationStep::DeleteIndex { store, name } => {
+                transaction.object_store(&store)?.delete_index(&name)?;
+            }
+            MigrationStep::RenameIndex {
+                store,
+                name,
+                new_name,
+            } => {
+                transaction
+                    .object_store(&store)?
+                    .index(&name)?
+                    .set_name(&new_name);
+            }
+            MigrationStep::Custom(f) => {
+                f(transaction).map_err(|err| MigrationError::Custom(err.into()))?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The changes that upgrade a database from the previous version in a [Schema] to the next.
+///
+/// Steps are applied in the order in which they are added.
+pub struct Migration {
+    steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    pub fn new() -> Self {
+        Migration { steps: Vec::new() }
+    }
+
+    pub fn create_object_store(mut self, name: &str, key_config: Option<KeyConfig>) -> Self {
+        self.steps.push(MigrationStep::CreateObjectStore {
+            name: name.to_string(),
+            key_config,
+        });
+
+        self
+    }
+
+    /// Creates an object store for records of type `T`, with the key configuration and indexes
+    /// declared by its [IdbRecord] implementation.
+    pub fn create_record_store<T>(mut self, name: &str) -> Self
+    where
+        T: IdbRecord,
+    {
+        self = self.create_object_store(name, T::key_config());
+
+        for index in T::indexes() {
+            self = self.create_index(name, &index.name, &index.key_path, &index.options);
+        }
+
+        self
+    }
+
+    pub fn delete_object_store(mut self, name: &str) -> Self {
+        self.steps.push(MigrationStep::DeleteObjectStore {
+            name: name.to_string(),
+        });
+
+        self
+    }
+
+    pub fn rename_object_store(mut self, name: &str, new_name: &str) -> Self {
+        self.steps.push(MigrationStep::RenameObjectStore {
+            name: name.to_string(),
+            new_name: new_name.to_string(),
+        });
+
+        self
+    }
+
+    pub fn create_index(
+        mut self,
+        store: &str,
+        name: &str,
+        key_path: &KeyPath,
+        options: &CreateIndexOptions,
+    ) -> Self {
+        self.steps.push(MigrationStep::CreateIndex {
+            store: store.to_string(),
+            name: name.to_string(),
+            key_path: key_path.clone(),
+            options: *options,
+        });
+
+        self
+    }
+
+    pub fn delete_index(mut self, store: &str, name: &str) -> Self {
+        self.steps.push(MigrationStep::DeleteIndex {
+            store: store.to_string(),
+            name: name.to_string(),
+        });
+
+        self
+    }
+
+    pub fn rename_index(mut self, store: &str, name: &str, new_name: &str) -> Self {
+        self.steps.push(MigrationStep::RenameIndex {
+            store: store.to_string(),
+            name: name.to_string(),
+            new_name: new_name.to_string(),
+        });
+
+        self
+    }
+
+    /// Adds a custom step, e.g. to migrate the data in existing object stores.
+    ///
+    /// The `migrate` function is called synchronously with the upgrade transaction. It may issue
+    /// requests against the transaction; these complete before the upgraded database is opened.
+    /// If `migrate` returns an error, then the migration fails with a [MigrationError::Custom]
+    /// error and the upgrade is aborted.
+    pub fn migrate_data<F, E>(mut self, migrate: F) -> Self
+    where
+        F: FnOnce(&UpgradeTransaction) -> Result<(), E> + 'static,
+        E: Into<Box<dyn Error>>,
+    {
+        self.steps
+            .push(MigrationStep::Custom(Box::new(move |transaction| {
+                migrate(transaction).map_err(Into::into)
+            })));
+
+        self
+    }
+}
+
+impl Default for Migration {
+    fn default() -> Self {
+        Migration::new()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct IndexModel {
+    key_path: String,
+    unique: bool,
+    multi_entry: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct StoreModel {
+    key_path: Option<String>,
+    auto_increment: bool,
+    indexes: BTreeMap<String, IndexModel>,
+}
+
+/// A step in a [Migration] failed.
+#[derive(Clone)]
+pub enum MigrationError {
+    GetObjectStore(GetObjectStoreError),
+    CreateObjectStore(CreateObjectStoreError),
+    DeleteObjectStore(DeleteObjectStoreError),
+    Index(IndexError),
+    CreateIndex(CreateIndexError),
+    DeleteIndex(DeleteIndexError),
+    /// A [migrate_data](Migration::migrate_data) step returned an error.
+    Custom(Rc<dyn Error>),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::GetObjectStore(err) => fmt::Display::fmt(err, f),
+            MigrationError::CreateObjectStore(err) => fmt::Display::fmt(err, f),
+            MigrationError::DeleteObjectStore(err) => fmt::Display::fmt(err, f),
+            MigrationError::Index(err) => fmt::Display::fmt(err, f),
+            MigrationError::CreateIndex(err) => fmt::Display::fmt(err, f),
+            MigrationError::DeleteIndex(err) => fmt::Display::fmt(err, f),
+            MigrationError::Custom(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl fmt::Debug for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::GetObjectStore(err) => {
+                f.debug_tuple("GetObjectStore").field(err).finish()
+            }
+            MigrationError::CreateObjectStore(err) => {
+                f.debug_tuple("CreateObjectStore").field(err).finish()
+            }
+            MigrationError::DeleteObjectStore(err) => {
+                f.debug_tuple("DeleteObjectStore").field(err).finish()
+            }
+            MigrationError::Index(err) => f.debug_tuple("Index").field(err).finish(),
+            MigrationError::CreateIndex(err) => f.debug_tuple("CreateIndex").field(err).finish(),
+            MigrationError::DeleteIndex(err) => f.debug_tuple("DeleteIndex").field(err).finish(),
+            MigrationError::Custom(err) => f.debug_tuple("Custom").field(err).finish(),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+macro_rules! impl_from_for_migration_error {
+    ($($variant:ident($error:ident)),*) => {
+        $(
+            impl From<$error> for MigrationError {
+                fn from(err: $error) -> Self {
+                    MigrationError::$variant(err)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_migration_error!(
+    GetObjectStore(GetObjectStoreError),
+    CreateObjectStore(CreateObjectStoreError),
+    DeleteObjectStore(DeleteObjectStoreError),
+    Index(IndexError),
+    CreateIndex(CreateIndexError),
+    DeleteIndex(DeleteIndexError)
+);
+
+/// A difference between the object stores in a database and the object stores declared by a
+/// [Schema].
+#[derive(Clone, PartialEq, Debug)]
+pub enum SchemaMismatch {
+    MissingObjectStore(String),
+    UnexpectedObjectStore(String),
+    KeyConfig { store: String },
+    MissingIndex { store: String, index: String },
+    UnexpectedIndex { store: String, index: String },
+    IndexConfig { store: String, index: String },
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaMismatch::MissingObjectStore(store) => {
+                write!(f, "object store `{}` is missing", store)
+            }
+            SchemaMismatch::UnexpectedObjectStore(store) => {
+                write!(f, "object store `{}` is not declared", store)
+            }
+            SchemaMismatch::KeyConfig { store } => write!(
+                f,
+                "the key configuration of object store `{}` does not match the declaration",
+                store
+            ),
+            SchemaMismatch::MissingIndex { store, index } => {
+                write!(
+                    f,
+                    "index `{}` on object store `{}` is missing",
+                    index, store
+                )
+            }
+            SchemaMismatch::UnexpectedIndex { store, index } => write!(
+                f,
+                "index `{}` on object store `{}` is not declared",
+                index, store
+            ),
+            SchemaMismatch::IndexConfig { store, index } => write!(
+                f,
+                "index `{}` on object store `{}` does not match the declaration",
+                index, store
+            ),
+        }
+    }
+}
+
+/// Error returned by [Schema::apply].
+#[derive(Clone)]
+pub enum SchemaError {
+    /// A step in the migration to the given `version` failed.
+    Migration { version: u32, error: MigrationError },
+    /// The migrations were applied, but the resulting database does not match the schema.
+    Mismatch(SchemaMismatch),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Migration { version, error } => {
+                write!(f, "migration to version {} failed: {}", version, error)
+            }
+            SchemaError::Mismatch(mismatch) => {
+                write!(f, "database does not match the schema: {}", mismatch)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Migration { version, error } => f
+                .debug_struct("Migration")
+                .field("version", version)
+                .field("error", error)
+                .finish(),
+            SchemaError::Mismatch(mismatch) => f.debug_tuple("Mismatch").field(mismatch).finish(),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/// Error returned by the future returned from [Schema::open_database].
+#[derive(Clone)]
+pub enum OpenSchemaDatabaseError {
+    /// The request to open the database failed.
+    Request(RequestOpenDatabaseError),
+    /// The schema could not be applied to the database; the upgrade was aborted.
+    Schema(SchemaError),
+}
+
+impl fmt::Display for OpenSchemaDatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenSchemaDatabaseError::Request(err) => fmt::Display::fmt(err, f),
+            OpenSchemaDatabaseError::Schema(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl fmt::Debug for OpenSchemaDatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenSchemaDatabaseError::Request(err) => f.debug_tuple("Request").field(err).finish(),
+            OpenSchemaDatabaseError::Schema(err) => f.debug_tuple("Schema").field(err).finish(),
+        }
+    }
+}
+
+impl Error for OpenSchemaDatabaseError {}
+
+/// Declares the versions of a database as an ordered list of [Migration]s.
+///
+/// When a database is opened with [Schema::open_database], the migrations for all versions after
+/// the database's current version are applied in order, after which the database is checked
+/// against the object stores and indexes declared by all migrations combined:
+///
+/// ```rust,ignore
+/// let schema = Schema::new()
+///     .version(
+///         1,
+///         Migration::new()
+///             .create_object_store("books", Some(KeyConfig {
+///                 path: key_path!("id"),
+///                 auto_increment: false,
+///             }))
+///             .create_index("books", "author", &key_path!("author"), &Default::default()),
+///     )
+///     .version(2, Migration::new().rename_index("books", "author", "by_author"));
+///
+/// let database = schema.open_database(&indexed_db, "book-store", || ())?.await?;
+/// ```
+pub struct Schema {
+    // Each migration is paired with the object stores declared up to and including its version.
+    migrations: Vec<(u32, Migration, BTreeMap<String, StoreModel>)>,
+    stores: BTreeMap<String, StoreModel>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Schema {
+            migrations: Vec::new(),
+            stores: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the `migration` that upgrades the database to the given `version`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `version` is `0` or is not greater than the previously added version, or if
+    /// the `migration` is inconsistent with the previous migrations (e.g. it creates an index on
+    /// an object store that does not exist).
+    pub fn version(mut self, version: u32, migration: Migration) -> Self {
+        if version <= self.latest_version().unwrap_or(0) {
+            throw_str("Schema versions must be greater than `0` and strictly increasing");
+        }
+
+        for step in &migration.steps {
+            self.declare(version, step);
+        }
+
+        self.migrations
+            .push((version, migration, self.stores.clone()));
+
+        self
+    }
+
+    /// Returns the latest version declared by this schema, or `None` if no versions were added.
+    pub fn latest_version(&self) -> Option<u32> {
+        self.migrations.last().map(|(version, ..)| *version)
+    }
+
+    fn declare(&mut self, version: u32, step: &MigrationStep) {
+        match step {
+            MigrationStep::CreateObjectStore { name, key_config } => {
+                if self.stores.contains_key(name) {
+                    invalid_migration(version, &format!("object store `{}` already exists", name));
+                }
+
+                self.stores.insert(
+                    name.clone(),
+                    StoreModel {
+                        key_path: key_config
+                            .as_ref()
+                            .map(|config| config.path.as_ref().to_string()),
+                        auto_increment: key_config
+                            .as_ref()
+                            .map(|config| config.auto_increment)
+                            .unwrap_or(false),
+                        indexes: BTreeMap::new(),
+                    },
+                );
+            }
+            MigrationStep::DeleteObjectStore { name } => {
+                if self.stores.remove(name).is_none() {
+                    invalid_migration(version, &format!("object store `{}` does not exist", name));
+                }
+            }
+            MigrationStep::RenameObjectStore { name, new_name } => {
+                if self.stores.contains_key(new_name) {
+                    invalid_migration(
+                        version,
+                        &format!("object store `{}` already exists", new_name),
+                    );
+                }
+
+                match self.stores.remove(name) {
+                    Some(store) => {
+                        self.stores.insert(new_name.clone(), store);
+                    }
+                    None => invalid_migration(
+                        version,
+                        &format!("object store `{}` does not exist", name),
+                    ),
+                }
+            }
+            MigrationStep::CreateIndex {
+                store,
+                name,
+                key_path,
+                options,
+            } => {
+                let Some(model) = self.stores.get_mut(store) else {
+                    invalid_migration(version, &format!("object store `{}` does not exist", store));
+                };
+
+                if model.indexes.contains_key(name) {
+                    invalid_migration(
+                        version,
+                        &format!(
+                            "index `{}` on object store `{}` already exists",
+                            name, store
+                        ),
+                    );
+                }
+
+                model.indexes.insert(
+                    name.clone(),
+                    IndexModel {
+                        key_path: key_path.as_ref().to_string(),
+                        unique: options.unique,
+                        multi_entry: options.multi_entry,
+                    },
+                );
+            }
+            MigrationStep::DeleteIndex { store, name } => {
+                let Some(model) = self.stores.get_mut(store) else {
+                    invalid_migration(version, &format!("object store `{}` does not exist", store));
+                };
+
+                if model.indexes.remove(name).is_none() {
+                    invalid_migration(
+                        version,
+                        &format!(
+                            "index `{}` on object store `{}` does not exist",
+                            name, store
+                        ),
+                    );
+                }
+            }
+            MigrationStep::RenameIndex {
+                store,
+                name,
+                new_name,
+            } => {
+                let Some(model) = self.stores.get_mut(store) else {
+                    invalid_migration(version, &format!("object store `{}` does not exist", store));
+                };
+
+                if model.indexes.contains_key(new_name) {
+                    invalid_migration(
+                        version,
+                        &format!(
+                            "index `{}` on object store `{}` already exists",
+                            new_name, store
+                        ),
+                    );
+                }
+
+                match model.indexes.remove(name) {
+                    Some(index) => {
+                        model.indexes.insert(new_name.clone(), index);
+                    }
+                    None => invalid_migration(
+                        version,
+                        &format!(
+                            "index `{}` on object store `{}` does not exist",
+                            name, store
+                        ),
+                    ),
+                }
+            }
+            MigrationStep::Custom(_) => {}
+        }
+    }
+
+    /// Applies the migrations for all versions greater than `old_version` and not greater than
+    /// `new_version`, and then verifies that the database matches the object stores and indexes
+    /// declared by the migrations up to `new_version`.
+    ///
+    /// If this returns an error, then the database may have been partially upgraded; the caller
+    /// should abort the `transaction`.
+    pub fn apply(
+        self,
+        transaction: &mut UpgradeTransaction,
+        old_version: u32,
+        new_version: u32,
+    ) -> Result<(), SchemaError> {
+        let mut expected = BTreeMap::new();
+
+        for (version, migration, stores) in self.migrations {
+            if version > new_version {
+                break;
+            }
+
+            expected = stores;
+
+            if version <= old_version {
+                continue;
+            }
+
+            for step in migration.steps {
+                step.apply(transaction)
+                    .map_err(|error| SchemaError::Migration { version, error })?;
+            }
+        }
+
+        verify(transaction, &expected).map_err(SchemaError::Mismatch)
+    }
+
+    /// Opens the database with the given `name` at the schema's
+    /// [latest_version](Schema::latest_version), applying this schema if the database needs to be
+    /// upgraded. If the schema declares no versions, then the database is opened at its current
+    /// version (or at version `1` if it does not exist yet).
+    ///
+    /// If the schema cannot be applied, then the upgrade transaction is aborted and the returned
+    /// future resolves with an [OpenSchemaDatabaseError::Schema] error. The `blocked` callback is
+    /// invoked if other connections to the database prevent the upgrade from starting.
+    pub fn open_database<Blocked>(
+        self,
+        factory: &Factory,
+        name: &str,
+        blocked: Blocked,
+    ) -> Result<impl Future<Output = Result<Database, OpenSchemaDatabaseError>>, OpenDatabaseError>
+    where
+        Blocked: FnOnce() + 'static,
+    {
+        let version = self
+            .latest_version()
+            .map(DatabaseVersion::Number)
+            .unwrap_or_default();
+
+        let schema_error = Rc::new(RefCell::new(None));
+        let schema_error_clone = schema_error.clone();
+
+        let upgrade_needed =
+            move |mut transaction: UpgradeTransaction, old_version, new_version| {
+                if let Err(err) = self.apply(&mut transaction, old_version, new_version) {
+                    *schema_error_clone.borrow_mut() = Some(err);
+
+                    transaction.abort();
+                }
+            };
+
+        let open = factory.open_database(OpenDataBaseRequest {
+            name,
+            version,
+            upgrade_needed,
+            blocked,
+        })?;
+
+        Ok(async move {
+            open.await
+                .map_err(|err| match schema_error.borrow_mut().take() {
+                    Some(err) => OpenSchemaDatabaseError::Schema(err),
+                    None => OpenSchemaDatabaseError::Request(err),
+                })
+        })
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::new()
+    }
+}
+
+fn invalid_migration(version: u32, message: &str) -> ! {
+    throw_str(&format!(
+        "invalid migration to version {}: {}",
+        version, message
+    ))
+}
+
+fn verify(
+    transaction: &UpgradeTransaction,
+    stores: &BTreeMap<String, StoreModel>,
+) -> Result<(), SchemaMismatch> {
+    let store_names: Vec<String> = transaction.object_store_names().collect();
+
+    if let Some(name) = store_names
+        .iter()
+        .find(|name| !stores.contains_key(name.as_str()))
+    {
+        return Err(SchemaMismatch::UnexpectedObjectStore(name.clone()));
+    }
+
+    for (name, model) in stores {
+        if !store_names.contains(name) {
+            return Err(SchemaMismatch::MissingObjectStore(name.clone()));
+        }
+
+        let store = transaction
+            .object_store(name)
+            .map_err(|_| SchemaMismatch::MissingObjectStore(name.clone()))?;
+
+        // Schemas only declare single key paths; any other kind of key path never matches.
+        let key_path = match store.key_path() {
+            Some(StoredKeyPath::Path(path)) => Some(path.as_ref().to_string()),
+            Some(_) => {
+                return Err(SchemaMismatch::KeyConfig {
+                    store: name.clone(),
+                })
+            }
+            None => None,
+        };
+
+        if key_path != model.key_path || store.auto_increment() != model.auto_increment {
+            return Err(SchemaMismatch::KeyConfig {
+                store: name.clone(),
+            });
+        }
+
+        let index_names: Vec<String> = store.index_names().collect();
+
+        if let Some(index) = index_names
+            .iter()
+            .find(|index| !model.indexes.contains_key(index.as_str()))
+        {
+            return Err(SchemaMismatch::UnexpectedIndex {
+                store: name.clone(),
+                index: index.clone(),
+            });
+        }
+
+        for (index_name, index_model) in &model.indexes {
+            let missing = || SchemaMismatch::MissingIndex {
+                store: name.clone(),
+                index: index_name.clone(),
+            };
+
+            if !index_names.contains(index_name) {
+                return Err(missing());
+            }
+
+            let index = store.index(index_name).map_err(|_| missing())?;
+            let mismatch = || SchemaMismatch::IndexConfig {
+                store: name.clone(),
+                index: index_name.clone(),
+            };
+
+            let StoredKeyPath::Path(key_path) = index.key_path() else {
+                return Err(mismatch());
+            };
+
+            let actual = IndexModel {
+                key_path: key_path.as_ref().to_string(),
+                unique: index.unique(),
+                multi_entry: index.multi_entry(),
+            };
+
+            if &actual != index_model {
+                return Err(mismatch());
+            }
+        }
+    }
+
+    Ok(())
+}
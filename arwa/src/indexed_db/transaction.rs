@@ -3,6 +3,7 @@ use std::marker;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
 use crate::dom_exception_wrapper;
+use crate::indexed_db::error_kind::idb_error_kind;
 use crate::indexed_db::KeyPath;
 
 pub struct KeyConfig {
@@ -25,6 +26,35 @@ dom_exception_wrapper!(GetObjectStoreError);
 dom_exception_wrapper!(CreateObjectStoreError);
 dom_exception_wrapper!(DeleteObjectStoreError);
 
+idb_error_kind!(GetObjectStoreError => GetObjectStoreErrorKind {
+    /// The transaction has finished.
+    InvalidState => "InvalidStateError",
+    /// There is no object store with the given name in the transaction's scope.
+    NotFound => "NotFoundError",
+});
+
+idb_error_kind!(CreateObjectStoreError => CreateObjectStoreErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The upgrade transaction has finished.
+    InvalidState => "InvalidStateError",
+    /// The key path is not a valid key path.
+    Syntax => "SyntaxError",
+    /// An object store with the given name already exists.
+    Constraint => "ConstraintError",
+    /// The key configuration enables `auto_increment`, but the key path is empty.
+    InvalidAccess => "InvalidAccessError",
+});
+
+idb_error_kind!(DeleteObjectStoreError => DeleteObjectStoreErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The upgrade transaction has finished.
+    InvalidState => "InvalidStateError",
+    /// There is no object store with the given name.
+    NotFound => "NotFoundError",
+});
+
 pub mod transaction_capability {
     mod write_seal {
         pub trait Seal {}
@@ -69,7 +99,19 @@ use futures::TryFutureExt;
 use js_sys::Array;
 use transaction_capability::*;
 
+use crate::indexed_db::cursor_stream::CursorSource;
 use crate::indexed_db::request_future::RequestFuture;
+use crate::indexed_db::{
+    decode_record, key_from_js, key_query_to_js, key_to_js, CursorStream, DecodeRecordError,
+    IdbRecord, IntoKey, IntoKeyQuery, Key, StoredKeyPath, TypedObjectStore,
+};
+
+fn keys_from_js_array(array: Array) -> Vec<Key> {
+    array
+        .iter()
+        .map(|key| key_from_js(&key).unwrap_throw())
+        .collect()
+}
 
 pub type ReadOnlyTransaction = Transaction<R>;
 pub type ReadWriteTransaction = Transaction<RW>;
@@ -119,6 +161,16 @@ impl<Capabilities> Transaction<Capabilities> {
         self.inner.abort().unwrap_throw();
     }
 
+    /// Returns the names of the object stores in this transaction's scope.
+    ///
+    /// For an upgrade transaction, this includes all object stores in the database.
+    pub fn object_store_names(&self) -> ObjectStoreNames {
+        ObjectStoreNames {
+            inner: self.inner.object_store_names(),
+            current: 0,
+        }
+    }
+
     pub fn object_store(
         &self,
         name: &str,
@@ -160,15 +212,31 @@ impl<Capabilities> Transaction<Capabilities> {
     }
 }
 
+pub struct ObjectStoreNames {
+    inner: web_sys::DomStringList,
+    current: u32,
+}
+
+impl Iterator for ObjectStoreNames {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.inner.get(self.current);
+
+        if current.is_some() {
+            self.current += 1;
+        }
+
+        current
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug, Default)]
 pub struct CreateIndexOptions {
     pub unique: bool,
     pub multi_entry: bool,
 }
 
-// TODO: add methods that an enum for these error types that disambiguate the errors that can occur
-// for each of the individual operations per the spec
-
 dom_exception_wrapper!(CountError);
 dom_exception_wrapper!(CountWithQueryError);
 dom_exception_wrapper!(GetError);
@@ -206,6 +274,291 @@ dom_exception_wrapper!(RequestOpenCursorWithQueryError);
 dom_exception_wrapper!(RequestOpenKeyCursorError);
 dom_exception_wrapper!(RequestOpenKeyCursorWithQueryError);
 
+idb_error_kind!(CountError => CountErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+});
+
+idb_error_kind!(CountWithQueryError => CountWithQueryErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(GetError => GetErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(GetKeyError => GetKeyErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(GetAllError => GetAllErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(GetAllKeysError => GetAllKeysErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(AddError => AddErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+    /// The object store uses out-of-line keys and does not have a key generator, or the value
+    /// does not contain a valid key at the store's key path.
+    Data => "DataError",
+    /// The value could not be cloned.
+    DataClone => "DataCloneError",
+});
+
+idb_error_kind!(AddWithQueryError => AddWithQueryErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+    /// The object store uses in-line keys, or the key is not valid.
+    Data => "DataError",
+    /// The value could not be cloned.
+    DataClone => "DataCloneError",
+});
+
+idb_error_kind!(PutError => PutErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+    /// The object store uses out-of-line keys and does not have a key generator, or the value
+    /// does not contain a valid key at the store's key path.
+    Data => "DataError",
+    /// The value could not be cloned.
+    DataClone => "DataCloneError",
+});
+
+idb_error_kind!(PutWithQueryError => PutWithQueryErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+    /// The object store uses in-line keys, or the key is not valid.
+    Data => "DataError",
+    /// The value could not be cloned.
+    DataClone => "DataCloneError",
+});
+
+idb_error_kind!(DeleteError => DeleteErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(ClearError => ClearErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+});
+
+idb_error_kind!(IndexError => IndexErrorKind {
+    /// The object store has been deleted, or the transaction has finished.
+    InvalidState => "InvalidStateError",
+    /// The object store does not have an index with the given name.
+    NotFound => "NotFoundError",
+});
+
+idb_error_kind!(CreateIndexError => CreateIndexErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The key path is not a valid key path.
+    Syntax => "SyntaxError",
+    /// The object store already has an index with the given name.
+    Constraint => "ConstraintError",
+    /// The index is a multi-entry index, but the key path is an array.
+    InvalidAccess => "InvalidAccessError",
+});
+
+idb_error_kind!(DeleteIndexError => DeleteIndexErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The object store does not have an index with the given name.
+    NotFound => "NotFoundError",
+});
+
+idb_error_kind!(OpenCursorError => OpenCursorErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+});
+
+idb_error_kind!(OpenCursorWithQueryError => OpenCursorWithQueryErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(OpenKeyCursorError => OpenKeyCursorErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+});
+
+idb_error_kind!(OpenKeyCursorWithQueryError => OpenKeyCursorWithQueryErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The object store or index has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The query is not a valid key.
+    Data => "DataError",
+});
+
+idb_error_kind!(RequestCountError => RequestCountErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestCountWithQueryError => RequestCountWithQueryErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestGetError => RequestGetErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestGetKeyError => RequestGetKeyErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestGetAllError => RequestGetAllErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestGetAllKeysError => RequestGetAllKeysErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestAddError => RequestAddErrorKind {
+    /// A record with the same key already exists, or the record violates the uniqueness
+    /// constraint of an index.
+    Constraint => "ConstraintError",
+    /// The storage quota was exceeded.
+    QuotaExceeded => "QuotaExceededError",
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestAddWithQueryError => RequestAddWithQueryErrorKind {
+    /// A record with the same key already exists, or the record violates the uniqueness
+    /// constraint of an index.
+    Constraint => "ConstraintError",
+    /// The storage quota was exceeded.
+    QuotaExceeded => "QuotaExceededError",
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestPutError => RequestPutErrorKind {
+    /// The record violates the uniqueness constraint of an index.
+    Constraint => "ConstraintError",
+    /// The storage quota was exceeded.
+    QuotaExceeded => "QuotaExceededError",
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestPutWithQueryError => RequestPutWithQueryErrorKind {
+    /// The record violates the uniqueness constraint of an index.
+    Constraint => "ConstraintError",
+    /// The storage quota was exceeded.
+    QuotaExceeded => "QuotaExceededError",
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestDeleteError => RequestDeleteErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestClearError => RequestClearErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestOpenCursorError => RequestOpenCursorErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestOpenCursorWithQueryError => RequestOpenCursorWithQueryErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestOpenKeyCursorError => RequestOpenKeyCursorErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestOpenKeyCursorWithQueryError => RequestOpenKeyCursorWithQueryErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
 pub struct ObjectStore<Capabilities> {
     inner: web_sys::IdbObjectStore,
     _capabilities: marker::PhantomData<Capabilities>,
@@ -230,18 +583,30 @@ impl<Capabilities> ObjectStore<Capabilities> {
         self.inner.set_name(name);
     }
 
-    pub fn key_path(&self) -> Option<KeyPath> {
-        self.inner.key_path().ok().map(|raw| {
-            let raw = raw.as_string().unwrap_or_default();
-
-            KeyPath::parse(&raw.to_string()).unwrap_throw()
-        })
+    /// Returns the key path of the object store, or `None` if the store uses out-of-line keys.
+    pub fn key_path(&self) -> Option<StoredKeyPath> {
+        // The key path is `null` if the store uses out-of-line keys.
+        self.inner
+            .key_path()
+            .ok()
+            .and_then(|raw| StoredKeyPath::from_js(&raw))
     }
 
     pub fn auto_increment(&self) -> bool {
         self.inner.auto_increment()
     }
 
+    /// Returns a view of this object store that reads and writes records of type `T`.
+    ///
+    /// Values are encoded and decoded as structured clones, see [IdbRecord]. Reading a stored value
+    /// that does not match `T` results in a [DecodeRecordError].
+    pub fn typed<T>(self) -> TypedObjectStore<T, Capabilities>
+    where
+        T: IdbRecord,
+    {
+        TypedObjectStore::new(self)
+    }
+
     pub fn index_names(&self) -> StoreIndexNames {
         StoreIndexNames {
             inner: self.inner.index_names(),
@@ -262,15 +627,15 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| CountError::new(err.unchecked_into()))
     }
 
-    pub fn count_with_query<K>(
+    pub fn count_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<u32, RequestCountWithQueryError>>, CountWithQueryError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .count_with_key(query.as_ref())
+            .count_with_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| v.as_f64().unwrap_throw() as u32)
@@ -279,15 +644,15 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| CountWithQueryError::new(err.unchecked_into()))
     }
 
-    pub fn get<K>(
+    pub fn get<Q>(
         &self,
-        key: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<Option<JsValue>, RequestGetError>>, GetError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get(key.as_ref())
+            .get(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| if v.is_undefined() { None } else { Some(v) })
@@ -296,18 +661,18 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| GetError::new(err.unchecked_into()))
     }
 
-    pub fn get_key<K>(
+    pub fn get_key<Q>(
         &self,
-        key: &K,
-    ) -> Result<impl Future<Output = Result<Option<JsValue>, RequestGetKeyError>>, GetKeyError>
+        query: Q,
+    ) -> Result<impl Future<Output = Result<Option<Key>, RequestGetKeyError>>, GetKeyError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get_key(key.as_ref())
+            .get_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
-                    .map_ok(|v| if v.is_undefined() { None } else { Some(v) })
+                    .map_ok(|v| key_from_js(&v))
                     .map_err(|err| RequestGetKeyError::new(err))
             })
             .map_err(|err| GetKeyError::new(err.unchecked_into()))
@@ -328,26 +693,27 @@ impl<Capabilities> ObjectStore<Capabilities> {
 
     pub fn get_all_keys(
         &self,
-    ) -> Result<impl Future<Output = Result<Array, RequestGetAllKeysError>>, GetAllKeysError> {
+    ) -> Result<impl Future<Output = Result<Vec<Key>, RequestGetAllKeysError>>, GetAllKeysError>
+    {
         self.inner
             .get_all_keys()
             .map(|ok| {
                 RequestFuture::from_request(ok)
-                    .map_ok(|v| v.unchecked_into())
+                    .map_ok(|v| keys_from_js_array(v.unchecked_into()))
                     .map_err(|err| RequestGetAllKeysError::new(err))
             })
             .map_err(|err| GetAllKeysError::new(err.unchecked_into()))
     }
 
-    pub fn get_all_with_key<K>(
+    pub fn get_all_with_key<Q>(
         &self,
-        key: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<Array, RequestGetAllError>>, GetAllError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get_all_with_key(key.as_ref())
+            .get_all_with_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| v.unchecked_into())
@@ -356,18 +722,18 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| GetAllError::new(err.unchecked_into()))
     }
 
-    pub fn get_all_keys_with_key<K>(
+    pub fn get_all_keys_with_key<Q>(
         &self,
-        key: &K,
-    ) -> Result<impl Future<Output = Result<Array, RequestGetAllKeysError>>, GetAllKeysError>
+        query: Q,
+    ) -> Result<impl Future<Output = Result<Vec<Key>, RequestGetAllKeysError>>, GetAllKeysError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get_all_keys_with_key(key.as_ref())
+            .get_all_keys_with_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
-                    .map_ok(|v| v.unchecked_into())
+                    .map_ok(|v| keys_from_js_array(v.unchecked_into()))
                     .map_err(|err| RequestGetAllKeysError::new(err))
             })
             .map_err(|err| GetAllKeysError::new(err.unchecked_into()))
@@ -396,19 +762,22 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| OpenCursorError::new(err.unchecked_into()))
     }
 
-    pub fn open_cursor_with_query<K>(
+    pub fn open_cursor_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
         direction: CursorDirection,
     ) -> Result<
         impl Future<Output = Result<Option<ValueCursor<Capabilities>>, RequestOpenCursorWithQueryError>>,
         OpenCursorWithQueryError,
     >
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .open_cursor_with_range_and_direction(query.as_ref(), direction.to_web_sys())
+            .open_cursor_with_range_and_direction(
+                &key_query_to_js(&query.into_key_query()),
+                direction.to_web_sys(),
+            )
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| {
@@ -446,9 +815,9 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| OpenKeyCursorError::new(err.unchecked_into()))
     }
 
-    pub fn open_key_cursor_with_query<K>(
+    pub fn open_key_cursor_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
         direction: CursorDirection,
     ) -> Result<
         impl Future<
@@ -457,10 +826,13 @@ impl<Capabilities> ObjectStore<Capabilities> {
         OpenKeyCursorWithQueryError,
     >
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .open_key_cursor_with_range_and_direction(query.as_ref(), direction.to_web_sys())
+            .open_key_cursor_with_range_and_direction(
+                &key_query_to_js(&query.into_key_query()),
+                direction.to_web_sys(),
+            )
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| {
@@ -475,6 +847,22 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| OpenKeyCursorWithQueryError::new(err.unchecked_into()))
     }
 
+    /// Returns a [Stream] over the records that match the `query`, in the given `direction`.
+    ///
+    /// Use `..` as the `query` to iterate over all records. See [CursorStream] for details.
+    ///
+    /// [Stream]: futures::Stream
+    pub fn iter<Q>(&self, query: Q, direction: CursorDirection) -> CursorStream<Capabilities>
+    where
+        Q: IntoKeyQuery,
+    {
+        CursorStream::new(
+            CursorSource::Store(self.inner.clone()),
+            query.into_key_query(),
+            direction,
+        )
+    }
+
     pub fn add<V>(
         &self,
         value: &V,
@@ -495,16 +883,16 @@ impl<Capabilities> ObjectStore<Capabilities> {
 
     pub fn add_with_key<K, V>(
         &self,
-        key: &K,
+        key: K,
         value: &V,
     ) -> Result<impl Future<Output = Result<(), RequestAddWithQueryError>>, AddWithQueryError>
     where
         Capabilities: Write,
-        K: AsRef<JsValue>,
+        K: IntoKey,
         V: AsRef<JsValue>,
     {
         self.inner
-            .add_with_key(value.as_ref(), key.as_ref())
+            .add_with_key(value.as_ref(), &key_to_js(&key.into_key()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|_| ())
@@ -533,16 +921,16 @@ impl<Capabilities> ObjectStore<Capabilities> {
 
     pub fn put_with_key<K, V>(
         &self,
-        key: &K,
+        key: K,
         value: &V,
     ) -> Result<impl Future<Output = Result<(), RequestPutWithQueryError>>, PutWithQueryError>
     where
         Capabilities: Write,
-        K: AsRef<JsValue>,
+        K: IntoKey,
         V: AsRef<JsValue>,
     {
         self.inner
-            .put_with_key(value.as_ref(), key.as_ref())
+            .put_with_key(value.as_ref(), &key_to_js(&key.into_key()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|_| ())
@@ -551,16 +939,16 @@ impl<Capabilities> ObjectStore<Capabilities> {
             .map_err(|err| PutWithQueryError::new(err.unchecked_into()))
     }
 
-    pub fn delete<K>(
+    pub fn delete<Q>(
         &self,
-        key: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<(), RequestDeleteError>>, DeleteError>
     where
         Capabilities: Write,
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .delete(key.as_ref())
+            .delete(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|_| ())
@@ -653,15 +1041,41 @@ impl<Capabilities> Index<Capabilities> {
         }
     }
 
-    pub fn count_with_query<K>(
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    pub fn set_name(&self, name: &str)
+    where
+        Capabilities: Upgrade,
+    {
+        self.inner.set_name(name);
+    }
+
+    pub fn key_path(&self) -> StoredKeyPath {
+        // An index always has a key path; the empty key path indexes the value itself.
+        let raw = self.inner.key_path().unwrap_throw();
+
+        StoredKeyPath::from_js(&raw).unwrap_or(StoredKeyPath::Value)
+    }
+
+    pub fn unique(&self) -> bool {
+        self.inner.unique()
+    }
+
+    pub fn multi_entry(&self) -> bool {
+        self.inner.multi_entry()
+    }
+
+    pub fn count_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<u32, RequestCountWithQueryError>>, CountWithQueryError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .count_with_key(query.as_ref())
+            .count_with_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| v.as_f64().unwrap_throw() as u32)
@@ -670,15 +1084,15 @@ impl<Capabilities> Index<Capabilities> {
             .map_err(|err| CountWithQueryError::new(err.unchecked_into()))
     }
 
-    pub fn get<K>(
+    pub fn get<Q>(
         &self,
-        key: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<Option<JsValue>, RequestGetError>>, GetError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get(key.as_ref())
+            .get(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| if v.is_undefined() { None } else { Some(v) })
@@ -687,18 +1101,18 @@ impl<Capabilities> Index<Capabilities> {
             .map_err(|err| GetError::new(err.unchecked_into()))
     }
 
-    pub fn get_key<K>(
+    pub fn get_key<Q>(
         &self,
-        key: &K,
-    ) -> Result<impl Future<Output = Result<Option<JsValue>, RequestGetKeyError>>, GetKeyError>
+        query: Q,
+    ) -> Result<impl Future<Output = Result<Option<Key>, RequestGetKeyError>>, GetKeyError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get_key(key.as_ref())
+            .get_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
-                    .map_ok(|v| if v.is_undefined() { None } else { Some(v) })
+                    .map_ok(|v| key_from_js(&v))
                     .map_err(|err| RequestGetKeyError::new(err))
             })
             .map_err(|err| GetKeyError::new(err.unchecked_into()))
@@ -719,26 +1133,27 @@ impl<Capabilities> Index<Capabilities> {
 
     pub fn get_all_keys(
         &self,
-    ) -> Result<impl Future<Output = Result<Array, RequestGetAllKeysError>>, GetAllKeysError> {
+    ) -> Result<impl Future<Output = Result<Vec<Key>, RequestGetAllKeysError>>, GetAllKeysError>
+    {
         self.inner
             .get_all_keys()
             .map(|ok| {
                 RequestFuture::from_request(ok)
-                    .map_ok(|v| v.unchecked_into())
+                    .map_ok(|v| keys_from_js_array(v.unchecked_into()))
                     .map_err(|err| RequestGetAllKeysError::new(err))
             })
             .map_err(|err| GetAllKeysError::new(err.unchecked_into()))
     }
 
-    pub fn get_all_with_query<K>(
+    pub fn get_all_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
     ) -> Result<impl Future<Output = Result<Array, RequestGetAllError>>, GetAllError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get_all_with_key(query.as_ref())
+            .get_all_with_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| v.unchecked_into())
@@ -747,18 +1162,18 @@ impl<Capabilities> Index<Capabilities> {
             .map_err(|err| GetAllError::new(err.unchecked_into()))
     }
 
-    pub fn get_all_keys_with_query<K>(
+    pub fn get_all_keys_with_query<Q>(
         &self,
-        query: &K,
-    ) -> Result<impl Future<Output = Result<Array, RequestGetAllKeysError>>, GetAllKeysError>
+        query: Q,
+    ) -> Result<impl Future<Output = Result<Vec<Key>, RequestGetAllKeysError>>, GetAllKeysError>
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .get_all_keys_with_key(query.as_ref())
+            .get_all_keys_with_key(&key_query_to_js(&query.into_key_query()))
             .map(|ok| {
                 RequestFuture::from_request(ok)
-                    .map_ok(|v| v.unchecked_into())
+                    .map_ok(|v| keys_from_js_array(v.unchecked_into()))
                     .map_err(|err| RequestGetAllKeysError::new(err))
             })
             .map_err(|err| GetAllKeysError::new(err.unchecked_into()))
@@ -787,19 +1202,22 @@ impl<Capabilities> Index<Capabilities> {
             .map_err(|err| OpenCursorError::new(err.unchecked_into()))
     }
 
-    pub fn open_cursor_with_query<K>(
+    pub fn open_cursor_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
         direction: CursorDirection,
     ) -> Result<
         impl Future<Output = Result<Option<ValueCursor<Capabilities>>, RequestOpenCursorWithQueryError>>,
         OpenCursorWithQueryError,
     >
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .open_cursor_with_range_and_direction(query.as_ref(), direction.to_web_sys())
+            .open_cursor_with_range_and_direction(
+                &key_query_to_js(&query.into_key_query()),
+                direction.to_web_sys(),
+            )
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| {
@@ -837,9 +1255,9 @@ impl<Capabilities> Index<Capabilities> {
             .map_err(|err| OpenKeyCursorError::new(err.unchecked_into()))
     }
 
-    pub fn open_key_cursor_with_query<K>(
+    pub fn open_key_cursor_with_query<Q>(
         &self,
-        query: &K,
+        query: Q,
         direction: CursorDirection,
     ) -> Result<
         impl Future<
@@ -848,10 +1266,13 @@ impl<Capabilities> Index<Capabilities> {
         OpenKeyCursorWithQueryError,
     >
     where
-        K: AsRef<JsValue>,
+        Q: IntoKeyQuery,
     {
         self.inner
-            .open_key_cursor_with_range_and_direction(query.as_ref(), direction.to_web_sys())
+            .open_key_cursor_with_range_and_direction(
+                &key_query_to_js(&query.into_key_query()),
+                direction.to_web_sys(),
+            )
             .map(|ok| {
                 RequestFuture::from_request(ok)
                     .map_ok(|v| {
@@ -865,6 +1286,23 @@ impl<Capabilities> Index<Capabilities> {
             })
             .map_err(|err| OpenKeyCursorWithQueryError::new(err.unchecked_into()))
     }
+
+    /// Returns a [Stream] over the records whose index key matches the `query`, in the given
+    /// `direction`.
+    ///
+    /// Use `..` as the `query` to iterate over all records. See [CursorStream] for details.
+    ///
+    /// [Stream]: futures::Stream
+    pub fn iter<Q>(&self, query: Q, direction: CursorDirection) -> CursorStream<Capabilities>
+    where
+        Q: IntoKeyQuery,
+    {
+        CursorStream::new(
+            CursorSource::Index(self.inner.clone()),
+            query.into_key_query(),
+            direction,
+        )
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -882,7 +1320,7 @@ impl Default for CursorDirection {
 }
 
 impl CursorDirection {
-    fn to_web_sys(&self) -> web_sys::IdbCursorDirection {
+    pub(crate) fn to_web_sys(self) -> web_sys::IdbCursorDirection {
         match self {
             CursorDirection::Next => web_sys::IdbCursorDirection::Next,
             CursorDirection::NextUnique => web_sys::IdbCursorDirection::Nextunique,
@@ -892,7 +1330,6 @@ impl CursorDirection {
     }
 }
 
-dom_exception_wrapper!(CursorKeyError);
 dom_exception_wrapper!(CursorAdvanceError);
 dom_exception_wrapper!(CursorContinueKeyError);
 dom_exception_wrapper!(CursorContinuePrimaryKeyError);
@@ -905,6 +1342,91 @@ dom_exception_wrapper!(RequestCursorContinuePrimaryKeyError);
 dom_exception_wrapper!(RequestCursorUpdateError);
 dom_exception_wrapper!(RequestCursorDeleteError);
 
+idb_error_kind!(CursorAdvanceError => CursorAdvanceErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The cursor is already being iterated or has iterated past its end, or its source has been
+    /// deleted.
+    InvalidState => "InvalidStateError",
+});
+
+idb_error_kind!(CursorContinueKeyError => CursorContinueKeyErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The cursor is already being iterated or has iterated past its end, or its source has been
+    /// deleted.
+    InvalidState => "InvalidStateError",
+    /// The key is not valid, or is not beyond the cursor's current position in the cursor's
+    /// direction.
+    Data => "DataError",
+});
+
+idb_error_kind!(CursorContinuePrimaryKeyError => CursorContinuePrimaryKeyErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The cursor is already being iterated or has iterated past its end, or its source has been
+    /// deleted.
+    InvalidState => "InvalidStateError",
+    /// The cursor's source is not an index, or the cursor's direction is not
+    /// [CursorDirection::Next] or [CursorDirection::Prev].
+    InvalidAccess => "InvalidAccessError",
+    /// The key or primary key is not valid, or is not beyond the cursor's current position in the
+    /// cursor's direction.
+    Data => "DataError",
+});
+
+idb_error_kind!(CursorUpdateError => CursorUpdateErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The cursor is being iterated or has iterated past its end, or its source has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+    /// The object store uses in-line keys and the key in the value does not match the cursor's
+    /// primary key.
+    Data => "DataError",
+    /// The value could not be cloned.
+    DataClone => "DataCloneError",
+});
+
+idb_error_kind!(CursorDeleteError => CursorDeleteErrorKind {
+    /// The transaction is not active (e.g. because it was already committed or aborted).
+    TransactionInactive => "TransactionInactiveError",
+    /// The cursor is being iterated or has iterated past its end, or its source has been deleted.
+    InvalidState => "InvalidStateError",
+    /// The transaction is read-only.
+    ReadOnly => "ReadOnlyError",
+});
+
+idb_error_kind!(RequestCursorAdvanceError => RequestCursorAdvanceErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestCursorContinueKeyError => RequestCursorContinueKeyErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestCursorContinuePrimaryKeyError => RequestCursorContinuePrimaryKeyErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestCursorUpdateError => RequestCursorUpdateErrorKind {
+    /// The record violates the uniqueness constraint of an index.
+    Constraint => "ConstraintError",
+    /// The storage quota was exceeded.
+    QuotaExceeded => "QuotaExceededError",
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
+idb_error_kind!(RequestCursorDeleteError => RequestCursorDeleteErrorKind {
+    /// The transaction was aborted before the request completed.
+    Abort => "AbortError",
+});
+
 mod cursor_seal {
     pub trait Seal {
         #[doc(hidden)]
@@ -923,26 +1445,18 @@ pub trait Cursor: cursor_seal::Seal {
         }
     }
 
-    fn key(&self) -> Option<JsValue> {
+    fn key(&self) -> Option<Key> {
         // I can't find any indication in the spec that this can actually fail
         let v = self.as_web_sys().key().unwrap_throw();
 
-        if v.is_undefined() {
-            None
-        } else {
-            Some(v)
-        }
+        key_from_js(&v)
     }
 
-    fn primary_key(&self) -> Option<JsValue> {
+    fn primary_key(&self) -> Option<Key> {
         // I can't find any indication in the spec that this can actually fail
         let v = self.as_web_sys().primary_key().unwrap_throw();
 
-        if v.is_undefined() {
-            None
-        } else {
-            Some(v)
-        }
+        key_from_js(&v)
     }
 }
 
@@ -980,16 +1494,16 @@ impl<Capabilities> KeyCursor<Capabilities> {
 
     pub fn continue_key<K>(
         self,
-        key: &K,
+        key: K,
     ) -> Result<
         impl Future<Output = Result<Option<KeyCursor<Capabilities>>, RequestCursorContinueKeyError>>,
         CursorContinueKeyError,
     >
     where
-        K: AsRef<JsValue>,
+        K: IntoKey,
     {
         self.inner
-            .continue_with_key(key.as_ref())
+            .continue_with_key(&key_to_js(&key.into_key()))
             .map(|ok| {
                 RequestFuture::from_request(self.inner.request())
                     .map_ok(|v| if v.is_null() { None } else { Some(self) })
@@ -1000,18 +1514,21 @@ impl<Capabilities> KeyCursor<Capabilities> {
 
     pub fn continue_primary_key<K, P>(
         self,
-        key: &K,
-        primary_key: &P,
+        key: K,
+        primary_key: P,
     ) -> Result<
         impl Future<Output = Result<Option<KeyCursor<Capabilities>>, RequestCursorContinueKeyError>>,
         CursorContinueKeyError,
     >
     where
-        K: AsRef<JsValue>,
-        P: AsRef<JsValue>,
+        K: IntoKey,
+        P: IntoKey,
     {
         self.inner
-            .continue_primary_key(key.as_ref(), primary_key.as_ref())
+            .continue_primary_key(
+                &key_to_js(&key.into_key()),
+                &key_to_js(&primary_key.into_key()),
+            )
             .map(|ok| {
                 RequestFuture::from_request(self.inner.request())
                     .map_ok(|v| if v.is_null() { None } else { Some(self) })
@@ -1052,6 +1569,14 @@ impl<Capabilities> ValueCursor<Capabilities> {
         }
     }
 
+    /// Decodes the value at the cursor's current position as a record of type `T`.
+    pub fn record<T>(&self) -> Result<Option<T>, DecodeRecordError>
+    where
+        T: IdbRecord,
+    {
+        self.value().map(decode_record).transpose()
+    }
+
     pub fn advance(
         self,
         count: u32,
@@ -1073,16 +1598,16 @@ impl<Capabilities> ValueCursor<Capabilities> {
 
     pub fn continue_key<K>(
         self,
-        key: &K,
+        key: K,
     ) -> Result<
         impl Future<Output = Result<Option<ValueCursor<Capabilities>>, RequestCursorContinueKeyError>>,
         CursorContinueKeyError,
     >
     where
-        K: AsRef<JsValue>,
+        K: IntoKey,
     {
         self.inner
-            .continue_with_key(key.as_ref())
+            .continue_with_key(&key_to_js(&key.into_key()))
             .map(|ok| {
                 RequestFuture::from_request(self.inner.request())
                     .map_ok(|v| if v.is_null() { None } else { Some(self) })
@@ -1093,18 +1618,21 @@ impl<Capabilities> ValueCursor<Capabilities> {
 
     pub fn continue_primary_key<K, P>(
         self,
-        key: &K,
-        primary_key: &P,
+        key: K,
+        primary_key: P,
     ) -> Result<
         impl Future<Output = Result<Option<ValueCursor<Capabilities>>, RequestCursorContinueKeyError>>,
         CursorContinueKeyError,
     >
     where
-        K: AsRef<JsValue>,
-        P: AsRef<JsValue>,
+        K: IntoKey,
+        P: IntoKey,
     {
         self.inner
-            .continue_primary_key(key.as_ref(), primary_key.as_ref())
+            .continue_primary_key(
+                &key_to_js(&key.into_key()),
+                &key_to_js(&primary_key.into_key()),
+            )
             .map(|ok| {
                 RequestFuture::from_request(self.inner.request())
                     .map_ok(|v| if v.is_null() { None } else { Some(self) })
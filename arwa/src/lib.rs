@@ -14,6 +14,7 @@ pub(crate) use self::impl_js_cast::*;
 pub use self::invalid_cast::*;
 pub use self::transferable::*;
 
+pub mod abort;
 pub mod collection;
 pub mod compression;
 pub mod connection;
@@ -38,11 +39,14 @@ pub mod media_type;
 pub mod message;
 pub mod navigator;
 pub mod performance;
+pub mod router;
+pub mod scheduler;
 pub mod scroll;
 pub mod security;
 pub mod storage;
 pub mod storage_manager;
 pub mod stream;
+pub mod structured_clone;
 pub mod subtle_crypto;
 pub mod timer;
 pub mod ui;
@@ -1,9 +1,11 @@
 use delegate::delegate;
+use wasm_bindgen::JsValue;
 
 use crate::event::impl_event_target_traits;
 use crate::message::{
     message_event_target_seal, message_sender_seal, MessageEventTarget, MessageSender,
 };
+use crate::structured_clone::new_data_clone_error;
 
 #[derive(Clone)]
 pub struct BroadcastChannel {
@@ -22,7 +24,23 @@ impl BroadcastChannel {
     }
 }
 
-impl message_sender_seal::Seal for BroadcastChannel {}
+impl message_sender_seal::Seal for BroadcastChannel {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        // Broadcast channels have no transfer list; objects can only be copied.
+        if transfer_list.length() > 0 {
+            let error = new_data_clone_error("broadcast channels cannot transfer objects");
+            let error: &web_sys::DomException = error.as_ref();
+
+            return Err(error.clone().into());
+        }
+
+        self.inner.post_message(message)
+    }
+}
 impl MessageSender for BroadcastChannel {}
 
 impl message_event_target_seal::Seal for BroadcastChannel {
@@ -5,6 +5,7 @@ use web_sys::ServiceWorker;
 
 use crate::event::impl_typed_event_traits;
 use crate::message::MessagePort;
+use crate::structured_clone::{deserialize, DeserializeError, FromStructuredClone};
 use crate::window::Window;
 
 pub enum MessageEventSource {
@@ -71,6 +72,17 @@ impl<T> MessageEvent<T> {
     pub fn data(&self) -> JsValue {
         self.inner.data()
     }
+
+    /// Attempts to deserialize the message data as a value of type `D`.
+    ///
+    /// See [FromStructuredClone] for details. Returns a [DeserializeError] that describes the path
+    /// to the offending value if the data does not match the representation expected by `D`.
+    pub fn data_as<D>(&self) -> Result<D, DeserializeError>
+    where
+        D: FromStructuredClone,
+    {
+        deserialize(self.inner.data())
+    }
 }
 
 impl<T> AsRef<web_sys::MessageEvent> for MessageEvent<T> {
@@ -1,3 +1,5 @@
+use wasm_bindgen::{JsCast, JsValue};
+
 use crate::event::impl_event_target_traits;
 use crate::message::{
     message_event_target_seal, message_sender_seal, MessageEventTarget, MessageSender,
@@ -8,10 +10,33 @@ pub struct MessagePort {
     pub(crate) inner: web_sys::MessagePort,
 }
 
-impl transferable_seal::Seal for MessagePort {}
+impl transferable_seal::Seal for MessagePort {
+    fn as_transferable(&self) -> &JsValue {
+        self.inner.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<web_sys::MessagePort>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        MessagePort {
+            inner: value.unchecked_into(),
+        }
+    }
+}
 impl Transferable for MessagePort {}
 
-impl message_sender_seal::Seal for MessagePort {}
+impl message_sender_seal::Seal for MessagePort {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        self.inner
+            .post_message_with_transferable(message, transfer_list)
+    }
+}
 impl MessageSender for MessagePort {}
 
 impl message_event_target_seal::Seal for MessagePort {
@@ -0,0 +1,7 @@
+pub use arwa_macro::route;
+
+mod route_pattern;
+pub use self::route_pattern::*;
+
+mod router;
+pub use self::router::*;
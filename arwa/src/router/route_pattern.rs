@@ -0,0 +1,155 @@
+use std::fmt;
+
+pub use arwa_parse::route_pattern::InvalidRoutePattern;
+#[doc(hidden)]
+pub use arwa_parse::route_pattern::Segment as RouteSegment;
+use arwa_parse::route_pattern::{match_path, RoutePattern as DynamicallyParsedRoutePattern};
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct StaticallyParsedRoutePattern {
+    #[doc(hidden)]
+    pub pattern: &'static str,
+    #[doc(hidden)]
+    pub segments: &'static [RouteSegment],
+}
+
+impl AsRef<str> for StaticallyParsedRoutePattern {
+    fn as_ref(&self) -> &str {
+        self.pattern
+    }
+}
+
+#[derive(Clone)]
+enum RoutePatternInternal {
+    Static(StaticallyParsedRoutePattern),
+    Dynamic(DynamicallyParsedRoutePattern),
+}
+
+/// A pattern that matches URL paths.
+///
+/// A pattern starts with a `/` and consists of `/`-separated segments. A segment is either static
+/// text that must match the path segment exactly, a named parameter (e.g. `:id`) that matches any
+/// single path segment, or a named wildcard (e.g. `*rest`) that matches the remainder of the path.
+/// A wildcard may only be used as the last segment, e.g. `/users/:id/posts/*rest`.
+///
+/// Patterns can be validated at compile time with the [route] macro.
+///
+/// [route]: crate::router::route
+#[derive(Clone)]
+pub struct RoutePattern {
+    internal: RoutePatternInternal,
+}
+
+impl RoutePattern {
+    pub fn parse(pattern: &str) -> Result<Self, InvalidRoutePattern> {
+        DynamicallyParsedRoutePattern::parse(pattern).map(|pattern| RoutePattern {
+            internal: RoutePatternInternal::Dynamic(pattern),
+        })
+    }
+
+    #[doc(hidden)]
+    pub const fn from_statically_parsed(pattern: StaticallyParsedRoutePattern) -> Self {
+        RoutePattern {
+            internal: RoutePatternInternal::Static(pattern),
+        }
+    }
+
+    /// Matches the `path` against this pattern.
+    ///
+    /// A single trailing `/` in the `path` is ignored, but a path that contains an empty segment
+    /// (e.g. `/users//1`) never matches. Returns the parameters captured from the path if it
+    /// matches, or `None` otherwise.
+    pub fn match_path(&self, path: &str) -> Option<RouteParams> {
+        let (pattern, segments) = match &self.internal {
+            RoutePatternInternal::Static(pattern) => (pattern.pattern, pattern.segments),
+            RoutePatternInternal::Dynamic(pattern) => (pattern.as_ref(), pattern.segments()),
+        };
+
+        match_path(pattern, segments, path).map(|captures| {
+            let params = captures
+                .into_iter()
+                .map(|capture| {
+                    let name = pattern[capture.name].to_string();
+                    let raw = &path[capture.value];
+
+                    // Leave the value as is if it is not a valid percent-encoded string.
+                    let value = js_sys::decode_uri_component(raw)
+                        .map(|value| value.into())
+                        .unwrap_or_else(|_| raw.to_string());
+
+                    (name, value)
+                })
+                .collect();
+
+            RouteParams { params }
+        })
+    }
+}
+
+impl AsRef<str> for RoutePattern {
+    fn as_ref(&self) -> &str {
+        match &self.internal {
+            RoutePatternInternal::Static(pattern) => pattern.as_ref(),
+            RoutePatternInternal::Dynamic(pattern) => pattern.as_ref(),
+        }
+    }
+}
+
+impl PartialEq for RoutePattern {
+    fn eq(&self, other: &Self) -> bool {
+        let self_as_str: &str = self.as_ref();
+        let other_as_str: &str = other.as_ref();
+
+        self_as_str == other_as_str
+    }
+}
+
+impl fmt::Display for RoutePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str: &str = self.as_ref();
+
+        fmt::Display::fmt(as_str, f)
+    }
+}
+
+impl fmt::Debug for RoutePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let as_str: &str = self.as_ref();
+
+        fmt::Debug::fmt(as_str, f)
+    }
+}
+
+/// The parameters captured from a path by a [RoutePattern].
+///
+/// Parameter values are percent-decoded.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RouteParams {
+    params: Vec<(String, String)>,
+}
+
+impl RouteParams {
+    /// Returns the value captured for the parameter with the given `name`, or `None` if the
+    /// pattern does not declare a parameter with that name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
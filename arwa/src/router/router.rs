@@ -0,0 +1,355 @@
+use std::cell::{Cell, RefCell};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures::Stream;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+use crate::html::HtmlAElement;
+use crate::router::{RouteParams, RoutePattern};
+use crate::url::{InvalidUrl, Origin, Url};
+use crate::window::Window;
+
+/// Determines which part of the URL a [Router] matches against its routes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RouterMode {
+    /// Match against the path of the URL; navigation is done with the session history.
+    History,
+    /// Match against the fragment of the URL, e.g. `/users/1` for `https://example.com/#/users/1`.
+    Hash,
+}
+
+/// A route that matched a path, along with the parameters it captured from the path.
+#[derive(Clone, Debug)]
+pub struct RouteMatch<R> {
+    route: R,
+    params: RouteParams,
+}
+
+impl<R> RouteMatch<R> {
+    pub fn route(&self) -> &R {
+        &self.route
+    }
+
+    pub fn params(&self) -> &RouteParams {
+        &self.params
+    }
+
+    pub fn into_route(self) -> R {
+        self.route
+    }
+}
+
+/// Matches the location of a [Window] against a list of routes.
+///
+/// Routes are tried in the order in which they were added; the first route with a pattern that
+/// matches the path is used. See [RouterMode] for the part of the URL that is used as the path.
+pub struct Router<R> {
+    mode: RouterMode,
+    routes: Vec<(RoutePattern, R)>,
+}
+
+impl<R> Router<R> {
+    pub fn new(mode: RouterMode) -> Self {
+        Router {
+            mode,
+            routes: Vec::new(),
+        }
+    }
+
+    pub fn mode(&self) -> RouterMode {
+        self.mode
+    }
+
+    /// Adds a route that is used when a path matches the `pattern`.
+    pub fn route(mut self, pattern: RoutePattern, route: R) -> Self {
+        self.routes.push((pattern, route));
+
+        self
+    }
+
+    /// Returns the first route that matches the `path`, or `None` if no route matches.
+    pub fn match_path(&self, path: &str) -> Option<RouteMatch<R>>
+    where
+        R: Clone,
+    {
+        self.routes.iter().find_map(|(pattern, route)| {
+            pattern.match_path(path).map(|params| RouteMatch {
+                route: route.clone(),
+                params,
+            })
+        })
+    }
+
+    /// Starts listening for navigation in the `window`, returning a stream of matched routes.
+    ///
+    /// The stream immediately yields the route for the current location, and then yields the route
+    /// for the new location every time the window navigates within the current document. An item
+    /// is `None` if no route matches the location.
+    ///
+    /// In [RouterMode::History] mode, clicks on `<a>` elements that link to a same-origin URL are
+    /// intercepted: rather than reloading the page, the URL is pushed onto the session history.
+    /// Clicks with a modifier key, clicks on links with a `target` other than `_self` and clicks on
+    /// links with a `download` attribute are not intercepted. In [RouterMode::Hash] mode links
+    /// should point to a fragment (e.g. `#/users/1`), which never reloads the page.
+    pub fn listen(self, window: &Window) -> Routes<R>
+    where
+        R: Clone + 'static,
+    {
+        let navigator = RouteNavigator {
+            window: window.clone(),
+            mode: self.mode,
+            signal: Rc::new(Signal {
+                // Yield the route for the current location on the first poll.
+                changed: Cell::new(true),
+                waker: RefCell::new(None),
+            }),
+        };
+
+        let web_sys_window: &web_sys::Window = window.as_ref();
+        let mut listeners = Vec::new();
+
+        let signal = navigator.signal.clone();
+
+        let event_type = match self.mode {
+            RouterMode::History => "popstate",
+            RouterMode::Hash => "hashchange",
+        };
+
+        listeners.push(Listener::new(
+            web_sys_window.as_ref(),
+            event_type,
+            move |_| signal.notify(),
+        ));
+
+        if self.mode == RouterMode::History {
+            let document = web_sys_window.document().unwrap_throw();
+            let navigator = navigator.clone();
+
+            listeners.push(Listener::new(document.as_ref(), "click", move |event| {
+                if let Some(url) = intercepted_url(&event, &navigator.window) {
+                    event.prevent_default();
+                    navigator.window.history().push_state((), &url);
+                    navigator.signal.notify();
+                }
+            }));
+        }
+
+        Routes {
+            router: self,
+            navigator,
+            _listeners: listeners,
+        }
+    }
+}
+
+/// Stream of matched routes, returned by [Router::listen].
+pub struct Routes<R> {
+    router: Router<R>,
+    navigator: RouteNavigator,
+    _listeners: Vec<Listener>,
+}
+
+impl<R> Routes<R> {
+    /// Returns a navigator that navigates within the current document and notifies this stream of
+    /// the navigation.
+    pub fn navigator(&self) -> RouteNavigator {
+        self.navigator.clone()
+    }
+}
+
+impl<R> Stream for Routes<R>
+where
+    R: Clone,
+{
+    type Item = Option<RouteMatch<R>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let signal = &self.navigator.signal;
+
+        // Note: if the location changes multiple times before the stream is polled, only the
+        // latest location is yielded.
+        if signal.changed.replace(false) {
+            let path = self.navigator.current_path();
+
+            Poll::Ready(Some(self.router.match_path(&path)))
+        } else {
+            *signal.waker.borrow_mut() = Some(cx.waker().clone());
+
+            Poll::Pending
+        }
+    }
+}
+
+/// Navigates within the current document, see [Routes::navigator].
+#[derive(Clone)]
+pub struct RouteNavigator {
+    window: Window,
+    mode: RouterMode,
+    signal: Rc<Signal>,
+}
+
+impl RouteNavigator {
+    /// Pushes a new entry for the `path` onto the session history.
+    ///
+    /// In [RouterMode::History] mode, the `path` is resolved against the current location. In
+    /// [RouterMode::Hash] mode, the `path` replaces the fragment of the current location.
+    pub fn push(&self, path: &str) -> Result<(), InvalidUrl> {
+        let url = self.resolve(path)?;
+
+        self.window.history().push_state((), &url);
+        self.signal.notify();
+
+        Ok(())
+    }
+
+    /// Replaces the current session history entry with an entry for the `path`.
+    ///
+    /// See [push](RouteNavigator::push) for details on how the `path` is resolved.
+    pub fn replace(&self, path: &str) -> Result<(), InvalidUrl> {
+        let url = self.resolve(path)?;
+
+        self.window.history().replace_state((), &url);
+        self.signal.notify();
+
+        Ok(())
+    }
+
+    fn resolve(&self, path: &str) -> Result<Url, InvalidUrl> {
+        let current = self.window.location().to_url();
+
+        match self.mode {
+            RouterMode::History => Url::parse_with_base(path, &current),
+            RouterMode::Hash => Url::parse_with_base(&format!("#{}", path), &current),
+        }
+    }
+
+    fn current_path(&self) -> String {
+        let url = self.window.location().to_url();
+
+        match self.mode {
+            RouterMode::History => url.path().to_string(),
+            RouterMode::Hash => {
+                let fragment = url.fragment().map(|f| &f[1..]).unwrap_or("");
+                let path = fragment.split('?').next().unwrap_or("");
+
+                if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path.to_string()
+                }
+            }
+        }
+    }
+}
+
+struct Signal {
+    changed: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl Signal {
+    fn notify(&self) {
+        self.changed.set(true);
+
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+// Note: we can't use the event streams here, as the click event must be canceled synchronously
+// from within the listener.
+struct Listener {
+    target: web_sys::EventTarget,
+    event_type: &'static str,
+    callback: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Listener {
+    fn new<F>(target: &web_sys::EventTarget, event_type: &'static str, f: F) -> Self
+    where
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        let callback = Closure::wrap(Box::new(f) as Box<dyn FnMut(web_sys::Event)>);
+
+        target
+            .add_event_listener_with_callback(event_type, callback.as_ref().unchecked_ref())
+            .unwrap_throw();
+
+        Listener {
+            target: target.clone(),
+            event_type,
+            callback,
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.target
+            .remove_event_listener_with_callback(
+                self.event_type,
+                self.callback.as_ref().unchecked_ref(),
+            )
+            .unwrap_throw();
+    }
+}
+
+fn intercepted_url(event: &web_sys::Event, window: &Window) -> Option<Url> {
+    let event: &web_sys::MouseEvent = event.dyn_ref()?;
+
+    if event.default_prevented()
+        || event.button() != 0
+        || event.meta_key()
+        || event.ctrl_key()
+        || event.shift_key()
+        || event.alt_key()
+    {
+        return None;
+    }
+
+    // Use the composed path so that links inside shadow trees are also intercepted.
+    let anchor = event
+        .composed_path()
+        .iter()
+        .find_map(|target| target.dyn_into::<HtmlAElement>().ok())?;
+
+    let target = anchor.target();
+
+    if !(target.is_empty() || target == "_self") {
+        return None;
+    }
+
+    let element: &web_sys::HtmlAnchorElement = anchor.as_ref();
+
+    if !element.has_attribute("href") || element.has_attribute("download") {
+        return None;
+    }
+
+    let url = anchor.href()?;
+    let current = window.location().to_url();
+
+    if !is_same_origin(&url, &current) {
+        return None;
+    }
+
+    // Let the browser handle navigation to a fragment within the current page.
+    if url.fragment().is_some() && url.path() == current.path() && url.query() == current.query() {
+        return None;
+    }
+
+    Some(url)
+}
+
+fn is_same_origin(a: &Url, b: &Url) -> bool {
+    // Note: an opaque origin is never same-origin with an origin obtained from another URL.
+    match (a.origin(), b.origin()) {
+        (Origin::Tuple(a_scheme, a_host, a_port), Origin::Tuple(b_scheme, b_host, b_port)) => {
+            a_scheme == b_scheme && a_host == b_host && a_port == b_port
+        }
+        _ => false,
+    }
+}
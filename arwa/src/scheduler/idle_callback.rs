@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+use crate::scheduler::macrotask::{
+    idle_global_scope, now, post_macrotask, IdleGlobalScope, RawIdleDeadline,
+};
+use crate::scheduler::post_task::TaskState;
+use crate::scheduler::TaskPriority;
+use crate::timer::Duration;
+
+// The longest idle period a browser will grant, used to emulate a deadline when
+// `requestIdleCallback` is not supported.
+const FALLBACK_IDLE_PERIOD: f64 = 50.0;
+
+enum IdleDeadlineInternal {
+    Native(RawIdleDeadline),
+    Fallback { deadline: f64 },
+}
+
+/// The deadline of an idle period, as resolved by [request_idle_callback].
+pub struct IdleDeadline {
+    inner: IdleDeadlineInternal,
+}
+
+impl IdleDeadline {
+    /// Returns the estimated number of milliseconds remaining in the current idle period.
+    ///
+    /// Returns `0.0` if the idle period has ended.
+    pub fn time_remaining(&self) -> f64 {
+        match &self.inner {
+            IdleDeadlineInternal::Native(deadline) => deadline.time_remaining(),
+            IdleDeadlineInternal::Fallback { deadline } => (deadline - now()).max(0.0),
+        }
+    }
+
+    /// Whether or not the callback was run because the timeout expired, rather than because the
+    /// browser became idle.
+    pub fn did_timeout(&self) -> bool {
+        match &self.inner {
+            IdleDeadlineInternal::Native(deadline) => deadline.did_timeout(),
+            IdleDeadlineInternal::Fallback { .. } => false,
+        }
+    }
+}
+
+/// Resolves with an [IdleDeadline] when the browser is idle.
+///
+/// Uses `requestIdleCallback` if it is available in the current global scope. If it is not, the
+/// future resolves in a new background-priority task (see [post_task]) with an emulated deadline
+/// of 50 milliseconds.
+///
+/// [post_task]: crate::scheduler::post_task
+pub fn request_idle_callback() -> RequestIdleCallback {
+    RequestIdleCallback {
+        timeout: Duration::Infinity,
+        initialized: false,
+        handle: None,
+        callback: None,
+        state: Rc::new(RefCell::new(TaskState::new())),
+    }
+}
+
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct RequestIdleCallback {
+    timeout: Duration,
+    initialized: bool,
+    handle: Option<(IdleGlobalScope, u32)>,
+    callback: Option<Closure<dyn FnMut(JsValue)>>,
+    state: Rc<RefCell<TaskState<IdleDeadline>>>,
+}
+
+impl RequestIdleCallback {
+    /// Resolves the future when the `timeout` expires, even if the browser has not become idle.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+}
+
+impl Future for RequestIdleCallback {
+    type Output = IdleDeadline;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let request = self.get_mut();
+
+        // Initialize if not initialized
+        if !request.initialized {
+            request.initialized = true;
+
+            let state = request.state.clone();
+
+            if let Some(global) = idle_global_scope() {
+                let callback = Closure::wrap(Box::new(move |deadline: JsValue| {
+                    state.borrow_mut().resolve(IdleDeadline {
+                        inner: IdleDeadlineInternal::Native(deadline.unchecked_into()),
+                    });
+                }) as Box<dyn FnMut(JsValue)>);
+
+                let options = Object::new();
+
+                if let Duration::Milliseconds(timeout) = request.timeout {
+                    Reflect::set(&options, &"timeout".into(), &timeout.into()).unwrap_throw();
+                }
+
+                let handle = global.request_idle_callback(&callback, &options);
+
+                request.handle = Some((global, handle));
+
+                // Hold on to the callback so that it wont drop prematurely.
+                request.callback = Some(callback);
+            } else {
+                post_macrotask(TaskPriority::Background, move || {
+                    let mut state = state.borrow_mut();
+
+                    if !state.cancelled {
+                        state.resolve(IdleDeadline {
+                            inner: IdleDeadlineInternal::Fallback {
+                                deadline: now() + FALLBACK_IDLE_PERIOD,
+                            },
+                        });
+                    }
+                });
+            }
+        }
+
+        let mut state = request.state.borrow_mut();
+
+        if let Some(deadline) = state.result.take() {
+            // The callback has run, there is nothing left to cancel.
+            request.handle = None;
+
+            Poll::Ready(deadline)
+        } else {
+            state.waker = Some(cx.waker().clone());
+
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for RequestIdleCallback {
+    fn drop(&mut self) {
+        self.state.borrow_mut().cancelled = true;
+
+        if let Some((global, handle)) = self.handle.take() {
+            global.cancel_idle_callback(handle);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use js_sys::{Object, Promise, Reflect};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::scheduler::TaskPriority;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object)]
+    pub type Scheduler;
+
+    #[wasm_bindgen(method, js_name = postTask)]
+    pub fn post_task(this: &Scheduler, callback: &JsValue, options: &Object) -> Promise;
+
+    #[wasm_bindgen(method, js_name = "yield")]
+    pub fn yield_(this: &Scheduler) -> Promise;
+
+    #[wasm_bindgen(extends = Object)]
+    pub type IdleGlobalScope;
+
+    #[wasm_bindgen(method, js_name = requestIdleCallback)]
+    pub fn request_idle_callback(
+        this: &IdleGlobalScope,
+        callback: &Closure<dyn FnMut(JsValue)>,
+        options: &Object,
+    ) -> u32;
+
+    #[wasm_bindgen(method, js_name = cancelIdleCallback)]
+    pub fn cancel_idle_callback(this: &IdleGlobalScope, handle: u32);
+
+    #[wasm_bindgen(extends = Object)]
+    pub type RawIdleDeadline;
+
+    #[wasm_bindgen(method, js_name = timeRemaining)]
+    pub fn time_remaining(this: &RawIdleDeadline) -> f64;
+
+    #[wasm_bindgen(method, getter, js_name = didTimeout)]
+    pub fn did_timeout(this: &RawIdleDeadline) -> bool;
+}
+
+fn has_global_property(global: &Object, name: &str) -> bool {
+    Reflect::has(global, &JsValue::from_str(name)).unwrap_or(false)
+}
+
+/// Returns the global `scheduler` of the Prioritized Task Scheduling API, if available in the
+/// current global scope.
+pub(super) fn global_scheduler() -> Option<Scheduler> {
+    let global = js_sys::global();
+
+    if !has_global_property(&global, "scheduler") {
+        return None;
+    }
+
+    let scheduler = Reflect::get(&global, &JsValue::from_str("scheduler")).ok()?;
+
+    if scheduler.is_object() {
+        Some(scheduler.unchecked_into())
+    } else {
+        None
+    }
+}
+
+/// Returns the current global scope if it supports `requestIdleCallback`.
+pub(super) fn idle_global_scope() -> Option<IdleGlobalScope> {
+    let global = js_sys::global();
+
+    if has_global_property(&global, "requestIdleCallback") {
+        Some(global.unchecked_into())
+    } else {
+        None
+    }
+}
+
+/// Returns the current time as reported by the `performance` object of the current global scope.
+pub(super) fn now() -> f64 {
+    Reflect::get(&js_sys::global(), &JsValue::from_str("performance"))
+        .unwrap_throw()
+        .unchecked_into::<web_sys::Performance>()
+        .now()
+}
+
+/// Runs the `callback` in a new task.
+///
+/// Uses `scheduler.postTask` with the given `priority` if the Prioritized Task Scheduling API is
+/// available; otherwise, falls back to posting a message on a `MessageChannel`, which ignores the
+/// `priority`.
+pub(super) fn post_macrotask<F>(priority: TaskPriority, callback: F)
+where
+    F: FnOnce() + 'static,
+{
+    if let Some(scheduler) = global_scheduler() {
+        let options = Object::new();
+
+        Reflect::set(
+            &options,
+            &JsValue::from_str("priority"),
+            &JsValue::from_str(priority.as_str()),
+        )
+        .unwrap_throw();
+
+        // The closure frees itself once it is invoked, the task will not be aborted so it will
+        // always be invoked eventually.
+        let callback = Closure::once_into_js(callback);
+
+        // The promise resolves with the result of the callback, which is always `undefined`.
+        let _ = scheduler.post_task(&callback, &options);
+    } else {
+        let channel = web_sys::MessageChannel::new().unwrap_throw();
+        let callback = Closure::once_into_js(move |_: JsValue| callback());
+
+        // The receiving port is kept alive by the browser while it has a message listener and an
+        // undelivered message.
+        channel
+            .port1()
+            .set_onmessage(Some(callback.unchecked_ref()));
+        channel
+            .port2()
+            .post_message(&JsValue::UNDEFINED)
+            .unwrap_throw();
+    }
+}
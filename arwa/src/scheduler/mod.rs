@@ -0,0 +1,10 @@
+mod idle_callback;
+pub use self::idle_callback::*;
+
+mod macrotask;
+
+mod post_task;
+pub use self::post_task::*;
+
+mod yield_now;
+pub use self::yield_now::*;
@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use crate::scheduler::macrotask::post_macrotask;
+
+/// The priority of a task posted with [post_task].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum TaskPriority {
+    /// For tasks that block the user from interacting with the page, such as rendering the result
+    /// of an input event.
+    UserBlocking,
+    /// For tasks that are visible to the user, but do not block user interaction.
+    #[default]
+    UserVisible,
+    /// For tasks that are not time-critical, such as logging or prefetching.
+    Background,
+}
+
+impl TaskPriority {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            TaskPriority::UserBlocking => "user-blocking",
+            TaskPriority::UserVisible => "user-visible",
+            TaskPriority::Background => "background",
+        }
+    }
+}
+
+pub(super) struct TaskState<T> {
+    pub(super) result: Option<T>,
+    pub(super) waker: Option<Waker>,
+    pub(super) cancelled: bool,
+}
+
+impl<T> TaskState<T> {
+    pub(super) fn new() -> Self {
+        TaskState {
+            result: None,
+            waker: None,
+            cancelled: false,
+        }
+    }
+
+    pub(super) fn resolve(&mut self, result: T) {
+        self.result = Some(result);
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Runs `f` in a new task with the given `priority` and resolves to its result.
+///
+/// Uses the Prioritized Task Scheduling API (`scheduler.postTask`) if it is available in the
+/// current global scope. If it is not, the task is posted as a macrotask with a `MessageChannel`
+/// instead, in which case the `priority` is ignored.
+///
+/// The task is posted when the future is first polled. If the future is dropped before the task
+/// runs, then `f` will not be called.
+pub fn post_task<F, T>(priority: TaskPriority, f: F) -> PostTask<T>
+where
+    F: FnOnce() -> T + 'static,
+    T: 'static,
+{
+    PostTask {
+        priority,
+        f: Some(Box::new(f)),
+        state: Rc::new(RefCell::new(TaskState::new())),
+    }
+}
+
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct PostTask<T> {
+    priority: TaskPriority,
+    f: Option<Box<dyn FnOnce() -> T>>,
+    state: Rc<RefCell<TaskState<T>>>,
+}
+
+impl<T> Future for PostTask<T>
+where
+    T: 'static,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let post_task = self.get_mut();
+
+        // Post the task if this is the first time we're polled.
+        if let Some(f) = post_task.f.take() {
+            let state = post_task.state.clone();
+
+            post_macrotask(post_task.priority, move || {
+                if state.borrow().cancelled {
+                    return;
+                }
+
+                let result = f();
+
+                state.borrow_mut().resolve(result);
+            });
+        }
+
+        let mut state = post_task.state.borrow_mut();
+
+        if let Some(result) = state.result.take() {
+            Poll::Ready(result)
+        } else {
+            state.waker = Some(cx.waker().clone());
+
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for PostTask<T> {
+    fn drop(&mut self) {
+        self.state.borrow_mut().cancelled = true;
+    }
+}
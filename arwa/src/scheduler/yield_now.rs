@@ -0,0 +1,56 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use js_sys::Reflect;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::scheduler::macrotask::global_scheduler;
+use crate::scheduler::{post_task, PostTask, TaskPriority};
+
+/// Yields to the event loop, allowing the browser to handle user input and render before the
+/// current computation continues.
+///
+/// Uses `scheduler.yield` if it is available in the current global scope, which continues with a
+/// higher priority than other pending tasks of the same priority. Otherwise, continues in a new
+/// task posted with [post_task].
+pub fn yield_now() -> YieldNow {
+    YieldNow {
+        state: YieldNowState::Initial,
+    }
+}
+
+enum YieldNowState {
+    Initial,
+    Native(JsFuture),
+    Fallback(PostTask<()>),
+}
+
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct YieldNow {
+    state: YieldNowState,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let yield_now = self.get_mut();
+
+        if let YieldNowState::Initial = yield_now.state {
+            yield_now.state = match global_scheduler() {
+                Some(scheduler) if Reflect::has(&scheduler, &"yield".into()).unwrap_or(false) => {
+                    YieldNowState::Native(scheduler.yield_().into())
+                }
+                _ => YieldNowState::Fallback(post_task(TaskPriority::UserVisible, || ())),
+            };
+        }
+
+        match &mut yield_now.state {
+            YieldNowState::Initial => unreachable!(),
+            // `scheduler.yield` only rejects if the task is aborted, which we never do.
+            YieldNowState::Native(future) => Pin::new(future).poll(cx).map(|_| ()),
+            YieldNowState::Fallback(future) => Pin::new(future).poll(cx),
+        }
+    }
+}
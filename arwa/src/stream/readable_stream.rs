@@ -20,7 +20,7 @@ use crate::finalization_registry::FinalizationRegistry;
 use crate::js_serialize::{js_deserialize, js_serialize};
 use crate::stream::writable_stream::writable_stream_seal::Seal;
 use crate::stream::{QueuingStrategy, QueuingStrategyIntoWebSys, TransformStream, WritableStream};
-use crate::{spawn_local, type_error_wrapper};
+use crate::{spawn_local, transferable_seal, type_error_wrapper, Transferable};
 
 thread_local! {
     static READABLE_STREAM_FINALIZATION_REGISTRY: FinalizationRegistry = {
@@ -415,6 +415,25 @@ impl<T, E, C> readable_stream_seal::Seal for CustomReadableStream<T, E, C> {
     }
 }
 
+impl<T, E, C> transferable_seal::Seal for CustomReadableStream<T, E, C> {
+    fn as_transferable(&self) -> &JsValue {
+        self.inner.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<web_sys::ReadableStream>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        CustomReadableStream {
+            inner: value.unchecked_into(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, E, C> Transferable for CustomReadableStream<T, E, C> {}
+
 impl<T, E, C> ReadableStream for CustomReadableStream<T, E, C>
 where
     T: JsCast,
@@ -579,6 +598,25 @@ impl<E, C> readable_stream_seal::Seal for CustomReadableByteStream<E, C> {
     }
 }
 
+impl<E, C> transferable_seal::Seal for CustomReadableByteStream<E, C> {
+    fn as_transferable(&self) -> &JsValue {
+        self.inner.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<web_sys::ReadableStream>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        CustomReadableByteStream {
+            inner: value.unchecked_into(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<E, C> Transferable for CustomReadableByteStream<E, C> {}
+
 impl<E, C> ReadableStream for CustomReadableByteStream<E, C>
 where
     E: JsCast,
@@ -14,7 +14,7 @@ use crate::stream::{
     readable_stream_seal, writable_stream_seal, QueuingStrategy, QueuingStrategyIntoWebSys,
     ReadableStream, WritableStream,
 };
-use crate::type_error_wrapper;
+use crate::{transferable_seal, type_error_wrapper, Transferable};
 
 pub trait TransformStream {
     type In: JsCast;
@@ -272,6 +272,25 @@ where
     type Reason = JsValue;
 }
 
+impl<In, Out, E> transferable_seal::Seal for CustomTransformStream<In, Out, E> {
+    fn as_transferable(&self) -> &JsValue {
+        self.inner.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<web_sys::TransformStream>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        CustomTransformStream {
+            inner: value.unchecked_into(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<In, Out, E> Transferable for CustomTransformStream<In, Out, E> {}
+
 impl<In, Out, E> TransformStream for CustomTransformStream<In, Out, E>
 where
     In: JsCast,
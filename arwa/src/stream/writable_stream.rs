@@ -17,7 +17,7 @@ use wasm_bindgen_futures::JsFuture;
 use crate::finalization_registry::FinalizationRegistry;
 use crate::js_serialize::{js_deserialize, js_serialize};
 use crate::stream::{QueuingStrategy, QueuingStrategyIntoWebSys};
-use crate::type_error_wrapper;
+use crate::{transferable_seal, type_error_wrapper, Transferable};
 
 thread_local! {
     static WRITABLE_STREAM_FINALIZATION_REGISTRY: FinalizationRegistry = {
@@ -302,6 +302,25 @@ where
     }
 }
 
+impl<T, E, C> transferable_seal::Seal for CustomWritableStream<T, E, C> {
+    fn as_transferable(&self) -> &JsValue {
+        self.inner.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<web_sys::WritableStream>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        CustomWritableStream {
+            inner: value.unchecked_into(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, E, C> Transferable for CustomWritableStream<T, E, C> {}
+
 impl<T, E, C> WritableStream for CustomWritableStream<T, E, C>
 where
     T: JsCast,
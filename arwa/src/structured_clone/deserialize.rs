@@ -0,0 +1,1078 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::file::{Blob, File};
+use crate::image_bitmap::ImageBitmap;
+use crate::{TransferBox, Transferable};
+
+/// A type that can be reconstructed from a structurally cloned value.
+///
+/// This is the counterpart to [StructuredClone](crate::structured_clone::StructuredClone): any
+/// value serialized by a type's `StructuredClone` implementation should be accepted by that type's
+/// `FromStructuredClone` implementation. Implementations for structs and enums can be derived with
+/// `#[derive(FromStructuredClone)]`, which uses the same representation as the `StructuredClone`
+/// derive.
+pub trait FromStructuredClone: Sized {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError>;
+}
+
+/// A segment in the path to a value that could not be deserialized.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PathSegment {
+    /// A property on an object.
+    Property(String),
+    /// An element in an array or set.
+    Index(usize),
+    /// The key of an entry in a map.
+    MapKey(usize),
+    /// The value of an entry in a map.
+    MapValue(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Property(name) => write!(f, ".{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+            PathSegment::MapKey(index) => write!(f, "[{}].key", index),
+            PathSegment::MapValue(index) => write!(f, "[{}].value", index),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DeserializeErrorKind {
+    InvalidType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    InvalidLength {
+        expected: usize,
+        found: usize,
+    },
+    UnknownVariant(String),
+    Custom(String),
+}
+
+#[derive(Clone)]
+pub struct DeserializeError {
+    path: Vec<PathSegment>,
+    kind: DeserializeErrorKind,
+}
+
+impl DeserializeError {
+    /// The path to the value that could not be deserialized, relative to the root value.
+    ///
+    /// An empty path indicates the root value.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    pub fn kind(&self) -> &DeserializeErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            DeserializeErrorKind::InvalidType { expected, found } => {
+                write!(f, "invalid type: expected {}, found {}", expected, found)?
+            }
+            DeserializeErrorKind::InvalidLength { expected, found } => write!(
+                f,
+                "invalid length: expected {} elements, found {}",
+                expected, found
+            )?,
+            DeserializeErrorKind::UnknownVariant(variant) => {
+                write!(f, "unknown variant `{}`", variant)?
+            }
+            DeserializeErrorKind::Custom(message) => f.write_str(message)?,
+        }
+
+        if !self.path.is_empty() {
+            f.write_str(" at `")?;
+
+            for segment in &self.path {
+                fmt::Display::fmt(segment, f)?;
+            }
+
+            f.write_str("`")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for DeserializeError {}
+
+impl From<DeserializeError> for JsValue {
+    fn from(value: DeserializeError) -> Self {
+        wasm_bindgen::JsError::from(value).into()
+    }
+}
+
+// Paths are stored as a linked list of segments that is only materialized when an error occurs,
+// so that deserializing a nested value does not require copying the path for every element.
+struct PathNode {
+    parent: Option<Rc<PathNode>>,
+    segment: PathSegment,
+}
+
+fn materialize_path(node: &Option<Rc<PathNode>>) -> Vec<PathSegment> {
+    let mut path = Vec::new();
+    let mut current = node.as_ref();
+
+    while let Some(node) = current {
+        path.push(node.segment.clone());
+        current = node.parent.as_ref();
+    }
+
+    path.reverse();
+
+    path
+}
+
+fn describe(value: &JsValue) -> &'static str {
+    if value.is_undefined() {
+        "undefined"
+    } else if value.is_null() {
+        "null"
+    } else if value.as_bool().is_some() {
+        "boolean"
+    } else if value.as_f64().is_some() {
+        "number"
+    } else if value.is_bigint() {
+        "bigint"
+    } else if value.is_string() {
+        "string"
+    } else if js_sys::Array::is_array(value) {
+        "array"
+    } else if value.is_instance_of::<js_sys::Map>() {
+        "map"
+    } else if value.is_instance_of::<js_sys::Set>() {
+        "set"
+    } else if value.is_instance_of::<js_sys::Date>() {
+        "date"
+    } else if value.is_instance_of::<js_sys::ArrayBuffer>() {
+        "array buffer"
+    } else if value.is_instance_of::<web_sys::Blob>() {
+        "blob"
+    } else if value.is_instance_of::<web_sys::ImageBitmap>() {
+        "image bitmap"
+    } else {
+        "object"
+    }
+}
+
+pub struct Deserializer {
+    value: JsValue,
+    path: Option<Rc<PathNode>>,
+}
+
+impl Deserializer {
+    pub(crate) fn new(value: JsValue) -> Self {
+        Deserializer { value, path: None }
+    }
+
+    fn nested(&self, value: JsValue, segment: PathSegment) -> Self {
+        Deserializer {
+            value,
+            path: Some(Rc::new(PathNode {
+                parent: self.path.clone(),
+                segment,
+            })),
+        }
+    }
+
+    /// Creates an error with a custom `message` for the value at this deserializer's path.
+    pub fn custom_error<M>(&self, message: M) -> DeserializeError
+    where
+        M: fmt::Display,
+    {
+        self.error(DeserializeErrorKind::Custom(message.to_string()))
+    }
+
+    pub fn invalid_type_error(&self, expected: &'static str) -> DeserializeError {
+        self.error(DeserializeErrorKind::InvalidType {
+            expected,
+            found: describe(&self.value),
+        })
+    }
+
+    pub fn invalid_length_error(&self, expected: usize, found: usize) -> DeserializeError {
+        self.error(DeserializeErrorKind::InvalidLength { expected, found })
+    }
+
+    pub fn unknown_variant_error(&self, variant: &str) -> DeserializeError {
+        self.error(DeserializeErrorKind::UnknownVariant(variant.to_string()))
+    }
+
+    fn error(&self, kind: DeserializeErrorKind) -> DeserializeError {
+        DeserializeError {
+            path: materialize_path(&self.path),
+            kind,
+        }
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        self.value.is_undefined()
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.value.is_null()
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.value.is_string()
+    }
+
+    pub fn deserialize_undefined(self) -> Result<(), DeserializeError> {
+        if self.value.is_undefined() {
+            Ok(())
+        } else {
+            Err(self.invalid_type_error("undefined"))
+        }
+    }
+
+    pub fn deserialize_null(self) -> Result<(), DeserializeError> {
+        if self.value.is_null() {
+            Ok(())
+        } else {
+            Err(self.invalid_type_error("null"))
+        }
+    }
+
+    pub fn deserialize_bool(self) -> Result<bool, DeserializeError> {
+        self.value
+            .as_bool()
+            .ok_or_else(|| self.invalid_type_error("boolean"))
+    }
+
+    pub fn deserialize_f64(self) -> Result<f64, DeserializeError> {
+        self.value
+            .as_f64()
+            .ok_or_else(|| self.invalid_type_error("number"))
+    }
+
+    /// Deserializes a `BigInt` as an `i64`.
+    pub fn deserialize_i64(self) -> Result<i64, DeserializeError> {
+        self.deserialize_big_int("i64")
+    }
+
+    /// Deserializes a `BigInt` as a `u64`.
+    pub fn deserialize_u64(self) -> Result<u64, DeserializeError> {
+        self.deserialize_big_int("u64")
+    }
+
+    /// Deserializes a `BigInt` as an `i128`.
+    pub fn deserialize_i128(self) -> Result<i128, DeserializeError> {
+        self.deserialize_big_int("i128")
+    }
+
+    /// Deserializes a `BigInt` as a `u128`.
+    pub fn deserialize_u128(self) -> Result<u128, DeserializeError> {
+        self.deserialize_big_int("u128")
+    }
+
+    fn deserialize_big_int<T>(self, expected: &'static str) -> Result<T, DeserializeError>
+    where
+        T: TryFrom<JsValue>,
+    {
+        if !self.value.is_bigint() {
+            return Err(self.invalid_type_error("bigint"));
+        }
+
+        T::try_from(self.value.clone()).map_err(|_| {
+            self.custom_error(format_args!("bigint out of range for type `{}`", expected))
+        })
+    }
+
+    pub fn deserialize_string(self) -> Result<String, DeserializeError> {
+        self.value
+            .as_string()
+            .ok_or_else(|| self.invalid_type_error("string"))
+    }
+
+    /// Deserializes a `Date` as its time value in milliseconds since the UNIX epoch.
+    pub fn deserialize_date(self) -> Result<f64, DeserializeError> {
+        self.value
+            .dyn_ref::<js_sys::Date>()
+            .map(|date| date.get_time())
+            .ok_or_else(|| self.invalid_type_error("date"))
+    }
+
+    /// Deserializes an `ArrayBuffer` as a copy of its bytes.
+    pub fn deserialize_array_buffer(self) -> Result<Vec<u8>, DeserializeError> {
+        self.value
+            .dyn_ref::<js_sys::ArrayBuffer>()
+            .map(|buffer| js_sys::Uint8Array::new(buffer).to_vec())
+            .ok_or_else(|| self.invalid_type_error("array buffer"))
+    }
+
+    pub fn deserialize_blob(self) -> Result<Blob, DeserializeError> {
+        self.deserialize_js_value::<web_sys::Blob>("blob")
+            .map(|blob| blob.into())
+    }
+
+    pub fn deserialize_image_bitmap(self) -> Result<ImageBitmap, DeserializeError> {
+        self.deserialize_js_value::<web_sys::ImageBitmap>("image bitmap")
+            .map(|image_bitmap| image_bitmap.into())
+    }
+
+    pub fn deserialize_array(self) -> Result<ArrayDeserializer, DeserializeError> {
+        if js_sys::Array::is_array(&self.value) {
+            Ok(ArrayDeserializer {
+                array: self.value.clone().unchecked_into(),
+                next: 0,
+                deserializer: self,
+            })
+        } else {
+            Err(self.invalid_type_error("array"))
+        }
+    }
+
+    /// Deserializes an array that must have exactly `len` elements.
+    pub fn deserialize_tuple(self, len: usize) -> Result<ArrayDeserializer, DeserializeError> {
+        let array = self.deserialize_array()?;
+        let found = array.len();
+
+        if found == len {
+            Ok(array)
+        } else {
+            Err(array.deserializer.invalid_length_error(len, found))
+        }
+    }
+
+    /// Deserializes an enum from its "externally tagged" representation: either a string that
+    /// holds the name of a unit variant, or an object with a single property, where the property
+    /// name is the name of the variant and the property value holds the variant's data.
+    pub fn deserialize_enum(self) -> Result<EnumDeserializer, DeserializeError> {
+        if let Some(variant) = self.value.as_string() {
+            return Ok(EnumDeserializer {
+                variant,
+                data: None,
+                deserializer: self,
+            });
+        }
+
+        if describe(&self.value) == "object" {
+            let keys = js_sys::Object::keys(self.value.unchecked_ref::<js_sys::Object>());
+
+            if keys.length() == 1 {
+                let key = keys.get(0);
+                let variant = key.as_string().unwrap_or_default();
+
+                // Getting a property from a plain object cannot fail.
+                let value = js_sys::Reflect::get(&self.value, &key).unwrap_or(JsValue::undefined());
+                let data = self.nested(value, PathSegment::Property(variant.clone()));
+
+                return Ok(EnumDeserializer {
+                    variant,
+                    data: Some(data),
+                    deserializer: self,
+                });
+            }
+        }
+
+        Err(self.invalid_type_error("string or object with a single property"))
+    }
+
+    pub fn deserialize_object(self) -> Result<ObjectDeserializer, DeserializeError> {
+        // Note: only plain objects are accepted; arrays, maps, sets and other object types that
+        // can be structurally cloned each have their own representation in the data model.
+        if describe(&self.value) == "object" {
+            Ok(ObjectDeserializer { deserializer: self })
+        } else {
+            Err(self.invalid_type_error("object"))
+        }
+    }
+
+    pub fn deserialize_map(self) -> Result<MapDeserializer, DeserializeError> {
+        if self.value.is_instance_of::<js_sys::Map>() {
+            let entries = js_sys::Array::from(&self.value);
+
+            Ok(MapDeserializer {
+                entries,
+                next: 0,
+                deserializer: self,
+            })
+        } else {
+            Err(self.invalid_type_error("map"))
+        }
+    }
+
+    pub fn deserialize_set(self) -> Result<SetDeserializer, DeserializeError> {
+        if self.value.is_instance_of::<js_sys::Set>() {
+            let elements = js_sys::Array::from(&self.value);
+
+            Ok(SetDeserializer {
+                elements,
+                next: 0,
+                deserializer: self,
+            })
+        } else {
+            Err(self.invalid_type_error("set"))
+        }
+    }
+
+    fn deserialize_js_value<T>(self, expected: &'static str) -> Result<T, DeserializeError>
+    where
+        T: JsCast,
+    {
+        if self.value.is_instance_of::<T>() {
+            Ok(self.value.unchecked_into())
+        } else {
+            Err(self.invalid_type_error(expected))
+        }
+    }
+}
+
+pub struct ArrayDeserializer {
+    deserializer: Deserializer,
+    array: js_sys::Array,
+    next: u32,
+}
+
+impl ArrayDeserializer {
+    pub fn len(&self) -> usize {
+        self.array.length() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.array.length() == 0
+    }
+
+    /// Deserializes the next element in the array, or returns `None` if all elements have been
+    /// deserialized.
+    pub fn deserialize_element<T>(&mut self) -> Result<Option<T>, DeserializeError>
+    where
+        T: FromStructuredClone,
+    {
+        if self.next >= self.array.length() {
+            return Ok(None);
+        }
+
+        let index = self.next;
+
+        self.next += 1;
+
+        let element = self
+            .deserializer
+            .nested(self.array.get(index), PathSegment::Index(index as usize));
+
+        T::deserialize(element).map(Some)
+    }
+
+    /// Deserializes the next element in the array with the given callback `f`, or returns `None`
+    /// if all elements have been deserialized.
+    pub fn deserialize_element_with<T, F>(&mut self, f: F) -> Result<Option<T>, DeserializeError>
+    where
+        F: FnOnce(Deserializer) -> Result<T, DeserializeError>,
+    {
+        if self.next >= self.array.length() {
+            return Ok(None);
+        }
+
+        let index = self.next;
+
+        self.next += 1;
+
+        let element = self
+            .deserializer
+            .nested(self.array.get(index), PathSegment::Index(index as usize));
+
+        f(element).map(Some)
+    }
+
+    /// Finishes deserializing the array, returns an error if the array has an unexpected length.
+    pub fn end(self) -> Result<(), DeserializeError> {
+        let len = self.array.length();
+
+        if self.next == len {
+            Ok(())
+        } else {
+            Err(self
+                .deserializer
+                .invalid_length_error(self.next as usize, len as usize))
+        }
+    }
+}
+
+pub struct EnumDeserializer {
+    deserializer: Deserializer,
+    variant: String,
+    data: Option<Deserializer>,
+}
+
+impl EnumDeserializer {
+    /// The name of the variant.
+    pub fn variant(&self) -> &str {
+        &self.variant
+    }
+
+    /// Finishes deserializing a unit variant, returns an error if the variant holds data.
+    pub fn deserialize_unit(self) -> Result<(), DeserializeError> {
+        if self.data.is_some() {
+            Err(self.deserializer.invalid_type_error("string"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Deserializes the data held by a non-unit variant with the given callback `f`, returns an
+    /// error if the variant does not hold data.
+    pub fn deserialize_data_with<T, F>(self, f: F) -> Result<T, DeserializeError>
+    where
+        F: FnOnce(Deserializer) -> Result<T, DeserializeError>,
+    {
+        match self.data {
+            Some(data) => f(data),
+            None => Err(self.deserializer.invalid_type_error("object")),
+        }
+    }
+
+    pub fn unknown_variant_error(&self) -> DeserializeError {
+        self.deserializer.unknown_variant_error(&self.variant)
+    }
+}
+
+pub struct ObjectDeserializer {
+    deserializer: Deserializer,
+}
+
+impl ObjectDeserializer {
+    /// Whether the object has an own property with the given `key`; properties inherited through
+    /// the object's prototype chain are not considered.
+    pub fn has_property(&self, key: &str) -> bool {
+        js_sys::Object::has_own(
+            self.deserializer.value.unchecked_ref::<js_sys::Object>(),
+            &JsValue::from_str(key),
+        )
+    }
+
+    /// Returns the names of the object's own enumerable properties.
+    pub fn keys(&self) -> Vec<String> {
+        js_sys::Object::keys(self.deserializer.value.unchecked_ref::<js_sys::Object>())
+            .iter()
+            .filter_map(|key| key.as_string())
+            .collect()
+    }
+
+    /// Deserializes the property with the given `key`.
+    ///
+    /// If the object does not have an own property with the given `key`, then the property value is
+    /// deserialized from `undefined`.
+    pub fn deserialize_property<T>(&self, key: &str) -> Result<T, DeserializeError>
+    where
+        T: FromStructuredClone,
+    {
+        self.deserialize_property_with(key, T::deserialize)
+    }
+
+    /// Deserializes the property with the given `key` with the given callback `f`.
+    pub fn deserialize_property_with<T, F>(&self, key: &str, f: F) -> Result<T, DeserializeError>
+    where
+        F: FnOnce(Deserializer) -> Result<T, DeserializeError>,
+    {
+        // Only own properties are considered, properties inherited through the prototype chain
+        // (e.g. `toString`) deserialize from `undefined`. Getting a property from a plain object
+        // cannot fail.
+        let value = if self.has_property(key) {
+            js_sys::Reflect::get(&self.deserializer.value, &JsValue::from_str(key))
+                .unwrap_or(JsValue::undefined())
+        } else {
+            JsValue::undefined()
+        };
+
+        f(self
+            .deserializer
+            .nested(value, PathSegment::Property(key.to_string())))
+    }
+
+    pub fn end(self) -> Result<(), DeserializeError> {
+        Ok(())
+    }
+}
+
+pub struct MapDeserializer {
+    deserializer: Deserializer,
+    entries: js_sys::Array,
+    next: u32,
+}
+
+impl MapDeserializer {
+    pub fn len(&self) -> usize {
+        self.entries.length() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.length() == 0
+    }
+
+    /// Deserializes the next entry in the map, or returns `None` if all entries have been
+    /// deserialized.
+    pub fn deserialize_entry<K, V>(&mut self) -> Result<Option<(K, V)>, DeserializeError>
+    where
+        K: FromStructuredClone,
+        V: FromStructuredClone,
+    {
+        if self.next >= self.entries.length() {
+            return Ok(None);
+        }
+
+        let index = self.next;
+
+        self.next += 1;
+
+        let entry: js_sys::Array = self.entries.get(index).unchecked_into();
+        let key = self
+            .deserializer
+            .nested(entry.get(0), PathSegment::MapKey(index as usize));
+        let value = self
+            .deserializer
+            .nested(entry.get(1), PathSegment::MapValue(index as usize));
+
+        Ok(Some((K::deserialize(key)?, V::deserialize(value)?)))
+    }
+
+    pub fn end(self) -> Result<(), DeserializeError> {
+        Ok(())
+    }
+}
+
+pub struct SetDeserializer {
+    deserializer: Deserializer,
+    elements: js_sys::Array,
+    next: u32,
+}
+
+impl SetDeserializer {
+    pub fn len(&self) -> usize {
+        self.elements.length() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.length() == 0
+    }
+
+    /// Deserializes the next element in the set, or returns `None` if all elements have been
+    /// deserialized.
+    pub fn deserialize_element<T>(&mut self) -> Result<Option<T>, DeserializeError>
+    where
+        T: FromStructuredClone,
+    {
+        if self.next >= self.elements.length() {
+            return Ok(None);
+        }
+
+        let index = self.next;
+
+        self.next += 1;
+
+        let element = self
+            .deserializer
+            .nested(self.elements.get(index), PathSegment::Index(index as usize));
+
+        T::deserialize(element).map(Some)
+    }
+
+    pub fn end(self) -> Result<(), DeserializeError> {
+        Ok(())
+    }
+}
+
+pub(crate) fn deserialize<T>(value: JsValue) -> Result<T, DeserializeError>
+where
+    T: FromStructuredClone,
+{
+    T::deserialize(Deserializer::new(value))
+}
+
+impl FromStructuredClone for () {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_undefined()
+    }
+}
+
+impl FromStructuredClone for bool {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_bool()
+    }
+}
+
+macro_rules! impl_from_structured_clone_integer {
+    ($($tpe:ident),*) => {
+        $(
+            impl FromStructuredClone for $tpe {
+                fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                    let value = deserializer.value.as_f64();
+
+                    match value {
+                        Some(value)
+                            if value.fract() == 0.0
+                                && value >= $tpe::MIN as f64
+                                && value <= $tpe::MAX as f64 =>
+                        {
+                            Ok(value as $tpe)
+                        }
+                        Some(_) => Err(deserializer.custom_error(concat!(
+                            "number is not a valid `",
+                            stringify!($tpe),
+                            "`"
+                        ))),
+                        None => Err(deserializer.invalid_type_error("number")),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_structured_clone_integer!(i8, i16, i32, isize, u8, u16, u32, usize);
+
+impl FromStructuredClone for f32 {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_f64().map(|value| value as f32)
+    }
+}
+
+impl FromStructuredClone for f64 {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_f64()
+    }
+}
+
+macro_rules! impl_from_structured_clone_big_int {
+    ($($tpe:ident => $method:ident),*) => {
+        $(
+            impl FromStructuredClone for $tpe {
+                fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                    deserializer.$method()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_structured_clone_big_int!(
+    i64 => deserialize_i64,
+    u64 => deserialize_u64,
+    i128 => deserialize_i128,
+    u128 => deserialize_u128
+);
+
+impl FromStructuredClone for char {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let string = deserializer
+            .value
+            .as_string()
+            .ok_or_else(|| deserializer.invalid_type_error("string"))?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(deserializer.custom_error("expected a string with a single character")),
+        }
+    }
+}
+
+impl FromStructuredClone for String {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_string()
+    }
+}
+
+impl FromStructuredClone for SystemTime {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let time_value = deserializer
+            .value
+            .dyn_ref::<js_sys::Date>()
+            .map(|date| date.get_time())
+            .ok_or_else(|| deserializer.invalid_type_error("date"))?;
+
+        // An invalid `Date` has a `NaN` time value.
+        if !time_value.is_finite() {
+            return Err(deserializer.custom_error("expected a valid date"));
+        }
+
+        let time = if time_value >= 0.0 {
+            Duration::try_from_secs_f64(time_value / 1000.0)
+                .ok()
+                .and_then(|duration| UNIX_EPOCH.checked_add(duration))
+        } else {
+            Duration::try_from_secs_f64(-time_value / 1000.0)
+                .ok()
+                .and_then(|duration| UNIX_EPOCH.checked_sub(duration))
+        };
+
+        time.ok_or_else(|| deserializer.custom_error("date is out of range for `SystemTime`"))
+    }
+}
+
+/// Both `null` and `undefined` deserialize as `None`, so that a missing object property
+/// deserializes as `None`. As a consequence, `Some` of a value that serializes as `null` or
+/// `undefined` (e.g. `Some(())`) does not round-trip: it deserializes as `None`.
+impl<T> FromStructuredClone for Option<T>
+where
+    T: FromStructuredClone,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        if deserializer.is_null() || deserializer.is_undefined() {
+            Ok(None)
+        } else {
+            T::deserialize(deserializer).map(Some)
+        }
+    }
+}
+
+macro_rules! impl_from_structured_clone_wrapper {
+    ($($tpe:ident),*) => {
+        $(
+            impl<T> FromStructuredClone for $tpe<T>
+            where
+                T: FromStructuredClone,
+            {
+                fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                    T::deserialize(deserializer).map($tpe::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_structured_clone_wrapper!(Box, Rc, Arc);
+
+impl<T, const N: usize> FromStructuredClone for [T; N]
+where
+    T: FromStructuredClone,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut array = deserializer.deserialize_tuple(N)?;
+        let mut elements = Vec::with_capacity(N);
+
+        while let Some(element) = array.deserialize_element()? {
+            elements.push(element);
+        }
+
+        // We've verified the length, so this cannot fail.
+        Ok(elements.try_into().ok().unwrap())
+    }
+}
+
+macro_rules! impl_from_structured_clone_seq {
+    ($($tpe:ident),*) => {
+        $(
+            impl<T> FromStructuredClone for $tpe<T>
+            where
+                T: FromStructuredClone,
+            {
+                fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                    let mut array = deserializer.deserialize_array()?;
+                    let mut result = $tpe::new();
+
+                    while let Some(element) = array.deserialize_element()? {
+                        result.extend(Some(element));
+                    }
+
+                    Ok(result)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_structured_clone_seq!(Vec, VecDeque, LinkedList);
+
+impl<T, S> FromStructuredClone for HashSet<T, S>
+where
+    T: FromStructuredClone + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut set = deserializer.deserialize_set()?;
+        let mut result = HashSet::with_capacity_and_hasher(set.len(), S::default());
+
+        while let Some(element) = set.deserialize_element()? {
+            result.insert(element);
+        }
+
+        Ok(result)
+    }
+}
+
+impl<T> FromStructuredClone for BTreeSet<T>
+where
+    T: FromStructuredClone + Ord,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut set = deserializer.deserialize_set()?;
+        let mut result = BTreeSet::new();
+
+        while let Some(element) = set.deserialize_element()? {
+            result.insert(element);
+        }
+
+        Ok(result)
+    }
+}
+
+impl<K, V, S> FromStructuredClone for HashMap<K, V, S>
+where
+    K: FromStructuredClone + Eq + Hash,
+    V: FromStructuredClone,
+    S: BuildHasher + Default,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut map = deserializer.deserialize_map()?;
+        let mut result = HashMap::with_capacity_and_hasher(map.len(), S::default());
+
+        while let Some((key, value)) = map.deserialize_entry()? {
+            result.insert(key, value);
+        }
+
+        Ok(result)
+    }
+}
+
+impl<K, V> FromStructuredClone for BTreeMap<K, V>
+where
+    K: FromStructuredClone + Ord,
+    V: FromStructuredClone,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut map = deserializer.deserialize_map()?;
+        let mut result = BTreeMap::new();
+
+        while let Some((key, value)) = map.deserialize_entry()? {
+            result.insert(key, value);
+        }
+
+        Ok(result)
+    }
+}
+
+macro_rules! impl_from_structured_clone_tuple {
+    ($len:literal => $($name:ident)+) => {
+        impl<$($name),+> FromStructuredClone for ($($name,)+)
+        where
+            $($name: FromStructuredClone),+
+        {
+            fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                let mut array = deserializer.deserialize_tuple($len)?;
+
+                // We've verified the length, so unwrapping the elements cannot fail.
+                Ok(($(array.deserialize_element::<$name>()?.unwrap(),)+))
+            }
+        }
+    };
+}
+
+impl_from_structured_clone_tuple!(1 => T0);
+impl_from_structured_clone_tuple!(2 => T0 T1);
+impl_from_structured_clone_tuple!(3 => T0 T1 T2);
+impl_from_structured_clone_tuple!(4 => T0 T1 T2 T3);
+impl_from_structured_clone_tuple!(5 => T0 T1 T2 T3 T4);
+impl_from_structured_clone_tuple!(6 => T0 T1 T2 T3 T4 T5);
+impl_from_structured_clone_tuple!(7 => T0 T1 T2 T3 T4 T5 T6);
+impl_from_structured_clone_tuple!(8 => T0 T1 T2 T3 T4 T5 T6 T7);
+impl_from_structured_clone_tuple!(9 => T0 T1 T2 T3 T4 T5 T6 T7 T8);
+impl_from_structured_clone_tuple!(10 => T0 T1 T2 T3 T4 T5 T6 T7 T8 T9);
+impl_from_structured_clone_tuple!(11 => T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10);
+impl_from_structured_clone_tuple!(12 => T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11);
+
+impl<T> FromStructuredClone for TransferBox<T>
+where
+    T: Transferable,
+{
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        if T::is_transferable_instance(&deserializer.value) {
+            Ok(TransferBox::new(T::from_transferred(deserializer.value)))
+        } else {
+            Err(deserializer.invalid_type_error("transferable object"))
+        }
+    }
+}
+
+impl FromStructuredClone for Blob {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_blob()
+    }
+}
+
+impl FromStructuredClone for File {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_js_value::<web_sys::File>("file")
+            .map(|file| file.into())
+    }
+}
+
+impl FromStructuredClone for ImageBitmap {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_image_bitmap()
+    }
+}
+
+macro_rules! impl_from_structured_clone_js_sys {
+    ($($tpe:ident => $expected:literal),*) => {
+        $(
+            impl FromStructuredClone for js_sys::$tpe {
+                fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                    deserializer.deserialize_js_value::<js_sys::$tpe>($expected)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_structured_clone_js_sys!(
+    ArrayBuffer => "array buffer",
+    DataView => "data view",
+    Date => "date",
+    Float32Array => "Float32Array",
+    Float64Array => "Float64Array",
+    Int8Array => "Int8Array",
+    Int16Array => "Int16Array",
+    Int32Array => "Int32Array",
+    RegExp => "regular expression",
+    Uint8Array => "Uint8Array",
+    Uint8ClampedArray => "Uint8ClampedArray",
+    Uint16Array => "Uint16Array",
+    Uint32Array => "Uint32Array",
+    BigInt64Array => "BigInt64Array",
+    BigUint64Array => "BigUint64Array"
+);
+
+impl FromStructuredClone for js_sys::BigInt {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        if deserializer.value.is_bigint() {
+            Ok(deserializer.value.unchecked_into())
+        } else {
+            Err(deserializer.invalid_type_error("bigint"))
+        }
+    }
+}
+
+impl FromStructuredClone for js_sys::JsString {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        if deserializer.value.is_string() {
+            Ok(deserializer.value.unchecked_into())
+        } else {
+            Err(deserializer.invalid_type_error("string"))
+        }
+    }
+}
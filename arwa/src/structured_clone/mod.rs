@@ -0,0 +1,7 @@
+pub use arwa_macro::{FromStructuredClone, StructuredClone};
+
+mod deserialize;
+pub use self::deserialize::*;
+
+mod serialize;
+pub use self::serialize::*;
@@ -0,0 +1,705 @@
+use std::borrow::Cow;
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+use crate::file::{Blob, File};
+use crate::image_bitmap::ImageBitmap;
+use crate::{dom_exception_wrapper, TransferBox, Transferable};
+
+dom_exception_wrapper!(DataCloneError);
+
+/// A type that can be serialized into a value that supports the browser's "structured clone"
+/// algorithm.
+///
+/// The data model is restricted to values that can be structurally cloned: primitives, strings,
+/// arrays, objects, maps, sets, dates, array buffers, blobs and image bitmaps. Implementations for
+/// structs and enums can be derived with `#[derive(StructuredClone)]`, in which case structs with
+/// named fields serialize as plain objects, tuple structs serialize as arrays, newtype structs
+/// serialize as their inner value and unit structs serialize as `null`. Enums use an "externally
+/// tagged" representation: unit variants serialize as a string holding the variant's name, other
+/// variants serialize as an object with a single property named after the variant.
+pub trait StructuredClone {
+    fn serialize(&self, serializer: Serializer) -> Serialized;
+}
+
+/// The result of serializing a value with a [Serializer].
+pub struct Serialized {
+    inner: JsValue,
+}
+
+impl Serialized {
+    fn new(inner: JsValue) -> Self {
+        Serialized { inner }
+    }
+
+    pub(crate) fn into_js_value(self) -> JsValue {
+        self.inner
+    }
+}
+
+struct SerializeContext {
+    transfer_list: js_sys::Array,
+    restore_transferred: RefCell<Vec<Box<dyn FnOnce()>>>,
+    error: RefCell<Option<String>>,
+}
+
+pub struct Serializer {
+    context: Rc<SerializeContext>,
+}
+
+impl Serializer {
+    fn new(context: Rc<SerializeContext>) -> Self {
+        Serializer { context }
+    }
+
+    fn record_error(&self, message: &str) {
+        let mut error = self.context.error.borrow_mut();
+
+        if error.is_none() {
+            *error = Some(message.to_string());
+        }
+    }
+
+    // Records a callback that puts a transferred object back where it was taken from, should the
+    // message not be posted.
+    fn record_transferred(&self, restore: Box<dyn FnOnce()>) {
+        self.context.restore_transferred.borrow_mut().push(restore);
+    }
+
+    pub fn serialize_undefined(self) -> Serialized {
+        Serialized::new(JsValue::undefined())
+    }
+
+    pub fn serialize_null(self) -> Serialized {
+        Serialized::new(JsValue::null())
+    }
+
+    pub fn serialize_bool(self, value: bool) -> Serialized {
+        Serialized::new(JsValue::from_bool(value))
+    }
+
+    pub fn serialize_f64(self, value: f64) -> Serialized {
+        Serialized::new(JsValue::from_f64(value))
+    }
+
+    /// Serializes the `value` as a `BigInt`.
+    pub fn serialize_i64(self, value: i64) -> Serialized {
+        Serialized::new(js_sys::BigInt::from(value).into())
+    }
+
+    /// Serializes the `value` as a `BigInt`.
+    pub fn serialize_u64(self, value: u64) -> Serialized {
+        Serialized::new(js_sys::BigInt::from(value).into())
+    }
+
+    /// Serializes the `value` as a `BigInt`.
+    pub fn serialize_i128(self, value: i128) -> Serialized {
+        Serialized::new(js_sys::BigInt::from(value).into())
+    }
+
+    /// Serializes the `value` as a `BigInt`.
+    pub fn serialize_u128(self, value: u128) -> Serialized {
+        Serialized::new(js_sys::BigInt::from(value).into())
+    }
+
+    pub fn serialize_str(self, value: &str) -> Serialized {
+        Serialized::new(JsValue::from_str(value))
+    }
+
+    /// Serializes a `Date` for the given time value in milliseconds since the UNIX epoch.
+    pub fn serialize_date(self, time_value: f64) -> Serialized {
+        Serialized::new(js_sys::Date::new(&JsValue::from_f64(time_value)).into())
+    }
+
+    /// Serializes an `ArrayBuffer` that holds a copy of the `bytes`.
+    pub fn serialize_array_buffer(self, bytes: &[u8]) -> Serialized {
+        Serialized::new(js_sys::Uint8Array::from(bytes).buffer().into())
+    }
+
+    pub fn serialize_blob(self, blob: &Blob) -> Serialized {
+        let js_value: &JsValue = blob.as_ref();
+
+        Serialized::new(js_value.clone())
+    }
+
+    pub fn serialize_image_bitmap(self, image_bitmap: &ImageBitmap) -> Serialized {
+        let js_value: &JsValue = image_bitmap.as_ref();
+
+        Serialized::new(js_value.clone())
+    }
+
+    /// Serializes the `transferable` object and adds it to the transfer list.
+    ///
+    /// The object will be transferred rather than copied; it will no longer be usable in the
+    /// current agent after the transfer.
+    pub fn serialize_transferable<T>(self, transferable: T) -> Serialized
+    where
+        T: Transferable,
+    {
+        let js_value = transferable.as_transferable().clone();
+
+        self.context.transfer_list.push(&js_value);
+
+        Serialized::new(js_value)
+    }
+
+    pub fn serialize_array(self) -> ArraySerializer {
+        ArraySerializer {
+            context: self.context,
+            array: js_sys::Array::new(),
+        }
+    }
+
+    pub fn serialize_object(self) -> ObjectSerializer {
+        ObjectSerializer {
+            context: self.context,
+            object: js_sys::Object::new(),
+        }
+    }
+
+    pub fn serialize_map(self) -> MapSerializer {
+        MapSerializer {
+            context: self.context,
+            map: js_sys::Map::new(),
+        }
+    }
+
+    pub fn serialize_set(self) -> SetSerializer {
+        SetSerializer {
+            context: self.context,
+            set: js_sys::Set::new(&JsValue::undefined()),
+        }
+    }
+
+    // Note: not public, only used for JS types that we know to be serializable.
+    fn serialize_js_value(self, value: &JsValue) -> Serialized {
+        Serialized::new(value.clone())
+    }
+}
+
+pub struct ArraySerializer {
+    context: Rc<SerializeContext>,
+    array: js_sys::Array,
+}
+
+impl ArraySerializer {
+    pub fn serialize_element<T>(&mut self, element: &T)
+    where
+        T: StructuredClone + ?Sized,
+    {
+        let serialized = element.serialize(Serializer::new(self.context.clone()));
+
+        self.array.push(&serialized.inner);
+    }
+
+    pub fn end(self) -> Serialized {
+        Serialized::new(self.array.into())
+    }
+}
+
+pub struct ObjectSerializer {
+    context: Rc<SerializeContext>,
+    object: js_sys::Object,
+}
+
+impl ObjectSerializer {
+    pub fn serialize_property<T>(&mut self, key: &str, value: &T)
+    where
+        T: StructuredClone + ?Sized,
+    {
+        let serialized = value.serialize(Serializer::new(self.context.clone()));
+
+        // Setting a property on a plain object we just created cannot fail.
+        js_sys::Reflect::set(&self.object, &JsValue::from_str(key), &serialized.inner).unwrap();
+    }
+
+    /// Serializes a property with the given `key`, using the `f` callback to serialize the
+    /// property's value.
+    pub fn serialize_property_with<F>(&mut self, key: &str, f: F)
+    where
+        F: FnOnce(Serializer) -> Serialized,
+    {
+        let serialized = f(Serializer::new(self.context.clone()));
+
+        js_sys::Reflect::set(&self.object, &JsValue::from_str(key), &serialized.inner).unwrap();
+    }
+
+    pub fn end(self) -> Serialized {
+        Serialized::new(self.object.into())
+    }
+}
+
+pub struct MapSerializer {
+    context: Rc<SerializeContext>,
+    map: js_sys::Map,
+}
+
+impl MapSerializer {
+    pub fn serialize_entry<K, V>(&mut self, key: &K, value: &V)
+    where
+        K: StructuredClone + ?Sized,
+        V: StructuredClone + ?Sized,
+    {
+        let key = key.serialize(Serializer::new(self.context.clone()));
+        let value = value.serialize(Serializer::new(self.context.clone()));
+
+        self.map.set(&key.inner, &value.inner);
+    }
+
+    pub fn end(self) -> Serialized {
+        Serialized::new(self.map.into())
+    }
+}
+
+pub struct SetSerializer {
+    context: Rc<SerializeContext>,
+    set: js_sys::Set,
+}
+
+impl SetSerializer {
+    pub fn serialize_element<T>(&mut self, element: &T)
+    where
+        T: StructuredClone + ?Sized,
+    {
+        let serialized = element.serialize(Serializer::new(self.context.clone()));
+
+        self.set.add(&serialized.inner);
+    }
+
+    pub fn end(self) -> Serialized {
+        Serialized::new(self.set.into())
+    }
+}
+
+pub(crate) struct SerializedMessage {
+    pub(crate) data: JsValue,
+    pub(crate) transfer_list: js_sys::Array,
+    restore_transferred: Vec<Box<dyn FnOnce()>>,
+}
+
+impl SerializedMessage {
+    /// Puts any objects that were taken out of a [TransferBox] back into their boxes; to be called
+    /// when the message could not be posted.
+    pub(crate) fn restore_transferred(self) {
+        for restore in self.restore_transferred {
+            restore();
+        }
+    }
+}
+
+pub(crate) fn serialize<T>(value: &T) -> Result<SerializedMessage, DataCloneError>
+where
+    T: StructuredClone + ?Sized,
+{
+    let context = Rc::new(SerializeContext {
+        transfer_list: js_sys::Array::new(),
+        restore_transferred: RefCell::new(Vec::new()),
+        error: RefCell::new(None),
+    });
+
+    let data = value
+        .serialize(Serializer::new(context.clone()))
+        .into_js_value();
+
+    let message = SerializedMessage {
+        data,
+        transfer_list: context.transfer_list.clone(),
+        restore_transferred: context.restore_transferred.take(),
+    };
+
+    if let Some(error) = context.error.borrow_mut().take() {
+        message.restore_transferred();
+
+        return Err(new_data_clone_error(&error));
+    }
+
+    Ok(message)
+}
+
+pub(crate) fn new_data_clone_error(message: &str) -> DataCloneError {
+    DataCloneError::new(
+        web_sys::DomException::new_with_message_and_name(message, "DataCloneError").unwrap_throw(),
+    )
+}
+
+impl StructuredClone for () {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_undefined()
+    }
+}
+
+impl StructuredClone for bool {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_bool(*self)
+    }
+}
+
+macro_rules! impl_structured_clone_number {
+    ($($tpe:ident),*) => {
+        $(
+            impl StructuredClone for $tpe {
+                fn serialize(&self, serializer: Serializer) -> Serialized {
+                    serializer.serialize_f64(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_structured_clone_number!(i8, i16, i32, isize, u8, u16, u32, usize, f32, f64);
+
+macro_rules! impl_structured_clone_big_int {
+    ($($tpe:ident => $method:ident),*) => {
+        $(
+            impl StructuredClone for $tpe {
+                fn serialize(&self, serializer: Serializer) -> Serialized {
+                    serializer.$method(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_structured_clone_big_int!(
+    i64 => serialize_i64,
+    u64 => serialize_u64,
+    i128 => serialize_i128,
+    u128 => serialize_u128
+);
+
+impl StructuredClone for char {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_str(self.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl StructuredClone for str {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_str(self)
+    }
+}
+
+impl StructuredClone for String {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_str(self)
+    }
+}
+
+impl StructuredClone for SystemTime {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let time_value = match self.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs_f64() * 1000.0,
+            Err(err) => -err.duration().as_secs_f64() * 1000.0,
+        };
+
+        serializer.serialize_date(time_value)
+    }
+}
+
+impl<T> StructuredClone for Option<T>
+where
+    T: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        match self {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_null(),
+        }
+    }
+}
+
+impl<T> StructuredClone for &'_ T
+where
+    T: StructuredClone + ?Sized,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> StructuredClone for &'_ mut T
+where
+    T: StructuredClone + ?Sized,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        (**self).serialize(serializer)
+    }
+}
+
+macro_rules! impl_structured_clone_deref {
+    ($($tpe:ident),*) => {
+        $(
+            impl<T> StructuredClone for $tpe<T>
+            where
+                T: StructuredClone + ?Sized,
+            {
+                fn serialize(&self, serializer: Serializer) -> Serialized {
+                    (**self).serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_structured_clone_deref!(Box, Rc, Arc);
+
+impl<T> StructuredClone for Cow<'_, T>
+where
+    T: StructuredClone + ToOwned + ?Sized,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> StructuredClone for Ref<'_, T>
+where
+    T: StructuredClone + ?Sized,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> StructuredClone for RefMut<'_, T>
+where
+    T: StructuredClone + ?Sized,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T> StructuredClone for RefCell<T>
+where
+    T: StructuredClone + ?Sized,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl<T> StructuredClone for [T]
+where
+    T: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let mut array = serializer.serialize_array();
+
+        for element in self {
+            array.serialize_element(element);
+        }
+
+        array.end()
+    }
+}
+
+impl<T, const N: usize> StructuredClone for [T; N]
+where
+    T: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+macro_rules! impl_structured_clone_seq {
+    ($($tpe:ident),*) => {
+        $(
+            impl<T> StructuredClone for $tpe<T>
+            where
+                T: StructuredClone,
+            {
+                fn serialize(&self, serializer: Serializer) -> Serialized {
+                    let mut array = serializer.serialize_array();
+
+                    for element in self {
+                        array.serialize_element(element);
+                    }
+
+                    array.end()
+                }
+            }
+        )*
+    };
+}
+
+impl_structured_clone_seq!(Vec, VecDeque, LinkedList);
+
+impl<T, S> StructuredClone for HashSet<T, S>
+where
+    T: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let mut set = serializer.serialize_set();
+
+        for element in self {
+            set.serialize_element(element);
+        }
+
+        set.end()
+    }
+}
+
+impl<T> StructuredClone for BTreeSet<T>
+where
+    T: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let mut set = serializer.serialize_set();
+
+        for element in self {
+            set.serialize_element(element);
+        }
+
+        set.end()
+    }
+}
+
+impl<K, V, S> StructuredClone for HashMap<K, V, S>
+where
+    K: StructuredClone,
+    V: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let mut map = serializer.serialize_map();
+
+        for (key, value) in self {
+            map.serialize_entry(key, value);
+        }
+
+        map.end()
+    }
+}
+
+impl<K, V> StructuredClone for BTreeMap<K, V>
+where
+    K: StructuredClone,
+    V: StructuredClone,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let mut map = serializer.serialize_map();
+
+        for (key, value) in self {
+            map.serialize_entry(key, value);
+        }
+
+        map.end()
+    }
+}
+
+macro_rules! impl_structured_clone_tuple {
+    ($($name:ident)+) => {
+        impl<$($name),+> StructuredClone for ($($name,)+)
+        where
+            $($name: StructuredClone),+
+        {
+            #[allow(non_snake_case)]
+            fn serialize(&self, serializer: Serializer) -> Serialized {
+                let ($($name,)+) = self;
+                let mut array = serializer.serialize_array();
+
+                $(array.serialize_element($name);)+
+
+                array.end()
+            }
+        }
+    };
+}
+
+impl_structured_clone_tuple!(T0);
+impl_structured_clone_tuple!(T0 T1);
+impl_structured_clone_tuple!(T0 T1 T2);
+impl_structured_clone_tuple!(T0 T1 T2 T3);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5 T6);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5 T6 T7);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10);
+impl_structured_clone_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11);
+
+impl<T> StructuredClone for TransferBox<T>
+where
+    T: Transferable + 'static,
+{
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        match self.take() {
+            Some(transferable) => {
+                let slot = self.slot();
+                let js_value = transferable.as_transferable().clone();
+
+                serializer.record_transferred(Box::new(move || {
+                    slot.set(Some(T::from_transferred(js_value)));
+                }));
+
+                serializer.serialize_transferable(transferable)
+            }
+            None => {
+                serializer.record_error("the transfer box no longer holds an object to transfer");
+
+                serializer.serialize_undefined()
+            }
+        }
+    }
+}
+
+impl StructuredClone for Blob {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_blob(self)
+    }
+}
+
+impl StructuredClone for File {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        let js_value: &JsValue = self.as_ref();
+
+        serializer.serialize_js_value(js_value)
+    }
+}
+
+impl StructuredClone for ImageBitmap {
+    fn serialize(&self, serializer: Serializer) -> Serialized {
+        serializer.serialize_image_bitmap(self)
+    }
+}
+
+macro_rules! impl_structured_clone_js_sys {
+    ($($tpe:ident),*) => {
+        $(
+            impl StructuredClone for js_sys::$tpe {
+                fn serialize(&self, serializer: Serializer) -> Serialized {
+                    serializer.serialize_js_value(self.unchecked_ref())
+                }
+            }
+        )*
+    };
+}
+
+impl_structured_clone_js_sys!(
+    ArrayBuffer,
+    BigInt,
+    Boolean,
+    DataView,
+    Date,
+    Float32Array,
+    Float64Array,
+    Int8Array,
+    Int16Array,
+    Int32Array,
+    JsString,
+    Number,
+    RegExp,
+    Uint8Array,
+    Uint8ClampedArray,
+    Uint16Array,
+    Uint32Array,
+    BigInt64Array,
+    BigUint64Array
+);
@@ -1,5 +1,44 @@
+use std::time;
+
+/// The duration of a timer.
+///
+/// Timers are scheduled with millisecond precision. A [std::time::Duration] may be converted into a
+/// timer duration with [Duration::from_std] (or the equivalent [From] implementation).
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Duration {
     Infinity,
     Milliseconds(u32),
 }
+
+impl Duration {
+    /// Converts a [std::time::Duration] into a timer duration.
+    ///
+    /// Fractional milliseconds are always rounded up, so that a timer never fires before the
+    /// requested duration has passed; in particular, a non-zero duration never becomes
+    /// `Milliseconds(0)`. Durations that exceed `u32::MAX` milliseconds become
+    /// [Duration::Infinity].
+    pub fn from_std(duration: time::Duration) -> Self {
+        let milliseconds = duration.as_nanos().div_ceil(1_000_000);
+
+        u32::try_from(milliseconds)
+            .map(Duration::Milliseconds)
+            .unwrap_or(Duration::Infinity)
+    }
+
+    /// Converts this timer duration into a [std::time::Duration], or returns `None` if this is
+    /// [Duration::Infinity].
+    pub fn to_std(&self) -> Option<time::Duration> {
+        match self {
+            Duration::Infinity => None,
+            Duration::Milliseconds(milliseconds) => {
+                Some(time::Duration::from_millis(*milliseconds as u64))
+            }
+        }
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        Duration::from_std(duration)
+    }
+}
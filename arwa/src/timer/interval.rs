@@ -14,6 +14,9 @@ enum TimerContext {
 
 impl TimerContext {
     fn spawn(&self, callback: &Closure<dyn FnMut()>, milliseconds: u32) -> i32 {
+        // Browsers store the delay as a signed 32 bit integer and fire immediately if it overflows.
+        let milliseconds = milliseconds.min(i32::MAX as u32);
+
         match self {
             TimerContext::Window(window) => window
                 .set_interval_with_callback_and_timeout_and_arguments_0(
@@ -36,6 +39,65 @@ impl TimerContext {
             TimerContext::WorkerGlobalScope(worker) => worker.clear_interval_with_handle(timer_id),
         }
     }
+
+    fn spawn_timeout(&self, callback: &Closure<dyn FnMut()>, milliseconds: u32) -> i32 {
+        let milliseconds = milliseconds.min(i32::MAX as u32);
+
+        match self {
+            TimerContext::Window(window) => window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    callback.as_ref().unchecked_ref(),
+                    milliseconds as i32,
+                )
+                .unwrap_throw(),
+            TimerContext::WorkerGlobalScope(worker) => worker
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    callback.as_ref().unchecked_ref(),
+                    milliseconds as i32,
+                )
+                .unwrap_throw(),
+        }
+    }
+
+    fn clear_timeout(&self, timer_id: i32) {
+        match self {
+            TimerContext::Window(window) => window.clear_timeout_with_handle(timer_id),
+            TimerContext::WorkerGlobalScope(worker) => worker.clear_timeout_with_handle(timer_id),
+        }
+    }
+
+    fn now(&self) -> f64 {
+        let performance = match self {
+            TimerContext::Window(window) => window.performance(),
+            TimerContext::WorkerGlobalScope(worker) => worker.performance(),
+        };
+
+        performance.unwrap_throw().now()
+    }
+}
+
+/// Decides how a drift-corrected [Interval] catches up when one or more ticks could not be yielded
+/// on time (for example, because the event loop was busy, or because the consumer was not polling
+/// the interval).
+///
+/// Also see [Interval::drift_corrected].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum MissedTickBehavior {
+    /// Yield all missed ticks immediately, one after the other, until the interval has caught up
+    /// with its original schedule.
+    #[default]
+    Burst,
+    /// Yield one tick immediately, then schedule the next tick a full period after the moment the
+    /// late tick was yielded.
+    Delay,
+    /// Yield one tick immediately, then skip the missed ticks and resume on the original schedule.
+    Skip,
+}
+
+struct DriftCorrection {
+    missed_tick_behavior: MissedTickBehavior,
+    deadline: Option<f64>,
+    counted_until: f64,
 }
 
 struct CallbackState {
@@ -59,6 +121,8 @@ pub struct Interval {
     callback: Option<Closure<dyn FnMut()>>,
     callback_state: CallbackState,
     timer_id: Option<i32>,
+    drift_correction: Option<DriftCorrection>,
+    missed_ticks: u64,
 }
 
 impl Interval {
@@ -69,6 +133,8 @@ impl Interval {
             callback: None,
             callback_state: CallbackState::uninitialized(),
             timer_id: None,
+            drift_correction: None,
+            missed_ticks: 0,
         }
     }
 
@@ -79,7 +145,120 @@ impl Interval {
             callback: None,
             callback_state: CallbackState::uninitialized(),
             timer_id: None,
+            drift_correction: None,
+            missed_ticks: 0,
+        }
+    }
+
+    /// Schedules the ticks of this interval against [Performance::now] to compensate for drift.
+    ///
+    /// By default, an interval relies on the browser's `setInterval`, which does not guarantee
+    /// that ticks stay aligned with the original schedule, and silently drops ticks that are not
+    /// consumed in time. A drift-corrected interval instead schedules each tick individually, such
+    /// that the `n`th tick is due `n` periods after the interval is first polled. The
+    /// `missed_tick_behavior` decides what happens when ticks could not be yielded on time; the
+    /// number of such ticks is reported by [missed_ticks](Interval::missed_ticks).
+    ///
+    /// Resets the interval if it was already started.
+    ///
+    /// [Performance::now]: crate::performance::Performance::now
+    pub fn drift_corrected(mut self, missed_tick_behavior: MissedTickBehavior) -> Self {
+        self.reset();
+
+        // The callback for `setInterval` does not record ticks while no waker is set.
+        self.callback = None;
+        self.drift_correction = Some(DriftCorrection {
+            missed_tick_behavior,
+            deadline: None,
+            counted_until: 0.0,
+        });
+
+        self
+    }
+
+    /// Returns the number of ticks that were due while an earlier tick had not yet been yielded.
+    ///
+    /// Always returns `0` for an interval that is not [drift corrected](Interval::drift_corrected).
+    pub fn missed_ticks(&self) -> u64 {
+        self.missed_ticks
+    }
+
+    fn reset(&mut self) {
+        if let Some(timer_id) = self.timer_id.take() {
+            if self.drift_correction.is_some() {
+                self.context.clear_timeout(timer_id);
+            } else {
+                self.context.clear(timer_id);
+            }
         }
+
+        self.callback_state.next = None;
+        self.missed_ticks = 0;
+    }
+
+    fn poll_drift_corrected(&mut self, cx: &mut Context<'_>, milliseconds: u32) -> Poll<()> {
+        // A period of `0` would never let the interval catch up, treat it as 1 millisecond.
+        let period = milliseconds.max(1) as f64;
+        let now = self.context.now();
+        let drift_correction = self.drift_correction.as_mut().unwrap_throw();
+        let deadline = *drift_correction.deadline.get_or_insert(now + period);
+        let fired = self.callback_state.next.take().is_some();
+
+        if fired || deadline <= now {
+            if let Some(timer_id) = self.timer_id.take() {
+                // Clear the timer in case we did not get here because it fired.
+                self.context.clear_timeout(timer_id);
+            }
+
+            // Count the deadlines that passed since this tick was due, but take care not to count
+            // deadlines that were already counted when an earlier tick was yielded.
+            let count_from = deadline.max(drift_correction.counted_until);
+            let missed = ((now - count_from) / period).floor().max(0.0);
+
+            drift_correction.counted_until = count_from + missed * period;
+            self.missed_ticks += missed as u64;
+
+            let next_deadline = match drift_correction.missed_tick_behavior {
+                MissedTickBehavior::Burst => deadline + period,
+                MissedTickBehavior::Delay => now + period,
+                MissedTickBehavior::Skip => {
+                    deadline + (((now - deadline) / period).floor().max(0.0) + 1.0) * period
+                }
+            };
+
+            drift_correction.deadline = Some(next_deadline);
+
+            return Poll::Ready(());
+        }
+
+        self.callback_state.waker = Some(cx.waker().clone());
+
+        if self.timer_id.is_none() {
+            let state_ptr = (&mut self.callback_state) as *mut CallbackState;
+
+            let callback = self.callback.get_or_insert_with(|| {
+                let callback = move || {
+                    // This is safe because of Pin
+                    let CallbackState { next, waker } = unsafe { &mut *state_ptr };
+
+                    // Always record that the timer fired, even if there is no waker, so that a
+                    // late tick still counts as due.
+                    next.replace(());
+
+                    if let Some(waker) = waker.take() {
+                        waker.wake();
+                    }
+                };
+
+                Closure::wrap(Box::new(callback) as Box<dyn FnMut()>)
+            });
+
+            let delay = (deadline - now).ceil() as u32;
+
+            self.timer_id = Some(self.context.spawn_timeout(callback, delay));
+        }
+
+        Poll::Pending
     }
 }
 
@@ -92,6 +271,10 @@ impl Stream for Interval {
             Duration::Milliseconds(milliseconds) => milliseconds,
         };
 
+        if self.drift_correction.is_some() {
+            return self.poll_drift_corrected(cx, milliseconds).map(Some);
+        }
+
         // Initialize if not initialized
         if self.timer_id.is_none() {
             let state_ptr = (&mut self.callback_state) as *mut CallbackState;
@@ -130,8 +313,6 @@ impl Stream for Interval {
 
 impl Drop for Interval {
     fn drop(&mut self) {
-        if let Some(timer_id) = self.timer_id {
-            self.context.clear(timer_id);
-        }
+        self.reset();
     }
 }
@@ -14,6 +14,9 @@ enum TimerContext {
 
 impl TimerContext {
     fn spawn(&self, callback: &Closure<dyn FnMut()>, milliseconds: u32) -> i32 {
+        // Browsers store the delay as a signed 32 bit integer and fire immediately if it overflows.
+        let milliseconds = milliseconds.min(i32::MAX as u32);
+
         match self {
             TimerContext::Window(window) => window
                 .set_timeout_with_callback_and_timeout_and_arguments_0(
@@ -81,6 +84,17 @@ impl Timeout {
             timer_id: None,
         }
     }
+
+    /// Creates a timeout in the context of the current global scope, which may either be a window
+    /// or a worker global scope.
+    pub(crate) fn global_context(duration: Duration) -> Self {
+        let global = js_sys::global();
+
+        match global.dyn_into::<web_sys::Window>() {
+            Ok(window) => Timeout::window_context(window, duration),
+            Err(global) => Timeout::worker_context(global.unchecked_into(), duration),
+        }
+    }
 }
 
 impl Future for Timeout {
@@ -1,6 +1,107 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::{JsCast, JsValue};
+
 pub(crate) mod transferable_seal {
-    pub trait Seal {}
+    use wasm_bindgen::JsValue;
+
+    pub trait Seal {
+        #[doc(hidden)]
+        fn as_transferable(&self) -> &JsValue;
+
+        #[doc(hidden)]
+        fn is_transferable_instance(value: &JsValue) -> bool
+        where
+            Self: Sized;
+
+        #[doc(hidden)]
+        fn from_transferred(value: JsValue) -> Self
+        where
+            Self: Sized;
+    }
 }
 
 /// Marker trait for types that can be send across agents.
 pub trait Transferable: transferable_seal::Seal {}
+
+/// Wraps a [Transferable] object so that it gets transferred, rather than copied, when the box is
+/// included in a message.
+///
+/// When a value that contains a `TransferBox` is serialized (e.g. when it is posted as a message
+/// with [MessageSender::post_message](crate::message::MessageSender::post_message)), the boxed
+/// object is taken out of the box and is added to the message's transfer list. After the transfer,
+/// the object is no longer usable in the current agent (it has been "neutered"), and as the box no
+/// longer holds the object, Rust code can no longer touch it either. If the message cannot be
+/// posted, then the object is put back into the box.
+///
+/// On the receiving end, the transferred object can be deserialized as a `TransferBox` again with
+/// [FromStructuredClone](crate::structured_clone::FromStructuredClone).
+pub struct TransferBox<T> {
+    inner: Rc<Cell<Option<T>>>,
+}
+
+impl<T> TransferBox<T>
+where
+    T: Transferable,
+{
+    pub fn new(value: T) -> Self {
+        TransferBox {
+            inner: Rc::new(Cell::new(Some(value))),
+        }
+    }
+
+    /// Takes the object back out of the box, destroying the box.
+    ///
+    /// Returns `None` if the object was already taken out of the box by serializing it as part of
+    /// a message.
+    pub fn into_inner(self) -> Option<T> {
+        self.inner.take()
+    }
+
+    /// Returns `true` if the object was taken out of the box by serializing it as part of a
+    /// message, `false` otherwise.
+    pub fn is_transferred(&self) -> bool {
+        let value = self.inner.take();
+        let is_transferred = value.is_none();
+
+        self.inner.set(value);
+
+        is_transferred
+    }
+
+    pub(crate) fn take(&self) -> Option<T> {
+        self.inner.take()
+    }
+
+    // Returns a handle to the box's slot, so that a transferred object can be put back into the
+    // box if the message it was part of could not be posted.
+    pub(crate) fn slot(&self) -> Rc<Cell<Option<T>>> {
+        self.inner.clone()
+    }
+}
+
+impl<T> From<T> for TransferBox<T>
+where
+    T: Transferable,
+{
+    fn from(value: T) -> Self {
+        TransferBox::new(value)
+    }
+}
+
+impl transferable_seal::Seal for js_sys::ArrayBuffer {
+    fn as_transferable(&self) -> &JsValue {
+        self.as_ref()
+    }
+
+    fn is_transferable_instance(value: &JsValue) -> bool {
+        value.is_instance_of::<js_sys::ArrayBuffer>()
+    }
+
+    fn from_transferred(value: JsValue) -> Self {
+        value.unchecked_into()
+    }
+}
+
+impl Transferable for js_sys::ArrayBuffer {}
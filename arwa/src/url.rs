@@ -485,3 +485,139 @@ impl fmt::Display for Url {
 }
 
 type_error_wrapper!(InvalidUrl);
+
+/// A list of name/value pairs, encoded as `application/x-www-form-urlencoded` (e.g. the query of
+/// a URL).
+///
+/// May be used as the body of a request or response (see [BodySource::UrlSearchParams]).
+///
+/// [BodySource::UrlSearchParams]: crate::fetch::BodySource::UrlSearchParams
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct UrlSearchParams {
+    pairs: Vec<(String, String)>,
+}
+
+impl UrlSearchParams {
+    pub fn new() -> Self {
+        UrlSearchParams { pairs: Vec::new() }
+    }
+
+    /// Decodes the name/value pairs in an `application/x-www-form-urlencoded` string.
+    ///
+    /// A single leading `?` is ignored, so the query of a URL may be passed as is. Decoding never
+    /// fails: invalid percent-encoded sequences are kept as is.
+    pub fn parse(query: &str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+
+        form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.pairs.iter().any(|(n, _)| n == name)
+    }
+
+    /// Returns the first value associated with the `name`, or `None` if there is no such value.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all values associated with the `name`.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.pairs
+            .iter()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn append(&mut self, name: &str, value: &str) {
+        self.pairs.push((name.to_string(), value.to_string()));
+    }
+
+    /// Replaces the value of the first pair with the `name` and removes all other pairs with the
+    /// `name`, or appends a new pair if there is no pair with the `name`.
+    pub fn set(&mut self, name: &str, value: &str) {
+        if let Some(index) = self.pairs.iter().position(|(n, _)| n == name) {
+            self.pairs[index].1 = value.to_string();
+
+            let mut i = 0;
+
+            self.pairs.retain(|(n, _)| {
+                let keep = i <= index || n != name;
+
+                i += 1;
+
+                keep
+            });
+        } else {
+            self.append(name, value);
+        }
+    }
+
+    /// Removes all pairs with the `name`.
+    pub fn remove(&mut self, name: &str) {
+        self.pairs.retain(|(n, _)| n != name);
+    }
+
+    /// Sorts the pairs by name, preserving the relative order of pairs with the same name.
+    pub fn sort(&mut self) {
+        self.pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    /// Returns an iterator over all name/value pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for UrlSearchParams
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        UrlSearchParams {
+            pairs: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+impl<K, V> Extend<(K, V)> for UrlSearchParams
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        self.pairs.extend(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into())),
+        );
+    }
+}
+
+impl fmt::Display for UrlSearchParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let encoded = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&self.pairs)
+            .finish();
+
+        f.write_str(&encoded)
+    }
+}
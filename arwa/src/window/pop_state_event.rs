@@ -4,6 +4,7 @@ use delegate::delegate;
 use wasm_bindgen::JsValue;
 
 use crate::event::impl_typed_event_traits;
+use crate::structured_clone::{deserialize, DeserializeError, FromStructuredClone};
 
 #[derive(Clone)]
 pub struct PopStateEvent<T> {
@@ -17,6 +18,16 @@ impl<T> PopStateEvent<T> {
             pub fn state(&self) -> JsValue;
         }
     }
+
+    /// Deserializes the state of the session history entry that was navigated to as a `S`.
+    ///
+    /// See [History::get_state_as](crate::history::History::get_state_as) for details.
+    pub fn state_as<S>(&self) -> Result<S, DeserializeError>
+    where
+        S: FromStructuredClone,
+    {
+        deserialize(self.inner.state())
+    }
 }
 
 impl_typed_event_traits!(PopStateEvent, PopStateEvent, "popstate");
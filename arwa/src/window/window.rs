@@ -4,7 +4,7 @@ use wasm_bindgen::{throw_val, JsCast, JsError, UnwrapThrowExt};
 use crate::connection::{connection_event_target_seal, ConnectionEventTarget};
 use crate::crypto::Crypto;
 use crate::cssom::{CssReadOnlyStyleDeclaration, Screen};
-use crate::dom::{DynamicDocument, DynamicElement, Element};
+use crate::dom::{DynamicDocument, DynamicElement, Element, PseudoElement};
 use crate::event::{impl_event_target_traits, impl_try_from_event_target, typed_event_iterator};
 use crate::execution::{execution_event_target_seal, ExecutionEventTarget};
 use crate::fetch::{
@@ -267,27 +267,28 @@ impl Window {
             .into()
     }
 
-    // TODO: pseudo selector parsing
-    // pub fn computed_style_for_psuedo<E>(
-    //     &self,
-    //     element: E,
-    //     pseudo: &str,
-    // ) -> Result<CssReadOnlyStyleDeclaration, InvalidPseudoElement>
-    // where
-    //     E: Element,
-    // {
-    //     self.inner
-    //         .get_computed_style_with_pseudo_elt(element.as_ref(), pseudo)
-    //         .map(|ok| {
-    //             // No indication in the spec that this can be null, unwrap for now.
-    //             ok.unwrap_throw().into()
-    //         })
-    //         .map_err(|err| {
-    //             let err: js_sys::TypeError = err.unchecked_into();
-    //
-    //             TypeError::new(err)
-    //         })
-    // }
+    /// Returns the computed style for the `pseudo_element` of the `element`.
+    ///
+    /// If the element does not have the pseudo-element (e.g. `::marker` on an element that is not
+    /// a list item), the returned style declaration is empty.
+    pub fn computed_style_for_pseudo<E>(
+        &self,
+        element: E,
+        pseudo_element: &PseudoElement,
+    ) -> CssReadOnlyStyleDeclaration
+    where
+        E: Element,
+    {
+        // This should never fail, as the pseudo-element is known to be valid.
+        self.inner
+            .get_computed_style_with_pseudo_elt(
+                element.as_web_sys_element(),
+                pseudo_element.as_ref(),
+            )
+            .unwrap_throw()
+            .unwrap_throw()
+            .into()
+    }
 
     pub fn move_by(&self, x: i32, y: i32) {
         // Note: move_by can fail, but this fails silently and does not throw an error, unwrap for
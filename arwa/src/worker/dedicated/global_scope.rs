@@ -1,4 +1,4 @@
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 
 use crate::fetch::{cache_context_seal, CacheContext};
 use crate::message::{
@@ -42,7 +42,16 @@ impl message_event_target_seal::Seal for DedicatedWorkerGlobalScope {
 
 impl MessageEventTarget for DedicatedWorkerGlobalScope {}
 
-impl message_sender_seal::Seal for DedicatedWorkerGlobalScope {}
+impl message_sender_seal::Seal for DedicatedWorkerGlobalScope {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        self.inner
+            .post_message_with_transfer(message, transfer_list)
+    }
+}
 
 impl MessageSender for DedicatedWorkerGlobalScope {}
 
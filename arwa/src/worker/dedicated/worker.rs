@@ -68,7 +68,16 @@ impl worker_seal::Seal for DedicatedWorker {
 
 impl Worker for DedicatedWorker {}
 
-impl message_sender_seal::Seal for DedicatedWorker {}
+impl message_sender_seal::Seal for DedicatedWorker {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        self.inner
+            .post_message_with_transfer(message, transfer_list)
+    }
+}
 
 impl MessageSender for DedicatedWorker {}
 
@@ -3,7 +3,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use delegate::delegate;
-use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{Client as WebSysClient, ServiceWorkerRegistration};
 
@@ -355,7 +355,16 @@ impl service_worker_client_seal::Seal for Client {
 
 impl ServiceWorkerClient for Client {}
 
-impl message_sender_seal::Seal for Client {}
+impl message_sender_seal::Seal for Client {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        self.inner
+            .post_message_with_transfer(message, transfer_list)
+    }
+}
 
 impl MessageSender for Client {}
 
@@ -437,7 +446,16 @@ impl service_worker_client_seal::Seal for WindowClient {
 
 impl ServiceWorkerClient for WindowClient {}
 
-impl message_sender_seal::Seal for WindowClient {}
+impl message_sender_seal::Seal for WindowClient {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        self.inner
+            .post_message_with_transfer(message, transfer_list)
+    }
+}
 
 impl MessageSender for WindowClient {}
 
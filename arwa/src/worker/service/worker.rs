@@ -1,6 +1,6 @@
 use std::marker;
 
-use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
 
 use crate::event::{
     impl_event_target_traits, impl_try_from_event_target, impl_typed_event_traits,
@@ -61,7 +61,16 @@ impl worker_seal::Seal for ServiceWorker {
 
 impl Worker for ServiceWorker {}
 
-impl message_sender_seal::Seal for ServiceWorker {}
+impl message_sender_seal::Seal for ServiceWorker {
+    fn post_message_internal(
+        &self,
+        message: &JsValue,
+        transfer_list: &js_sys::Array,
+    ) -> Result<(), JsValue> {
+        self.inner
+            .post_message_with_transferable(message, transfer_list)
+    }
+}
 
 impl MessageSender for ServiceWorker {}
 
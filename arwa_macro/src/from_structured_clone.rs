@@ -0,0 +1,131 @@
+use proc_macro::{Diagnostic, Level};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Data, DeriveInput, Fields, Generics};
+
+pub fn expand_derive_from_structured_clone(input: DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => deserialize_fields(quote!(#ident), &data.fields),
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let variant_name = variant_ident.unraw().to_string();
+
+                if let Fields::Unit = variant.fields {
+                    quote! {
+                        #variant_name => {
+                            variant.deserialize_unit()?;
+
+                            Ok(#ident::#variant_ident)
+                        }
+                    }
+                } else {
+                    let deserialize =
+                        deserialize_fields(quote!(#ident::#variant_ident), &variant.fields);
+
+                    quote! {
+                        #variant_name => variant.deserialize_data_with(|deserializer| {
+                            #deserialize
+                        })
+                    }
+                }
+            });
+
+            quote! {
+                let variant = deserializer.deserialize_enum()?;
+                let name = variant.variant().to_string();
+
+                match name.as_str() {
+                    #(#arms,)*
+                    _ => Err(variant.unknown_variant_error()),
+                }
+            }
+        }
+        Data::Union(data) => {
+            Diagnostic::spanned(
+                data.union_token.span().unwrap(),
+                Level::Error,
+                "`FromStructuredClone` cannot be derived for unions",
+            )
+            .emit();
+
+            return TokenStream::new();
+        }
+    };
+
+    quote! {
+        impl #impl_generics arwa::structured_clone::FromStructuredClone for #ident #ty_generics #where_clause {
+            fn deserialize(
+                deserializer: arwa::structured_clone::Deserializer
+            ) -> Result<Self, arwa::structured_clone::DeserializeError> {
+                #body
+            }
+        }
+    }
+}
+
+fn deserialize_fields(constructor: TokenStream, fields: &Fields) -> TokenStream {
+    match fields {
+        Fields::Named(fields) => {
+            let properties = fields.named.iter().map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                let name = ident.unraw().to_string();
+
+                quote!(#ident: object.deserialize_property(#name)?)
+            });
+
+            quote! {
+                let object = deserializer.deserialize_object()?;
+                let value = #constructor {
+                    #(#properties,)*
+                };
+
+                object.end()?;
+
+                Ok(value)
+            }
+        }
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+            arwa::structured_clone::FromStructuredClone::deserialize(deserializer).map(#constructor)
+        },
+        Fields::Unnamed(fields) => {
+            let len = fields.unnamed.len();
+            let elements = fields
+                .unnamed
+                .iter()
+                .map(|_| quote!(array.deserialize_element()?.unwrap()));
+
+            quote! {
+                let mut array = deserializer.deserialize_tuple(#len)?;
+
+                // The length of the array was verified, so unwrapping the elements cannot fail.
+                let value = #constructor(#(#elements),*);
+
+                array.end()?;
+
+                Ok(value)
+            }
+        }
+        Fields::Unit => quote! {
+            deserializer.deserialize_null()?;
+
+            Ok(#constructor)
+        },
+    }
+}
+
+fn add_trait_bounds(mut generics: Generics) -> Generics {
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(arwa::structured_clone::FromStructuredClone));
+    }
+
+    generics
+}
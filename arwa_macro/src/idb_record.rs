@@ -0,0 +1,250 @@
+use arwa_parse::idb_key_path::IdbKeyPath;
+use proc_macro::{Diagnostic, Level};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Attribute, Data, DeriveInput, Fields, Generics, Lit, Meta, NestedMeta};
+
+pub fn expand_derive_idb_record(input: DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields,
+            fields => {
+                Diagnostic::spanned(
+                    fields.span().unwrap(),
+                    Level::Error,
+                    "`IdbRecord` can only be derived for structs with named fields",
+                )
+                .emit();
+
+                return TokenStream::new();
+            }
+        },
+        Data::Enum(data) => {
+            Diagnostic::spanned(
+                data.enum_token.span().unwrap(),
+                Level::Error,
+                "`IdbRecord` can only be derived for structs with named fields",
+            )
+            .emit();
+
+            return TokenStream::new();
+        }
+        Data::Union(data) => {
+            Diagnostic::spanned(
+                data.union_token.span().unwrap(),
+                Level::Error,
+                "`IdbRecord` can only be derived for structs with named fields",
+            )
+            .emit();
+
+            return TokenStream::new();
+        }
+    };
+
+    let field_names: Vec<String> = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap().unraw().to_string())
+        .collect();
+
+    let mut key_path = None;
+    let mut auto_increment = false;
+
+    for meta in idb_attribute_items(&input.attrs) {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident("key_path") =>
+            {
+                if let Lit::Str(lit) = &name_value.lit {
+                    let value = lit.value();
+
+                    if let Err(err) = IdbKeyPath::parse(&value) {
+                        Diagnostic::spanned(lit.span().unwrap(), Level::Error, err.to_string())
+                            .emit();
+                    } else {
+                        let root = value.split('.').next().unwrap_or_default();
+
+                        if !field_names.iter().any(|name| name == root) {
+                            Diagnostic::spanned(
+                                lit.span().unwrap(),
+                                Level::Error,
+                                format!("key path refers to unknown field `{}`", root),
+                            )
+                            .emit();
+                        }
+                    }
+
+                    key_path = Some(lit.clone());
+                } else {
+                    Diagnostic::spanned(
+                        name_value.lit.span().unwrap(),
+                        Level::Error,
+                        "expected a string literal",
+                    )
+                    .emit();
+                }
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("auto_increment") => {
+                auto_increment = true;
+            }
+            meta => {
+                Diagnostic::spanned(
+                    meta.span().unwrap(),
+                    Level::Error,
+                    "unknown attribute; expected `key_path = \"...\"` or `auto_increment`",
+                )
+                .emit();
+            }
+        }
+    }
+
+    let key_config = if let Some(key_path) = key_path {
+        quote! {
+            Some(arwa::indexed_db::KeyConfig {
+                path: arwa::indexed_db::KeyPath::from_statically_parsed(
+                    arwa::indexed_db::StaticallyParsedKeyPath {
+                        key_path: #key_path
+                    }
+                ),
+                auto_increment: #auto_increment,
+            })
+        }
+    } else {
+        if auto_increment {
+            Diagnostic::spanned(
+                ident.span().unwrap(),
+                Level::Error,
+                "`auto_increment` requires a `key_path`",
+            )
+            .emit();
+        }
+
+        quote!(None)
+    };
+
+    let mut indexes = Vec::new();
+
+    for field in &fields.named {
+        let field_name = field.ident.as_ref().unwrap().unraw().to_string();
+
+        let mut index_name = None;
+        let mut unique = false;
+        let mut multi_entry = false;
+
+        for meta in idb_attribute_items(&field.attrs) {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("index") => {
+                    index_name = Some(field_name.clone());
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("index") =>
+                {
+                    if let Lit::Str(lit) = &name_value.lit {
+                        index_name = Some(lit.value());
+                    } else {
+                        Diagnostic::spanned(
+                            name_value.lit.span().unwrap(),
+                            Level::Error,
+                            "expected a string literal",
+                        )
+                        .emit();
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("unique") => {
+                    unique = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("multi_entry") => {
+                    multi_entry = true;
+                }
+                meta => {
+                    Diagnostic::spanned(
+                        meta.span().unwrap(),
+                        Level::Error,
+                        "unknown attribute; expected `index`, `index = \"...\"`, `unique` or \
+                        `multi_entry`",
+                    )
+                    .emit();
+                }
+            }
+        }
+
+        if let Some(index_name) = index_name {
+            indexes.push(quote! {
+                arwa::indexed_db::IndexConfig {
+                    name: #index_name.to_string(),
+                    key_path: arwa::indexed_db::KeyPath::from_statically_parsed(
+                        arwa::indexed_db::StaticallyParsedKeyPath {
+                            key_path: #field_name
+                        }
+                    ),
+                    options: arwa::indexed_db::CreateIndexOptions {
+                        unique: #unique,
+                        multi_entry: #multi_entry,
+                    },
+                }
+            });
+        } else if unique || multi_entry {
+            Diagnostic::spanned(
+                field.span().unwrap(),
+                Level::Error,
+                "`unique` and `multi_entry` require an `index`",
+            )
+            .emit();
+        }
+    }
+
+    quote! {
+        impl #impl_generics arwa::indexed_db::IdbRecord for #ident #ty_generics #where_clause {
+            fn key_config() -> Option<arwa::indexed_db::KeyConfig> {
+                #key_config
+            }
+
+            fn indexes() -> Vec<arwa::indexed_db::IndexConfig> {
+                vec![#(#indexes),*]
+            }
+        }
+    }
+}
+
+// Collects the items of all `#[idb(...)]` attributes.
+fn idb_attribute_items(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    let mut items = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("idb")) {
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => items.extend(list.nested),
+            Ok(meta) => {
+                Diagnostic::spanned(
+                    meta.span().unwrap(),
+                    Level::Error,
+                    "expected a list of items, e.g. `#[idb(index)]`",
+                )
+                .emit();
+            }
+            Err(err) => {
+                Diagnostic::spanned(err.span().unwrap(), Level::Error, err.to_string()).emit();
+            }
+        }
+    }
+
+    items
+}
+
+fn add_trait_bounds(mut generics: Generics) -> Generics {
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(arwa::structured_clone::StructuredClone));
+        param
+            .bounds
+            .push(parse_quote!(arwa::structured_clone::FromStructuredClone));
+    }
+
+    generics
+}
@@ -2,16 +2,22 @@
 
 use arwa_parse::custom_element_name::CustomElementName;
 use arwa_parse::dom_token::Token;
+use arwa_parse::header::HeaderName;
 use arwa_parse::idb_key_path::IdbKeyPath;
 use arwa_parse::request_method::RequestMethod;
-use arwa_parse::selector::Selector;
+use arwa_parse::route_pattern::{RoutePattern, Segment};
+use arwa_parse::selector::{PseudoElement, Selector};
 use arwa_parse::xml_name::{Name, NonColonName, QualifiedName};
 use oxilangtag::LanguageTag;
 use proc_macro::{Diagnostic, Level, TokenStream};
 use quote::quote;
-use syn::{parse_macro_input, LitStr};
+use syn::{parse_macro_input, DeriveInput, LitStr};
 use url::{Origin, Url};
 
+mod from_structured_clone;
+mod idb_record;
+mod structured_clone;
+
 #[proc_macro]
 pub fn custom_element_name(tokens_in: TokenStream) -> TokenStream {
     let name_string = parse_macro_input!(tokens_in as LitStr);
@@ -29,6 +35,41 @@ pub fn custom_element_name(tokens_in: TokenStream) -> TokenStream {
     tokens_out.into()
 }
 
+#[proc_macro_derive(FromStructuredClone)]
+pub fn derive_from_structured_clone(tokens_in: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens_in as DeriveInput);
+
+    from_structured_clone::expand_derive_from_structured_clone(input).into()
+}
+
+#[proc_macro]
+pub fn header_name(tokens_in: TokenStream) -> TokenStream {
+    let header_name_string = parse_macro_input!(tokens_in as LitStr);
+
+    // Header names are normalized to lower case.
+    let normalized = match HeaderName::parse(&header_name_string.value()) {
+        Ok(header_name) => header_name.as_ref().to_string(),
+        Err(err) => {
+            Diagnostic::spanned(
+                header_name_string.span().unwrap(),
+                Level::Error,
+                err.to_string(),
+            )
+            .emit();
+
+            header_name_string.value()
+        }
+    };
+
+    let tokens_out = quote! {
+        arwa::fetch::HeaderName::from_statically_parsed(arwa::fetch::StaticallyParsedHeaderName {
+            header_name: #normalized
+        })
+    };
+
+    tokens_out.into()
+}
+
 #[proc_macro]
 pub fn idb_key_path(tokens_in: TokenStream) -> TokenStream {
     let key_path_string = parse_macro_input!(tokens_in as LitStr);
@@ -51,6 +92,13 @@ pub fn idb_key_path(tokens_in: TokenStream) -> TokenStream {
     tokens_out.into()
 }
 
+#[proc_macro_derive(IdbRecord, attributes(idb))]
+pub fn derive_idb_record(tokens_in: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens_in as DeriveInput);
+
+    idb_record::expand_derive_idb_record(input).into()
+}
+
 #[proc_macro]
 pub fn lang(tokens_in: TokenStream) -> TokenStream {
     let lang_string = parse_macro_input!(tokens_in as LitStr);
@@ -214,6 +262,55 @@ pub fn request_method(tokens_in: TokenStream) -> TokenStream {
     tokens_out.into()
 }
 
+#[proc_macro]
+pub fn route(tokens_in: TokenStream) -> TokenStream {
+    let pattern_string = parse_macro_input!(tokens_in as LitStr);
+
+    match RoutePattern::parse(&pattern_string.value()) {
+        Ok(pattern) => {
+            let segments = pattern.segments().iter().map(|segment| match segment {
+                Segment::Static(range) => {
+                    let start = range.start;
+                    let end = range.end;
+
+                    quote!(arwa::router::RouteSegment::Static(#start..#end))
+                }
+                Segment::Param(range) => {
+                    let start = range.start;
+                    let end = range.end;
+
+                    quote!(arwa::router::RouteSegment::Param(#start..#end))
+                }
+                Segment::Wildcard(range) => {
+                    let start = range.start;
+                    let end = range.end;
+
+                    quote!(arwa::router::RouteSegment::Wildcard(#start..#end))
+                }
+            });
+
+            let tokens_out = quote! {
+                arwa::router::RoutePattern::from_statically_parsed(arwa::router::StaticallyParsedRoutePattern {
+                    pattern: #pattern_string,
+                    segments: &[#(#segments),*]
+                })
+            };
+
+            tokens_out.into()
+        }
+        Err(err) => {
+            Diagnostic::spanned(
+                pattern_string.span().unwrap(),
+                Level::Error,
+                err.to_string(),
+            )
+            .emit();
+
+            TokenStream::new()
+        }
+    }
+}
+
 #[proc_macro]
 pub fn selector(tokens_in: TokenStream) -> TokenStream {
     let selector_string = parse_macro_input!(tokens_in as LitStr);
@@ -236,6 +333,35 @@ pub fn selector(tokens_in: TokenStream) -> TokenStream {
     tokens_out.into()
 }
 
+#[proc_macro]
+pub fn pseudo_element(tokens_in: TokenStream) -> TokenStream {
+    let pseudo_element_string = parse_macro_input!(tokens_in as LitStr);
+
+    if let Err(err) = PseudoElement::parse(&pseudo_element_string.value()) {
+        Diagnostic::spanned(
+            pseudo_element_string.span().unwrap(),
+            Level::Error,
+            err.to_string(),
+        )
+        .emit();
+    }
+
+    let tokens_out = quote! {
+        arwa::dom::PseudoElement::from_statically_parsed(arwa::dom::StaticallyParsedPseudoElement {
+            pseudo_element: #pseudo_element_string
+        })
+    };
+
+    tokens_out.into()
+}
+
+#[proc_macro_derive(StructuredClone)]
+pub fn derive_structured_clone(tokens_in: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens_in as DeriveInput);
+
+    structured_clone::expand_derive_structured_clone(input).into()
+}
+
 #[proc_macro]
 pub fn token(tokens_in: TokenStream) -> TokenStream {
     let token_string = parse_macro_input!(tokens_in as LitStr);
@@ -0,0 +1,199 @@
+use proc_macro::{Diagnostic, Level};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Data, DeriveInput, Fields, Generics, Index};
+
+pub fn expand_derive_structured_clone(input: DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => serialize_struct(&data.fields),
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let variant_name = variant_ident.unraw().to_string();
+
+                match &variant.fields {
+                    Fields::Unit => quote! {
+                        #ident::#variant_ident => serializer.serialize_str(#variant_name)
+                    },
+                    Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                        #ident::#variant_ident(__field_0) => {
+                            let mut object = serializer.serialize_object();
+
+                            object.serialize_property(#variant_name, __field_0);
+
+                            object.end()
+                        }
+                    },
+                    Fields::Unnamed(fields) => {
+                        let bindings = field_bindings(fields.unnamed.len());
+
+                        quote! {
+                            #ident::#variant_ident(#(#bindings),*) => {
+                                let mut object = serializer.serialize_object();
+
+                                object.serialize_property_with(#variant_name, |serializer| {
+                                    let mut array = serializer.serialize_array();
+
+                                    #(array.serialize_element(#bindings);)*
+
+                                    array.end()
+                                });
+
+                                object.end()
+                            }
+                        }
+                    }
+                    Fields::Named(fields) => {
+                        // Bind the fields to generated identifiers, so that field names do not
+                        // clash with the locals used in the expansion (e.g. `object`).
+                        let field_idents: Vec<_> = fields
+                            .named
+                            .iter()
+                            .map(|f| f.ident.clone().unwrap())
+                            .collect();
+                        let bindings = field_bindings(field_idents.len());
+                        let names = field_idents.iter().map(|f| f.unraw().to_string());
+
+                        quote! {
+                            #ident::#variant_ident { #(#field_idents: #bindings),* } => {
+                                let mut object = serializer.serialize_object();
+
+                                object.serialize_property_with(#variant_name, |serializer| {
+                                    let mut object = serializer.serialize_object();
+
+                                    #(object.serialize_property(#names, #bindings);)*
+
+                                    object.end()
+                                });
+
+                                object.end()
+                            }
+                        }
+                    }
+                }
+            });
+
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(data) => {
+            Diagnostic::spanned(
+                data.union_token.span().unwrap(),
+                Level::Error,
+                "`StructuredClone` cannot be derived for unions",
+            )
+            .emit();
+
+            return TokenStream::new();
+        }
+    };
+
+    quote! {
+        impl #impl_generics arwa::structured_clone::StructuredClone for #ident #ty_generics #where_clause {
+            fn serialize(
+                &self,
+                serializer: arwa::structured_clone::Serializer
+            ) -> arwa::structured_clone::Serialized {
+                #body
+            }
+        }
+    }
+}
+
+fn serialize_struct(fields: &Fields) -> TokenStream {
+    match fields {
+        Fields::Named(fields) => {
+            let properties = fields.named.iter().map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                let name = ident.unraw().to_string();
+
+                quote!(object.serialize_property(#name, &self.#ident);)
+            });
+
+            quote! {
+                let mut object = serializer.serialize_object();
+
+                #(#properties)*
+
+                object.end()
+            }
+        }
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            quote!(arwa::structured_clone::StructuredClone::serialize(
+                &self.0, serializer
+            ))
+        }
+        Fields::Unnamed(fields) => {
+            let elements = (0..fields.unnamed.len()).map(|i| {
+                let index = Index::from(i);
+
+                quote!(array.serialize_element(&self.#index);)
+            });
+
+            quote! {
+                let mut array = serializer.serialize_array();
+
+                #(#elements)*
+
+                array.end()
+            }
+        }
+        Fields::Unit => quote!(serializer.serialize_null()),
+    }
+}
+
+fn field_bindings(len: usize) -> Vec<Ident> {
+    (0..len).map(|i| format_ident!("__field_{}", i)).collect()
+}
+
+fn add_trait_bounds(mut generics: Generics) -> Generics {
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(arwa::structured_clone::StructuredClone));
+    }
+
+    generics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_fields_use_generated_bindings() {
+        let input: DeriveInput = parse_quote! {
+            enum Message {
+                Text { object: String, serializer: String },
+                Pair(String, String),
+                Single(String),
+            }
+        };
+
+        let expanded = expand_derive_structured_clone(input).to_string();
+
+        assert!(expanded.contains(
+            &quote!(Message::Text {
+                object: __field_0,
+                serializer: __field_1
+            })
+            .to_string()
+        ));
+        assert!(expanded.contains(&quote!(Message::Pair(__field_0, __field_1)).to_string()));
+        assert!(expanded.contains(&quote!(Message::Single(__field_0)).to_string()));
+        assert!(
+            expanded.contains(&quote!(object.serialize_property("object", __field_0);).to_string())
+        );
+        assert!(expanded
+            .contains(&quote!(object.serialize_property("serializer", __field_1);).to_string()));
+    }
+}
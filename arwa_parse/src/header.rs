@@ -0,0 +1,539 @@
+use std::error::Error;
+use std::fmt;
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+#[derive(Clone)]
+pub struct InvalidHeaderName {
+    header_name: String,
+    invalid_pos: usize,
+}
+
+impl fmt::Display for InvalidHeaderName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.header_name.is_empty() {
+            write!(f, "The empty string is not a valid header name.")
+        } else {
+            write!(
+                f,
+                "`{}` is not a valid header name; disallowed code point at position `{}`.",
+                self.header_name, self.invalid_pos
+            )
+        }
+    }
+}
+
+impl fmt::Debug for InvalidHeaderName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for InvalidHeaderName {}
+
+/// A valid HTTP header name.
+///
+/// Header names are case-insensitive; a parsed header name is normalized to lower case.
+#[derive(Clone)]
+pub struct HeaderName {
+    header_name: String,
+}
+
+impl HeaderName {
+    pub fn parse(header_name: &str) -> Result<Self, InvalidHeaderName> {
+        if header_name.is_empty() {
+            return Err(InvalidHeaderName {
+                header_name: header_name.to_string(),
+                invalid_pos: 0,
+            });
+        }
+
+        for (i, c) in header_name.chars().enumerate() {
+            if !is_token_char(c) {
+                return Err(InvalidHeaderName {
+                    header_name: header_name.to_string(),
+                    invalid_pos: i,
+                });
+            }
+        }
+
+        Ok(HeaderName {
+            header_name: header_name.to_ascii_lowercase(),
+        })
+    }
+
+    pub fn trusted(header_name: String) -> Self {
+        HeaderName { header_name }
+    }
+}
+
+impl AsRef<str> for HeaderName {
+    fn as_ref(&self) -> &str {
+        &self.header_name
+    }
+}
+
+/// Splits a comma-separated header value into its elements.
+///
+/// Commas inside quoted strings do not separate elements. Leading and trailing whitespace is
+/// trimmed from each element and empty elements are skipped.
+pub fn split_list(value: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == ',' && !in_quotes {
+            elements.push(&value[start..i]);
+            start = i + 1;
+        }
+    }
+
+    elements.push(&value[start..]);
+
+    elements
+        .into_iter()
+        .map(|element| element.trim_matches([' ', '\t']))
+        .filter(|element| !element.is_empty())
+        .collect()
+}
+
+// Headers whose values are defined as comma-separated lists, the elements of which never contain
+// unquoted commas. Note that this excludes e.g. `Link` and `WWW-Authenticate`, which are lists of
+// elements that may contain unquoted commas.
+const LIST_HEADERS: &[&str] = &[
+    "accept",
+    "accept-charset",
+    "accept-encoding",
+    "accept-language",
+    "accept-patch",
+    "accept-ranges",
+    "access-control-allow-headers",
+    "access-control-allow-methods",
+    "access-control-expose-headers",
+    "access-control-request-headers",
+    "allow",
+    "alt-svc",
+    "cache-control",
+    "connection",
+    "content-encoding",
+    "content-language",
+    "expect",
+    "forwarded",
+    "if-match",
+    "if-none-match",
+    "pragma",
+    "server-timing",
+    "te",
+    "timing-allow-origin",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "vary",
+    "via",
+    "x-forwarded-for",
+];
+
+/// Whether the value of the header with the given name is a comma-separated list that can be
+/// split into its elements with [split_list].
+///
+/// Returns `false` for headers that are not known to be lists, as well as for headers that hold a
+/// single value that may contain commas (e.g. dates in `Expires` or `Last-Modified`).
+pub fn is_list_header(header_name: &str) -> bool {
+    LIST_HEADERS
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(header_name))
+}
+
+// Removes the quotes and escapes from a quoted string, or returns the value as is if it is not a
+// quoted string.
+fn unquote(value: &str) -> String {
+    if let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        let mut unquoted = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                if let Some(c) = chars.next() {
+                    unquoted.push(c);
+                }
+            } else {
+                unquoted.push(c);
+            }
+        }
+
+        unquoted
+    } else {
+        value.to_string()
+    }
+}
+
+/// The directives of a `Cache-Control` header.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CacheControl {
+    directives: Vec<(String, Option<String>)>,
+}
+
+impl CacheControl {
+    /// Parses the value of a `Cache-Control` header.
+    ///
+    /// Directive names are normalized to lower case and quoted directive values are unquoted.
+    /// Elements that are not valid directives are ignored.
+    pub fn parse(value: &str) -> Self {
+        let directives = split_list(value)
+            .into_iter()
+            .filter_map(|directive| {
+                let (name, value) = match directive.split_once('=') {
+                    Some((name, value)) => (name.trim(), Some(unquote(value.trim()))),
+                    None => (directive, None),
+                };
+
+                if !name.is_empty() && name.chars().all(is_token_char) {
+                    Some((name.to_ascii_lowercase(), value))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        CacheControl { directives }
+    }
+
+    /// Returns an iterator over the directive names and their (optional) values.
+    pub fn directives(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.directives
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_deref()))
+    }
+
+    /// Returns `None` if the directive is not present, or `Some` with the (optional) value of the
+    /// first occurrence of the directive if it is present.
+    ///
+    /// The `name` is matched case-insensitively.
+    pub fn directive(&self, name: &str) -> Option<Option<&str>> {
+        self.directives
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_deref())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.directive(name).is_some()
+    }
+
+    fn seconds(&self, name: &str) -> Option<u64> {
+        self.directive(name).flatten()?.parse().ok()
+    }
+
+    pub fn max_age(&self) -> Option<u64> {
+        self.seconds("max-age")
+    }
+
+    pub fn s_maxage(&self) -> Option<u64> {
+        self.seconds("s-maxage")
+    }
+
+    pub fn stale_while_revalidate(&self) -> Option<u64> {
+        self.seconds("stale-while-revalidate")
+    }
+
+    pub fn no_cache(&self) -> bool {
+        self.contains("no-cache")
+    }
+
+    pub fn no_store(&self) -> bool {
+        self.contains("no-store")
+    }
+
+    pub fn no_transform(&self) -> bool {
+        self.contains("no-transform")
+    }
+
+    pub fn must_revalidate(&self) -> bool {
+        self.contains("must-revalidate")
+    }
+
+    pub fn public(&self) -> bool {
+        self.contains("public")
+    }
+
+    pub fn private(&self) -> bool {
+        self.contains("private")
+    }
+
+    pub fn immutable(&self) -> bool {
+        self.contains("immutable")
+    }
+}
+
+/// An entity tag, as used by the `ETag` header.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Parses an entity tag, e.g. `"xyzzy"` or `W/"xyzzy"`.
+    ///
+    /// Returns `None` if the value is not a valid entity tag.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (weak, quoted) = match value.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value),
+        };
+
+        let tag = quoted.strip_prefix('"')?.strip_suffix('"')?;
+
+        // An entity tag may contain any visible character except the double quote, and also allows
+        // non-ASCII characters.
+        if tag
+            .chars()
+            .any(|c| c == '"' || c.is_ascii_control() || c == ' ')
+        {
+            return None;
+        }
+
+        Some(EntityTag {
+            weak,
+            tag: tag.to_string(),
+        })
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// The opaque tag, without the quotes.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Compares two entity tags with the strong comparison function: both tags must be strong and
+    /// their opaque tags must be equal.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compares two entity tags with the weak comparison function: their opaque tags must be
+    /// equal, regardless of either tag being weak.
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/\"{}\"", self.tag)
+        } else {
+            write!(f, "\"{}\"", self.tag)
+        }
+    }
+}
+
+/// An element of a header value list with a quality value, such as the media ranges in an
+/// `Accept` header.
+#[derive(Clone, PartialEq, Debug)]
+pub struct QualityItem<'a> {
+    /// The element, without its `q` parameter.
+    pub value: String,
+    /// The source element.
+    pub source: &'a str,
+    /// The quality value, between `0.0` and `1.0`.
+    pub quality: f32,
+}
+
+fn parse_quality(value: &str) -> Option<f32> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+    if fraction.len() > 3 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    match integer {
+        "0" => format!("0.{}", fraction).parse().ok(),
+        "1" if fraction.chars().all(|c| c == '0') => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Parses a comma-separated list of elements with optional quality values (`q` parameters), such
+/// as an `Accept` header.
+///
+/// Elements without a `q` parameter have a quality of `1.0`; elements with an invalid `q`
+/// parameter are ignored. The items are ordered by descending quality; items with equal quality
+/// keep their original order.
+pub fn parse_quality_list(value: &str) -> Vec<QualityItem<'_>> {
+    let mut items: Vec<QualityItem> = split_list(value)
+        .into_iter()
+        .filter_map(|element| {
+            let mut parts = element.split(';');
+            let mut value = parts.next().unwrap_or("").trim().to_string();
+            let mut quality = 1.0;
+
+            for part in parts {
+                let part = part.trim();
+
+                match part.split_once('=') {
+                    Some((name, q)) if name.trim().eq_ignore_ascii_case("q") => {
+                        quality = parse_quality(q.trim())?;
+                    }
+                    _ => {
+                        value.push(';');
+                        value.push_str(part);
+                    }
+                }
+            }
+
+            Some(QualityItem {
+                value,
+                source: element,
+                quality,
+            })
+        })
+        .collect();
+
+    // `sort_by` is stable, so items with equal quality keep their original order.
+    items.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_name_valid() {
+        assert_eq!(
+            HeaderName::parse("Content-Type").unwrap().as_ref(),
+            "content-type"
+        );
+        assert!(HeaderName::parse("x-custom_header!").is_ok());
+    }
+
+    #[test]
+    fn header_name_invalid() {
+        assert!(HeaderName::parse("").is_err());
+        assert!(HeaderName::parse("content type").is_err());
+        assert!(HeaderName::parse("content:type").is_err());
+        assert!(HeaderName::parse("naïve").is_err());
+    }
+
+    #[test]
+    fn split_list_quoted() {
+        assert_eq!(
+            split_list(r#" a, "b, c" ,, d="e\",f" "#),
+            vec!["a", r#""b, c""#, r#"d="e\",f""#]
+        );
+        assert_eq!(split_list(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn list_headers() {
+        assert!(is_list_header("accept-encoding"));
+        assert!(is_list_header("Cache-Control"));
+        assert!(!is_list_header("expires"));
+        assert!(!is_list_header("last-modified"));
+        assert!(!is_list_header("www-authenticate"));
+        assert!(!is_list_header("x-custom"));
+    }
+
+    #[test]
+    fn cache_control() {
+        let cache_control =
+            CacheControl::parse(r#"Max-Age=3600, no-cache="Set-Cookie", public, bad name"#);
+
+        assert_eq!(cache_control.max_age(), Some(3600));
+        assert_eq!(
+            cache_control.directive("no-cache"),
+            Some(Some("Set-Cookie"))
+        );
+        assert!(cache_control.public());
+        assert!(!cache_control.private());
+        assert_eq!(cache_control.s_maxage(), None);
+        assert_eq!(cache_control.directives().count(), 3);
+    }
+
+    #[test]
+    fn cache_control_invalid_seconds() {
+        assert_eq!(CacheControl::parse("max-age=soon").max_age(), None);
+        assert_eq!(CacheControl::parse("max-age").max_age(), None);
+    }
+
+    #[test]
+    fn entity_tag() {
+        let strong = EntityTag::parse(r#""xyzzy""#).unwrap();
+        let weak = EntityTag::parse(r#" W/"xyzzy" "#).unwrap();
+
+        assert!(!strong.is_weak());
+        assert!(weak.is_weak());
+        assert_eq!(weak.tag(), "xyzzy");
+        assert!(strong.weak_eq(&weak));
+        assert!(!strong.strong_eq(&weak));
+        assert!(strong.strong_eq(&strong));
+        assert_eq!(weak.to_string(), r#"W/"xyzzy""#);
+        assert_eq!(EntityTag::parse(r#""""#).unwrap().tag(), "");
+    }
+
+    #[test]
+    fn entity_tag_invalid() {
+        assert!(EntityTag::parse("xyzzy").is_none());
+        assert!(EntityTag::parse(r#"w/"xyzzy""#).is_none());
+        assert!(EntityTag::parse(r#""xy"zzy""#).is_none());
+        assert!(EntityTag::parse(r#""xyzzy"#).is_none());
+    }
+
+    #[test]
+    fn quality_list_ordering() {
+        let items =
+            parse_quality_list("text/*;q=0.3, text/html;q=0.7, text/html;level=1, */*;q=0.5");
+        let values: Vec<(&str, f32)> = items
+            .iter()
+            .map(|item| (item.value.as_str(), item.quality))
+            .collect();
+
+        assert_eq!(
+            values,
+            vec![
+                ("text/html;level=1", 1.0),
+                ("text/html", 0.7),
+                ("*/*", 0.5),
+                ("text/*", 0.3)
+            ]
+        );
+    }
+
+    #[test]
+    fn quality_list_stable() {
+        let items = parse_quality_list("b, a;q=1.000, c ; Q=0.5; level=1");
+
+        assert_eq!(items[0].value, "b");
+        assert_eq!(items[1].value, "a");
+        assert_eq!(items[2].value, "c;level=1");
+        assert_eq!(items[2].source, "c ; Q=0.5; level=1");
+    }
+
+    #[test]
+    fn quality_list_invalid_quality() {
+        let items = parse_quality_list("a;q=2, b;q=0.1234, c;q=1.5, d;q=0.001");
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].value, "d");
+        assert_eq!(items[0].quality, 0.001);
+    }
+}
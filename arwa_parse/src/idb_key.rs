@@ -0,0 +1,567 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An IndexedDB key.
+///
+/// Keys are ordered according to the IndexedDB key comparison algorithm: numbers sort before
+/// dates, dates before strings, strings before binary keys and binary keys before arrays. Numbers
+/// and dates compare numerically, strings compare by their UTF-16 code units, binary keys compare
+/// byte-wise and arrays compare element-wise (where an array that is a prefix of another array
+/// sorts first).
+///
+/// Note that `NaN` is not a valid number key and that a date key must be a valid time value, see
+/// [is_valid](Key::is_valid).
+#[derive(Clone, Debug)]
+pub enum Key {
+    Number(f64),
+    /// A date, in milliseconds since the Unix epoch.
+    Date(f64),
+    String(String),
+    Binary(Vec<u8>),
+    Array(Vec<Key>),
+}
+
+impl Key {
+    /// Whether this key is a valid IndexedDB key.
+    ///
+    /// A key is invalid if it is (or contains) a number that is `NaN`, or a date that is not a
+    /// valid time value: a date must be finite and must not be more than 8.64e15 milliseconds
+    /// (100,000,000 days) away from the Unix epoch.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Key::Number(value) => !value.is_nan(),
+            Key::Date(value) => value.is_finite() && value.abs() <= MAX_TIME_VALUE,
+            Key::String(_) | Key::Binary(_) => true,
+            Key::Array(keys) => keys.iter().all(|key| key.is_valid()),
+        }
+    }
+
+    fn type_rank(&self) -> u8 {
+        match self {
+            Key::Number(_) => 0,
+            Key::Date(_) => 1,
+            Key::String(_) => 2,
+            Key::Binary(_) => 3,
+            Key::Array(_) => 4,
+        }
+    }
+}
+
+// The greatest absolute time value that a date may have.
+const MAX_TIME_VALUE: f64 = 8.64e15;
+
+fn compare_numbers(a: f64, b: f64) -> Ordering {
+    // Valid keys never contain `NaN`; fall back to a total order so that `Ord` remains lawful for
+    // invalid keys. Note that `-0.0` and `0.0` compare as equal, as they do in IndexedDB.
+    a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b))
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Key::Number(a), Key::Number(b)) | (Key::Date(a), Key::Date(b)) => {
+                compare_numbers(*a, *b)
+            }
+            (Key::String(a), Key::String(b)) => a.encode_utf16().cmp(b.encode_utf16()),
+            (Key::Binary(a), Key::Binary(b)) => a.cmp(b),
+            (Key::Array(a), Key::Array(b)) => a.cmp(b),
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+/// Conversion into an IndexedDB [Key].
+///
+/// Rust integers up to 32 bits and floats convert into number keys, strings into string keys,
+/// byte vectors and byte slices into binary keys, [SystemTime] into date keys and tuples into array
+/// keys.
+pub trait IntoKey {
+    fn into_key(self) -> Key;
+}
+
+impl IntoKey for Key {
+    fn into_key(self) -> Key {
+        self
+    }
+}
+
+impl IntoKey for &'_ Key {
+    fn into_key(self) -> Key {
+        self.clone()
+    }
+}
+
+macro_rules! impl_into_number_key {
+    ($($tpe:ident),*) => {
+        $(
+            impl IntoKey for $tpe {
+                fn into_key(self) -> Key {
+                    Key::Number(self as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_number_key!(i8, i16, i32, u8, u16, u32, f32, f64);
+
+impl IntoKey for String {
+    fn into_key(self) -> Key {
+        Key::String(self)
+    }
+}
+
+impl IntoKey for &'_ str {
+    fn into_key(self) -> Key {
+        Key::String(self.to_string())
+    }
+}
+
+impl IntoKey for Vec<u8> {
+    fn into_key(self) -> Key {
+        Key::Binary(self)
+    }
+}
+
+impl IntoKey for &'_ [u8] {
+    fn into_key(self) -> Key {
+        Key::Binary(self.to_vec())
+    }
+}
+
+impl IntoKey for SystemTime {
+    fn into_key(self) -> Key {
+        let milliseconds = match self.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs_f64() * 1000.0,
+            Err(err) => -err.duration().as_secs_f64() * 1000.0,
+        };
+
+        Key::Date(milliseconds)
+    }
+}
+
+impl IntoKey for Vec<Key> {
+    fn into_key(self) -> Key {
+        Key::Array(self)
+    }
+}
+
+macro_rules! impl_into_array_key {
+    ($($tpe:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<$($tpe),*> IntoKey for ($($tpe,)*)
+        where
+            $($tpe: IntoKey),*
+        {
+            fn into_key(self) -> Key {
+                let ($($tpe,)*) = self;
+
+                Key::Array(vec![$($tpe.into_key()),*])
+            }
+        }
+    };
+}
+
+impl_into_array_key!(A);
+impl_into_array_key!(A, B);
+impl_into_array_key!(A, B, C);
+impl_into_array_key!(A, B, C, D);
+impl_into_array_key!(A, B, C, D, E);
+impl_into_array_key!(A, B, C, D, E, F);
+
+/// Error returned when creating a [KeyRange] from invalid bounds.
+#[derive(Clone, PartialEq, Debug)]
+pub enum InvalidKeyRange {
+    /// One of the bounds is not a valid key, see [Key::is_valid].
+    InvalidKey,
+    /// The lower bound is greater than the upper bound, or the bounds are equal and at least one
+    /// of the bounds is open.
+    EmptyRange,
+}
+
+impl fmt::Display for InvalidKeyRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidKeyRange::InvalidKey => f.write_str("key range bound is not a valid key"),
+            InvalidKeyRange::EmptyRange => f.write_str("key range is empty"),
+        }
+    }
+}
+
+impl Error for InvalidKeyRange {}
+
+/// A continuous interval over IndexedDB [Key]s.
+///
+/// Key ranges can be created from Rust's range types, e.g. `KeyRange::try_from(1..10)` or
+/// `KeyRange::try_from("a"..="f")`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyRange {
+    lower: Bound<Key>,
+    upper: Bound<Key>,
+}
+
+impl KeyRange {
+    /// Creates a key range from a `lower` and an `upper` bound.
+    ///
+    /// Returns an error if either bound is not a valid key or if the range would be empty.
+    pub fn new(lower: Bound<Key>, upper: Bound<Key>) -> Result<Self, InvalidKeyRange> {
+        let is_valid = |bound: &Bound<Key>| match bound {
+            Bound::Included(key) | Bound::Excluded(key) => key.is_valid(),
+            Bound::Unbounded => true,
+        };
+
+        if !is_valid(&lower) || !is_valid(&upper) {
+            return Err(InvalidKeyRange::InvalidKey);
+        }
+
+        match (&lower, &upper) {
+            (Bound::Included(l), Bound::Included(u)) if l > u => {
+                return Err(InvalidKeyRange::EmptyRange)
+            }
+            (Bound::Included(l) | Bound::Excluded(l), Bound::Excluded(u))
+            | (Bound::Excluded(l), Bound::Included(u))
+                if l >= u =>
+            {
+                return Err(InvalidKeyRange::EmptyRange)
+            }
+            _ => (),
+        }
+
+        Ok(KeyRange { lower, upper })
+    }
+
+    /// Creates a key range that only contains the given `key`.
+    pub fn only<K>(key: K) -> Result<Self, InvalidKeyRange>
+    where
+        K: IntoKey,
+    {
+        let key = key.into_key();
+
+        KeyRange::new(Bound::Included(key.clone()), Bound::Included(key))
+    }
+
+    /// Creates a key range that contains all keys greater than (or equal to, if the bound is not
+    /// `open`) the given `lower` bound.
+    pub fn lower_bound<K>(lower: K, open: bool) -> Result<Self, InvalidKeyRange>
+    where
+        K: IntoKey,
+    {
+        KeyRange::new(bound(lower.into_key(), open), Bound::Unbounded)
+    }
+
+    /// Creates a key range that contains all keys smaller than (or equal to, if the bound is not
+    /// `open`) the given `upper` bound.
+    pub fn upper_bound<K>(upper: K, open: bool) -> Result<Self, InvalidKeyRange>
+    where
+        K: IntoKey,
+    {
+        KeyRange::new(Bound::Unbounded, bound(upper.into_key(), open))
+    }
+
+    pub fn lower(&self) -> Bound<&Key> {
+        self.lower.as_ref()
+    }
+
+    pub fn upper(&self) -> Bound<&Key> {
+        self.upper.as_ref()
+    }
+
+    /// Whether the `key` falls inside this key range.
+    pub fn contains(&self, key: &Key) -> bool {
+        let above_lower = match &self.lower {
+            Bound::Included(lower) => key >= lower,
+            Bound::Excluded(lower) => key > lower,
+            Bound::Unbounded => true,
+        };
+
+        let below_upper = match &self.upper {
+            Bound::Included(upper) => key <= upper,
+            Bound::Excluded(upper) => key < upper,
+            Bound::Unbounded => true,
+        };
+
+        above_lower && below_upper
+    }
+}
+
+fn bound(key: Key, open: bool) -> Bound<Key> {
+    if open {
+        Bound::Excluded(key)
+    } else {
+        Bound::Included(key)
+    }
+}
+
+impl<K> TryFrom<Range<K>> for KeyRange
+where
+    K: IntoKey,
+{
+    type Error = InvalidKeyRange;
+
+    fn try_from(range: Range<K>) -> Result<Self, Self::Error> {
+        KeyRange::new(
+            Bound::Included(range.start.into_key()),
+            Bound::Excluded(range.end.into_key()),
+        )
+    }
+}
+
+impl<K> TryFrom<RangeInclusive<K>> for KeyRange
+where
+    K: IntoKey,
+{
+    type Error = InvalidKeyRange;
+
+    fn try_from(range: RangeInclusive<K>) -> Result<Self, Self::Error> {
+        let (start, end) = range.into_inner();
+
+        KeyRange::new(
+            Bound::Included(start.into_key()),
+            Bound::Included(end.into_key()),
+        )
+    }
+}
+
+impl<K> TryFrom<RangeFrom<K>> for KeyRange
+where
+    K: IntoKey,
+{
+    type Error = InvalidKeyRange;
+
+    fn try_from(range: RangeFrom<K>) -> Result<Self, Self::Error> {
+        KeyRange::lower_bound(range.start, false)
+    }
+}
+
+impl<K> TryFrom<RangeTo<K>> for KeyRange
+where
+    K: IntoKey,
+{
+    type Error = InvalidKeyRange;
+
+    fn try_from(range: RangeTo<K>) -> Result<Self, Self::Error> {
+        KeyRange::upper_bound(range.end, true)
+    }
+}
+
+impl<K> TryFrom<RangeToInclusive<K>> for KeyRange
+where
+    K: IntoKey,
+{
+    type Error = InvalidKeyRange;
+
+    fn try_from(range: RangeToInclusive<K>) -> Result<Self, Self::Error> {
+        KeyRange::upper_bound(range.end, false)
+    }
+}
+
+/// A query that selects records by key: either a single [Key], or a [KeyRange].
+#[derive(Clone, PartialEq, Debug)]
+pub enum KeyQuery {
+    Key(Key),
+    Range(KeyRange),
+}
+
+/// Conversion into a [KeyQuery].
+///
+/// Implemented for all types that implement [IntoKey], and for [KeyRange]. The full range `..`
+/// selects all records.
+pub trait IntoKeyQuery {
+    fn into_key_query(self) -> KeyQuery;
+}
+
+impl<K> IntoKeyQuery for K
+where
+    K: IntoKey,
+{
+    fn into_key_query(self) -> KeyQuery {
+        KeyQuery::Key(self.into_key())
+    }
+}
+
+impl IntoKeyQuery for KeyRange {
+    fn into_key_query(self) -> KeyQuery {
+        KeyQuery::Range(self)
+    }
+}
+
+impl IntoKeyQuery for &'_ KeyRange {
+    fn into_key_query(self) -> KeyQuery {
+        KeyQuery::Range(self.clone())
+    }
+}
+
+impl IntoKeyQuery for RangeFull {
+    fn into_key_query(self) -> KeyQuery {
+        KeyQuery::Range(KeyRange {
+            lower: Bound::Unbounded,
+            upper: Bound::Unbounded,
+        })
+    }
+}
+
+impl IntoKeyQuery for KeyQuery {
+    fn into_key_query(self) -> KeyQuery {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_order() {
+        let keys = [
+            Key::Number(f64::INFINITY),
+            Key::Date(-8.64e15),
+            Key::String(String::new()),
+            Key::Binary(Vec::new()),
+            Key::Array(Vec::new()),
+        ];
+
+        for window in keys.windows(2) {
+            assert!(window[0] < window[1]);
+        }
+    }
+
+    #[test]
+    fn number_order() {
+        assert!(1.into_key() < 2.into_key());
+        assert!((-1.5).into_key() < 0.into_key());
+        assert_eq!(Key::Number(-0.0), Key::Number(0.0));
+        assert!(Key::Number(f64::NEG_INFINITY) < Key::Number(f64::MIN));
+    }
+
+    #[test]
+    fn string_order_uses_utf16_code_units() {
+        // U+10000 is encoded as a surrogate pair starting with 0xD800, which sorts before 0xFFFF,
+        // even though the code point is greater.
+        assert!("\u{10000}".into_key() < "\u{FFFF}".into_key());
+        assert!("a".into_key() < "b".into_key());
+        assert!("a".into_key() < "aa".into_key());
+        assert!("B".into_key() < "a".into_key());
+    }
+
+    #[test]
+    fn binary_order() {
+        assert!(vec![0u8, 1].into_key() < vec![0u8, 2].into_key());
+        assert!(vec![1u8].into_key() < vec![1u8, 0].into_key());
+        assert!(Vec::<u8>::new().into_key() < vec![0u8].into_key());
+    }
+
+    #[test]
+    fn array_order() {
+        assert!((1,).into_key() < (1, 0).into_key());
+        assert!((1, "b").into_key() < (2, "a").into_key());
+        assert!((1, "a").into_key() < (1, "b").into_key());
+        assert!(("a",).into_key() > (1, 2, 3).into_key());
+        assert_eq!(
+            (1, "a").into_key(),
+            Key::Array(vec![Key::Number(1.0), "a".into_key()])
+        );
+    }
+
+    #[test]
+    fn validity() {
+        assert!(1.into_key().is_valid());
+        assert!(!f64::NAN.into_key().is_valid());
+        assert!(!Key::Date(f64::NAN).is_valid());
+        assert!(Key::Number(f64::INFINITY).is_valid());
+        assert!(Key::Date(8.64e15).is_valid());
+        assert!(Key::Date(-8.64e15).is_valid());
+        assert!(!Key::Date(f64::INFINITY).is_valid());
+        assert!(!Key::Date(f64::NEG_INFINITY).is_valid());
+        assert!(!Key::Date(8.64e15 + 1.0).is_valid());
+        assert!(!(1, f64::NAN).into_key().is_valid());
+    }
+
+    #[test]
+    fn system_time_into_date() {
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(1500);
+
+        assert_eq!(time.into_key(), Key::Date(1500.0));
+    }
+
+    #[test]
+    fn range_contains() {
+        let range = KeyRange::try_from(1..5).unwrap();
+
+        assert!(range.contains(&1.into_key()));
+        assert!(range.contains(&4.5.into_key()));
+        assert!(!range.contains(&5.into_key()));
+        assert!(!range.contains(&"a".into_key()));
+
+        let range = KeyRange::try_from("a"..="c").unwrap();
+
+        assert!(range.contains(&"bz".into_key()));
+        assert!(range.contains(&"c".into_key()));
+        assert!(!range.contains(&"ca".into_key()));
+
+        let range = KeyRange::try_from(10..).unwrap();
+
+        assert!(range.contains(&"a".into_key()));
+        assert!(!range.contains(&9.into_key()));
+
+        let range = KeyRange::try_from(..10).unwrap();
+
+        assert!(!range.contains(&10.into_key()));
+
+        let range = KeyRange::only((1, "a")).unwrap();
+
+        assert!(range.contains(&(1, "a").into_key()));
+        assert!(!range.contains(&(1, "a", 0).into_key()));
+    }
+
+    #[test]
+    fn key_query() {
+        let range = KeyRange::try_from(1..5).unwrap();
+
+        assert_eq!(1.into_key_query(), KeyQuery::Key(Key::Number(1.0)));
+        assert_eq!((&range).into_key_query(), KeyQuery::Range(range));
+        assert_eq!(
+            (..).into_key_query(),
+            KeyQuery::Range(KeyRange::new(Bound::Unbounded, Bound::Unbounded).unwrap())
+        );
+    }
+
+    #[test]
+    fn range_invalid() {
+        assert_eq!(KeyRange::try_from(5..1), Err(InvalidKeyRange::EmptyRange));
+        assert_eq!(KeyRange::try_from(1..1), Err(InvalidKeyRange::EmptyRange));
+        assert!(KeyRange::try_from(1..=1).is_ok());
+        assert_eq!(
+            KeyRange::try_from("a".."\u{10000}"),
+            Ok(KeyRange::new(
+                Bound::Included("a".into_key()),
+                Bound::Excluded("\u{10000}".into_key())
+            )
+            .unwrap())
+        );
+        assert_eq!(
+            KeyRange::try_from("\u{FFFF}"..="\u{10000}"),
+            Err(InvalidKeyRange::EmptyRange)
+        );
+        assert_eq!(
+            KeyRange::try_from(0.0..f64::NAN),
+            Err(InvalidKeyRange::InvalidKey)
+        );
+    }
+}
@@ -44,6 +44,23 @@ impl IdbKeyPath {
             error,
         })
     }
+
+    /// Creates a key path from a string that is known to be a valid key path (e.g. a key path
+    /// reported by the browser) without validating it.
+    pub fn trusted(key_path: String) -> Self {
+        let mut identifiers = Vec::new();
+        let mut start = 0;
+
+        for identifier in key_path.split('.') {
+            identifiers.push(start..start + identifier.len());
+            start += identifier.len() + 1;
+        }
+
+        IdbKeyPath {
+            raw: key_path,
+            identifiers,
+        }
+    }
 }
 
 impl AsRef<str> for IdbKeyPath {
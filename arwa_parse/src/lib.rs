@@ -1,6 +1,9 @@
 pub mod custom_element_name;
 pub mod dom_token;
+pub mod header;
+pub mod idb_key;
 pub mod idb_key_path;
 pub mod request_method;
+pub mod route_pattern;
 pub mod selector;
 pub mod xml_name;
@@ -0,0 +1,379 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+pub struct InvalidRoutePattern {
+    pattern: String,
+    message: String,
+    offset: usize,
+}
+
+impl fmt::Display for InvalidRoutePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid route pattern: {} (at offset {})",
+            self.pattern, self.message, self.offset
+        )
+    }
+}
+
+impl fmt::Debug for InvalidRoutePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for InvalidRoutePattern {}
+
+/// A segment of a route pattern.
+///
+/// All ranges index into the pattern string. For [Segment::Param] and [Segment::Wildcard] the range
+/// covers the name of the parameter, without the leading `:` or `*`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Segment {
+    Static(Range<usize>),
+    Param(Range<usize>),
+    Wildcard(Range<usize>),
+}
+
+/// A value captured from a path by a [Segment::Param] or [Segment::Wildcard] segment.
+///
+/// The `name` range indexes into the pattern string, the `value` range indexes into the path.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Capture {
+    pub name: Range<usize>,
+    pub value: Range<usize>,
+}
+
+#[derive(Clone)]
+pub struct RoutePattern {
+    raw: String,
+    segments: Vec<Segment>,
+}
+
+impl RoutePattern {
+    pub fn parse(pattern: &str) -> Result<Self, InvalidRoutePattern> {
+        parse_segments(pattern)
+            .map(|segments| RoutePattern {
+                raw: pattern.to_string(),
+                segments,
+            })
+            .map_err(|(message, offset)| InvalidRoutePattern {
+                pattern: pattern.to_string(),
+                message,
+                offset,
+            })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn match_path(&self, path: &str) -> Option<Vec<Capture>> {
+        match_path(&self.raw, &self.segments, path)
+    }
+}
+
+impl AsRef<str> for RoutePattern {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+fn parse_segments(pattern: &str) -> Result<Vec<Segment>, (String, usize)> {
+    if !pattern.starts_with('/') {
+        return Err(("a route pattern must start with `/`".to_string(), 0));
+    }
+
+    let mut segments = Vec::new();
+
+    // The root pattern has no segments.
+    if pattern.len() == 1 {
+        return Ok(segments);
+    }
+
+    let mut names = HashSet::new();
+    let mut start = 1;
+
+    for segment in pattern[1..].split('/') {
+        let end = start + segment.len();
+
+        if let Some(Segment::Wildcard(_)) = segments.last() {
+            return Err((
+                "a wildcard segment must be the last segment".to_string(),
+                start - 1,
+            ));
+        }
+
+        if segment.is_empty() {
+            return Err(("unexpected empty segment".to_string(), start));
+        }
+
+        let parsed = if let Some(name) = segment.strip_prefix(':') {
+            validate_name(name, start + 1)?;
+
+            Segment::Param(start + 1..end)
+        } else if let Some(name) = segment.strip_prefix('*') {
+            validate_name(name, start + 1)?;
+
+            Segment::Wildcard(start + 1..end)
+        } else {
+            for (pos, c) in segment.char_indices() {
+                if c == ':' || c == '*' || c == '?' || c == '#' || c.is_whitespace() {
+                    return Err((format!("unexpected `{}` in static segment", c), start + pos));
+                }
+            }
+
+            Segment::Static(start..end)
+        };
+
+        if let Segment::Param(name) | Segment::Wildcard(name) = &parsed {
+            if !names.insert(&pattern[name.clone()]) {
+                return Err((
+                    format!("duplicate parameter name `{}`", &pattern[name.clone()]),
+                    name.start,
+                ));
+            }
+        }
+
+        segments.push(parsed);
+        start = end + 1;
+    }
+
+    Ok(segments)
+}
+
+fn validate_name(name: &str, offset: usize) -> Result<(), (String, usize)> {
+    let mut chars = name.char_indices();
+
+    match chars.next() {
+        Some((_, c)) if c.is_ascii_alphabetic() || c == '_' => (),
+        Some((_, c)) => {
+            return Err((
+                format!("expected a parameter name but found `{}`", c),
+                offset,
+            ))
+        }
+        None => {
+            return Err((
+                "unexpected end; expected a parameter name".to_string(),
+                offset,
+            ))
+        }
+    }
+
+    for (pos, c) in chars {
+        if !(c.is_ascii_alphanumeric() || c == '_') {
+            return Err((
+                format!("unexpected `{}` in parameter name", c),
+                offset + pos,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Matches the `path` against the `segments` of the route `pattern`.
+///
+/// A single trailing `/` in the `path` is ignored. As with route patterns, a path must not
+/// contain empty segments: a path such as `/users//1` or `/users//` never matches. Returns the
+/// captured parameter values if the path matches, or `None` if it does not.
+pub fn match_path(pattern: &str, segments: &[Segment], path: &str) -> Option<Vec<Capture>> {
+    if !path.starts_with('/') || path.contains("//") {
+        return None;
+    }
+
+    let path = if path.len() > 1 {
+        path.strip_suffix('/').unwrap_or(path)
+    } else {
+        path
+    };
+
+    let mut captures = Vec::new();
+    let mut start = 1;
+
+    for segment in segments {
+        if let Segment::Wildcard(name) = segment {
+            // Also allow the wildcard to match an empty remainder.
+            let value = if start > path.len() {
+                path.len()..path.len()
+            } else {
+                start..path.len()
+            };
+
+            captures.push(Capture {
+                name: name.clone(),
+                value,
+            });
+
+            return Some(captures);
+        }
+
+        if start > path.len() {
+            return None;
+        }
+
+        let end = path[start..]
+            .find('/')
+            .map(|pos| start + pos)
+            .unwrap_or(path.len());
+
+        if start == end {
+            return None;
+        }
+
+        match segment {
+            Segment::Static(range) => {
+                if pattern[range.clone()] != path[start..end] {
+                    return None;
+                }
+            }
+            Segment::Param(name) => captures.push(Capture {
+                name: name.clone(),
+                value: start..end,
+            }),
+            Segment::Wildcard(_) => unreachable!(),
+        }
+
+        start = end + 1;
+    }
+
+    if start >= path.len() {
+        Some(captures)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn captured<'a>(pattern: &'a str, path: &'a str) -> Option<Vec<(&'a str, &'a str)>> {
+        let parsed = RoutePattern::parse(pattern).unwrap();
+
+        parsed.match_path(path).map(|captures| {
+            captures
+                .into_iter()
+                .map(|c| (&pattern[c.name], &path[c.value]))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn parse_root() {
+        assert_eq!(RoutePattern::parse("/").unwrap().segments(), &[]);
+    }
+
+    #[test]
+    fn parse_segments() {
+        assert_eq!(
+            RoutePattern::parse("/users/:id/posts/*rest")
+                .unwrap()
+                .segments(),
+            &[
+                Segment::Static(1..6),
+                Segment::Param(8..10),
+                Segment::Static(11..16),
+                Segment::Wildcard(18..22),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_missing_leading_slash() {
+        assert!(RoutePattern::parse("users").is_err());
+        assert!(RoutePattern::parse("").is_err());
+    }
+
+    #[test]
+    fn parse_empty_segment() {
+        assert!(RoutePattern::parse("/users//posts").is_err());
+        assert!(RoutePattern::parse("/users/").is_err());
+    }
+
+    #[test]
+    fn parse_invalid_param_name() {
+        assert!(RoutePattern::parse("/users/:").is_err());
+        assert!(RoutePattern::parse("/users/:1d").is_err());
+        assert!(RoutePattern::parse("/users/:id-x").is_err());
+        assert!(RoutePattern::parse("/files/*").is_err());
+    }
+
+    #[test]
+    fn parse_duplicate_param_name() {
+        assert!(RoutePattern::parse("/users/:id/posts/:id").is_err());
+        assert!(RoutePattern::parse("/users/:id/*id").is_err());
+    }
+
+    #[test]
+    fn parse_wildcard_not_last() {
+        assert!(RoutePattern::parse("/files/*rest/edit").is_err());
+    }
+
+    #[test]
+    fn parse_invalid_static_segment() {
+        assert!(RoutePattern::parse("/users?id").is_err());
+        assert!(RoutePattern::parse("/users#id").is_err());
+        assert!(RoutePattern::parse("/us:ers").is_err());
+        assert!(RoutePattern::parse("/us ers").is_err());
+    }
+
+    #[test]
+    fn match_root() {
+        assert_eq!(captured("/", "/"), Some(vec![]));
+        assert_eq!(captured("/", "/users"), None);
+    }
+
+    #[test]
+    fn match_static() {
+        assert_eq!(captured("/users", "/users"), Some(vec![]));
+        assert_eq!(captured("/users", "/users/"), Some(vec![]));
+        assert_eq!(captured("/users", "/user"), None);
+        assert_eq!(captured("/users", "/users/1"), None);
+        assert_eq!(captured("/users", "/"), None);
+    }
+
+    #[test]
+    fn match_params() {
+        assert_eq!(
+            captured("/users/:id/posts/:post_id", "/users/42/posts/7"),
+            Some(vec![("id", "42"), ("post_id", "7")])
+        );
+        assert_eq!(captured("/users/:id", "/users"), None);
+        assert_eq!(captured("/users/:id", "/users/"), None);
+        assert_eq!(captured("/users/:id", "/users//"), None);
+        assert_eq!(captured("/users/:id", "/users/42/posts"), None);
+    }
+
+    #[test]
+    fn match_wildcard() {
+        assert_eq!(
+            captured("/users/:id/posts/*rest", "/users/42/posts/a/b/c"),
+            Some(vec![("id", "42"), ("rest", "a/b/c")])
+        );
+        assert_eq!(captured("/files/*path", "/files"), Some(vec![("path", "")]));
+        assert_eq!(
+            captured("/files/*path", "/files/"),
+            Some(vec![("path", "")])
+        );
+        assert_eq!(captured("/files/*path", "/file"), None);
+    }
+
+    #[test]
+    fn match_empty_segment() {
+        assert_eq!(captured("/", "//"), None);
+        assert_eq!(captured("/users", "/users//"), None);
+        assert_eq!(captured("/users", "//users"), None);
+        assert_eq!(captured("/users/:id", "/users//1"), None);
+        assert_eq!(captured("/files/*path", "/files/a//b"), None);
+    }
+
+    #[test]
+    fn match_relative_path() {
+        assert_eq!(captured("/users", "users"), None);
+    }
+}
@@ -0,0 +1,991 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use super::{
+    case_insensitive_eq, is_whitespace, unescape, ANPlusB, AttributeMatcher, AttributeSelector,
+    AttributeWithValue, CaseSensitivity, Combinator, ComplexSelector, CompoundSelector,
+    PseudoClassSelector, RelativeComplexSelector, Selector, SelectorList, TypeSelector,
+};
+
+/// The element states that a selector may query, but which cannot be derived from the structure of
+/// an element tree.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ElementState {
+    AnyLink,
+    Link,
+    Visited,
+    LocalLink,
+    Target,
+    TargetWithin,
+    Hover,
+    Active,
+    Focus,
+    FocusVisible,
+    FocusWithin,
+    Current,
+    Past,
+    Future,
+    Playing,
+    Paused,
+    Enabled,
+    Disabled,
+    ReadOnly,
+    ReadWrite,
+    PlaceholderShown,
+    Default,
+    Checked,
+    Indeterminate,
+    Blank,
+    Valid,
+    Invalid,
+    InRange,
+    OutOfRange,
+    Required,
+    Optional,
+    UserInvalid,
+}
+
+/// An element in a tree that selectors can be matched against with [matches].
+///
+/// Handles are expected to be cheap to clone (e.g. a reference or an index into an arena).
+pub trait SelectorElement: Clone {
+    fn parent_element(&self) -> Option<Self>;
+
+    fn previous_sibling_element(&self) -> Option<Self>;
+
+    fn next_sibling_element(&self) -> Option<Self>;
+
+    fn first_child_element(&self) -> Option<Self>;
+
+    /// Whether the element has any child elements or any non-empty text nodes; an element that does
+    /// not is matched by `:empty`.
+    fn has_children(&self) -> bool;
+
+    fn local_name(&self) -> Cow<'_, str>;
+
+    fn id(&self) -> Option<Cow<'_, str>>;
+
+    fn has_class(&self, class: &str) -> bool;
+
+    fn attribute(&self, name: &str) -> Option<Cow<'_, str>>;
+
+    /// Whether the element is an HTML element in an HTML document.
+    ///
+    /// Type selectors and attribute names are matched ASCII case-insensitively for such elements.
+    fn is_html_element_in_html_document(&self) -> bool {
+        true
+    }
+
+    /// Whether the element is in the given `state`.
+    ///
+    /// Defaults to `false` for all states.
+    fn is_in_state(&self, state: ElementState) -> bool {
+        let _ = state;
+
+        false
+    }
+}
+
+/// Returns `true` if the `element` matches any of the complex selectors in the `selector`, `false`
+/// otherwise.
+///
+/// The `:root` and `:scope` pseudo-classes match an element that has no parent element. The
+/// `:dir()` pseudo-class is resolved from the `dir` attributes of the element and its ancestors,
+/// where `dir="auto"` is treated as left-to-right. Column combinators and the `:nth-col()` and
+/// `:nth-last-col()` pseudo-classes never match. All other pseudo-classes that depend on state
+/// that cannot be derived from the element tree are resolved with
+/// [SelectorElement::is_in_state]. A selector that targets a pseudo-element never matches an
+/// element.
+pub fn matches<E>(selector: &Selector, element: &E) -> bool
+where
+    E: SelectorElement,
+{
+    Matcher {
+        source: &selector.raw,
+    }
+    .matches_selector_list(&selector.parsed, element)
+}
+
+struct Matcher<'a> {
+    source: &'a str,
+}
+
+impl Matcher<'_> {
+    fn identifier(&self, range: &Range<usize>) -> Cow<'_, str> {
+        unescape(&self.source[range.clone()])
+    }
+
+    fn matches_selector_list<E>(&self, selector_list: &SelectorList, element: &E) -> bool
+    where
+        E: SelectorElement,
+    {
+        selector_list
+            .iter()
+            .any(|selector| self.matches_complex(selector, element))
+    }
+
+    fn matches_complex<E>(&self, selector: &ComplexSelector, element: &E) -> bool
+    where
+        E: SelectorElement,
+    {
+        // Match right-to-left: the last compound selector is the subject of the complex selector.
+        self.matches_complex_from(selector, selector.tail.len(), element)
+    }
+
+    // Compound selector `0` is the head of the complex selector, compound selector `n` is the
+    // `n - 1`th selector in the tail.
+    fn matches_complex_from<E>(&self, selector: &ComplexSelector, index: usize, element: &E) -> bool
+    where
+        E: SelectorElement,
+    {
+        if index == 0 {
+            return self.matches_compound(&selector.head, element);
+        }
+
+        let combined = &selector.tail[index - 1];
+
+        if !self.matches_compound(&combined.selector, element) {
+            return false;
+        }
+
+        match combined.combinator {
+            Combinator::Descendant => {
+                let mut current = element.parent_element();
+
+                while let Some(ancestor) = current {
+                    if self.matches_complex_from(selector, index - 1, &ancestor) {
+                        return true;
+                    }
+
+                    current = ancestor.parent_element();
+                }
+
+                false
+            }
+            Combinator::Child => element
+                .parent_element()
+                .map(|parent| self.matches_complex_from(selector, index - 1, &parent))
+                .unwrap_or(false),
+            Combinator::NextSibling => element
+                .previous_sibling_element()
+                .map(|sibling| self.matches_complex_from(selector, index - 1, &sibling))
+                .unwrap_or(false),
+            Combinator::SubsequentSibling => {
+                let mut current = element.previous_sibling_element();
+
+                while let Some(sibling) = current {
+                    if self.matches_complex_from(selector, index - 1, &sibling) {
+                        return true;
+                    }
+
+                    current = sibling.previous_sibling_element();
+                }
+
+                false
+            }
+            Combinator::Column => false,
+        }
+    }
+
+    // Matches the parts of a relative selector left-to-right, starting from the `anchor`.
+    fn matches_relative<E>(
+        &self,
+        selector: &RelativeComplexSelector,
+        index: usize,
+        anchor: &E,
+    ) -> bool
+    where
+        E: SelectorElement,
+    {
+        let Some(combined) = selector.parts.get(index) else {
+            return true;
+        };
+
+        let check = |candidate: &E| {
+            self.matches_compound(&combined.selector, candidate)
+                && self.matches_relative(selector, index + 1, candidate)
+        };
+
+        match combined.combinator {
+            Combinator::Descendant => {
+                let mut stack: Vec<E> = anchor.first_child_element().into_iter().collect();
+
+                while let Some(candidate) = stack.pop() {
+                    if check(&candidate) {
+                        return true;
+                    }
+
+                    if let Some(sibling) = candidate.next_sibling_element() {
+                        stack.push(sibling);
+                    }
+
+                    if let Some(child) = candidate.first_child_element() {
+                        stack.push(child);
+                    }
+                }
+
+                false
+            }
+            Combinator::Child => {
+                let mut current = anchor.first_child_element();
+
+                while let Some(child) = current {
+                    if check(&child) {
+                        return true;
+                    }
+
+                    current = child.next_sibling_element();
+                }
+
+                false
+            }
+            Combinator::NextSibling => anchor
+                .next_sibling_element()
+                .map(|sibling| check(&sibling))
+                .unwrap_or(false),
+            Combinator::SubsequentSibling => {
+                let mut current = anchor.next_sibling_element();
+
+                while let Some(sibling) = current {
+                    if check(&sibling) {
+                        return true;
+                    }
+
+                    current = sibling.next_sibling_element();
+                }
+
+                false
+            }
+            Combinator::Column => false,
+        }
+    }
+
+    fn matches_compound<E>(&self, selector: &CompoundSelector, element: &E) -> bool
+    where
+        E: SelectorElement,
+    {
+        if selector.pseudo_element.is_some() {
+            return false;
+        }
+
+        if let Some(TypeSelector::Identifier(name)) = &selector.type_selector {
+            let name = self.identifier(name);
+            let local_name = element.local_name();
+
+            let matches = if element.is_html_element_in_html_document() {
+                case_insensitive_eq(&local_name, &name)
+            } else {
+                local_name == name
+            };
+
+            if !matches {
+                return false;
+            }
+        }
+
+        if let Some(id) = &selector.id_selector {
+            if element.id().as_deref() != Some(&self.identifier(id)) {
+                return false;
+            }
+        }
+
+        for class in &selector.class_selectors {
+            if !element.has_class(&self.identifier(class)) {
+                return false;
+            }
+        }
+
+        for attribute in &selector.attribute_selectors {
+            if !self.matches_attribute(attribute, element) {
+                return false;
+            }
+        }
+
+        for pseudo_class in &selector.pseudo_class_selectors {
+            if !self.matches_pseudo_class(pseudo_class, element) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn matches_attribute<E>(&self, selector: &AttributeSelector, element: &E) -> bool
+    where
+        E: SelectorElement,
+    {
+        let attribute_value = |name: &str| {
+            if element.is_html_element_in_html_document() {
+                element.attribute(&name.to_ascii_lowercase())
+            } else {
+                element.attribute(name)
+            }
+        };
+
+        match selector {
+            AttributeSelector::Exists(name) => attribute_value(&self.identifier(name)).is_some(),
+            AttributeSelector::WithValue(AttributeWithValue {
+                name,
+                matcher,
+                value,
+                case_sensitivity,
+            }) => {
+                let Some(actual) = attribute_value(&self.identifier(name)) else {
+                    return false;
+                };
+
+                let expected = self.identifier(value);
+
+                let (actual, expected) = if *case_sensitivity == CaseSensitivity::Insensitive {
+                    (
+                        Cow::Owned(actual.to_ascii_lowercase()),
+                        Cow::Owned(expected.to_ascii_lowercase()),
+                    )
+                } else {
+                    (actual, expected)
+                };
+
+                match matcher {
+                    AttributeMatcher::Exact => actual == expected,
+                    AttributeMatcher::Includes => {
+                        !expected.is_empty()
+                            && !expected.contains(is_whitespace)
+                            && actual.split(is_whitespace).any(|part| part == expected)
+                    }
+                    AttributeMatcher::BeginsWith => {
+                        !expected.is_empty() && actual.starts_with(expected.as_ref())
+                    }
+                    AttributeMatcher::EndsWith => {
+                        !expected.is_empty() && actual.ends_with(expected.as_ref())
+                    }
+                    AttributeMatcher::Substring => {
+                        !expected.is_empty() && actual.contains(expected.as_ref())
+                    }
+                    AttributeMatcher::HyphenatedBeginsWidth => {
+                        actual == expected
+                            || actual
+                                .strip_prefix(expected.as_ref())
+                                .map(|rest| rest.starts_with('-'))
+                                .unwrap_or(false)
+                    }
+                }
+            }
+        }
+    }
+
+    fn matches_pseudo_class<E>(&self, selector: &PseudoClassSelector, element: &E) -> bool
+    where
+        E: SelectorElement,
+    {
+        match selector {
+            PseudoClassSelector::Is(list) | PseudoClassSelector::Where(list) => {
+                self.matches_selector_list(list, element)
+            }
+            PseudoClassSelector::Not(list) => !self.matches_selector_list(list, element),
+            PseudoClassSelector::Has(list) => list
+                .iter()
+                .any(|selector| self.matches_relative(selector, 0, element)),
+            PseudoClassSelector::Dir(dir) => {
+                let expected = self.identifier(dir);
+                let actual = if is_rtl(element) { "rtl" } else { "ltr" };
+
+                case_insensitive_eq(&expected, actual)
+            }
+            PseudoClassSelector::Lang(lang) => {
+                let expected = self.identifier(lang);
+
+                element_lang(element)
+                    .map(|actual| lang_matches(&actual, &expected))
+                    .unwrap_or(false)
+            }
+            PseudoClassSelector::AnyLink => element.is_in_state(ElementState::AnyLink),
+            PseudoClassSelector::Link => element.is_in_state(ElementState::Link),
+            PseudoClassSelector::Visited => element.is_in_state(ElementState::Visited),
+            PseudoClassSelector::LocalLink => element.is_in_state(ElementState::LocalLink),
+            PseudoClassSelector::Target => element.is_in_state(ElementState::Target),
+            PseudoClassSelector::TargetWithin => element.is_in_state(ElementState::TargetWithin),
+            PseudoClassSelector::Scope | PseudoClassSelector::Root => {
+                element.parent_element().is_none()
+            }
+            PseudoClassSelector::Hover => element.is_in_state(ElementState::Hover),
+            PseudoClassSelector::Active => element.is_in_state(ElementState::Active),
+            PseudoClassSelector::Focus => element.is_in_state(ElementState::Focus),
+            PseudoClassSelector::FocusVisible => element.is_in_state(ElementState::FocusVisible),
+            PseudoClassSelector::FocusWithin => element.is_in_state(ElementState::FocusWithin),
+            PseudoClassSelector::Current(list) => {
+                element.is_in_state(ElementState::Current)
+                    && list
+                        .as_ref()
+                        .map(|list| self.matches_selector_list(list, element))
+                        .unwrap_or(true)
+            }
+            PseudoClassSelector::Past => element.is_in_state(ElementState::Past),
+            PseudoClassSelector::Future => element.is_in_state(ElementState::Future),
+            PseudoClassSelector::Playing => element.is_in_state(ElementState::Playing),
+            PseudoClassSelector::Paused => element.is_in_state(ElementState::Paused),
+            PseudoClassSelector::Enabled => element.is_in_state(ElementState::Enabled),
+            PseudoClassSelector::Disabled => element.is_in_state(ElementState::Disabled),
+            PseudoClassSelector::ReadOnly => element.is_in_state(ElementState::ReadOnly),
+            PseudoClassSelector::ReadWrite => element.is_in_state(ElementState::ReadWrite),
+            PseudoClassSelector::PlaceholderShown => {
+                element.is_in_state(ElementState::PlaceholderShown)
+            }
+            PseudoClassSelector::Default => element.is_in_state(ElementState::Default),
+            PseudoClassSelector::Checked => element.is_in_state(ElementState::Checked),
+            PseudoClassSelector::Indeterminate => element.is_in_state(ElementState::Indeterminate),
+            PseudoClassSelector::Blank => element.is_in_state(ElementState::Blank),
+            PseudoClassSelector::Valid => element.is_in_state(ElementState::Valid),
+            PseudoClassSelector::Invalid => element.is_in_state(ElementState::Invalid),
+            PseudoClassSelector::InRange => element.is_in_state(ElementState::InRange),
+            PseudoClassSelector::OutOfRange => element.is_in_state(ElementState::OutOfRange),
+            PseudoClassSelector::Required => element.is_in_state(ElementState::Required),
+            PseudoClassSelector::Optional => element.is_in_state(ElementState::Optional),
+            PseudoClassSelector::UserInvalid => element.is_in_state(ElementState::UserInvalid),
+            PseudoClassSelector::Empty => !element.has_children(),
+            PseudoClassSelector::NthChild(a_n_plus_b_of) => {
+                let of = a_n_plus_b_of.of();
+
+                if let Some(of) = of {
+                    if !self.matches_selector_list(of, element) {
+                        return false;
+                    }
+                }
+
+                let index = count_siblings(element, E::previous_sibling_element, |sibling| {
+                    of.map(|of| self.matches_selector_list(of, sibling))
+                        .unwrap_or(true)
+                });
+
+                a_n_plus_b_matches(a_n_plus_b_of.a_n_plus_b(), index)
+            }
+            PseudoClassSelector::NthLastChild(a_n_plus_b_of) => {
+                let of = a_n_plus_b_of.of();
+
+                if let Some(of) = of {
+                    if !self.matches_selector_list(of, element) {
+                        return false;
+                    }
+                }
+
+                let index = count_siblings(element, E::next_sibling_element, |sibling| {
+                    of.map(|of| self.matches_selector_list(of, sibling))
+                        .unwrap_or(true)
+                });
+
+                a_n_plus_b_matches(a_n_plus_b_of.a_n_plus_b(), index)
+            }
+            PseudoClassSelector::FirstChild => element.previous_sibling_element().is_none(),
+            PseudoClassSelector::LastChild => element.next_sibling_element().is_none(),
+            PseudoClassSelector::OnlyChild => {
+                element.previous_sibling_element().is_none()
+                    && element.next_sibling_element().is_none()
+            }
+            PseudoClassSelector::NthOfType(a_n_plus_b) => {
+                let index = count_siblings(element, E::previous_sibling_element, |sibling| {
+                    is_same_type(element, sibling)
+                });
+
+                a_n_plus_b_matches(*a_n_plus_b, index)
+            }
+            PseudoClassSelector::NthLastOfType(a_n_plus_b) => {
+                let index = count_siblings(element, E::next_sibling_element, |sibling| {
+                    is_same_type(element, sibling)
+                });
+
+                a_n_plus_b_matches(*a_n_plus_b, index)
+            }
+            PseudoClassSelector::FirstOfType => {
+                count_siblings(element, E::previous_sibling_element, |sibling| {
+                    is_same_type(element, sibling)
+                }) == 1
+            }
+            PseudoClassSelector::LastOfType => {
+                count_siblings(element, E::next_sibling_element, |sibling| {
+                    is_same_type(element, sibling)
+                }) == 1
+            }
+            PseudoClassSelector::OnlyOfType => {
+                count_siblings(element, E::previous_sibling_element, |sibling| {
+                    is_same_type(element, sibling)
+                }) == 1
+                    && count_siblings(element, E::next_sibling_element, |sibling| {
+                        is_same_type(element, sibling)
+                    }) == 1
+            }
+            PseudoClassSelector::NthCol(_) | PseudoClassSelector::NthLastCol(_) => false,
+        }
+    }
+}
+
+// Returns the 1-based index of the `element` among the siblings in the direction of `next` that
+// satisfy the `filter`.
+fn count_siblings<E, N, F>(element: &E, next: N, filter: F) -> i32
+where
+    N: Fn(&E) -> Option<E>,
+    F: Fn(&E) -> bool,
+{
+    let mut index = 1;
+    let mut current = next(element);
+
+    while let Some(sibling) = current {
+        if filter(&sibling) {
+            index += 1;
+        }
+
+        current = next(&sibling);
+    }
+
+    index
+}
+
+fn is_same_type<E>(a: &E, b: &E) -> bool
+where
+    E: SelectorElement,
+{
+    a.local_name() == b.local_name()
+}
+
+fn a_n_plus_b_matches(a_n_plus_b: ANPlusB, index: i32) -> bool {
+    let (a, b) = match a_n_plus_b {
+        ANPlusB::Even => (2, 0),
+        ANPlusB::Odd => (2, 1),
+        ANPlusB::ANPlusB(a, b) => (a, b),
+    };
+
+    if a == 0 {
+        index == b
+    } else {
+        let diff = index - b;
+
+        diff % a == 0 && diff / a >= 0
+    }
+}
+
+fn is_rtl<E>(element: &E) -> bool
+where
+    E: SelectorElement,
+{
+    let mut current = Some(element.clone());
+
+    while let Some(element) = current {
+        if let Some(dir) = element.attribute("dir") {
+            if case_insensitive_eq(&dir, "rtl") {
+                return true;
+            } else if case_insensitive_eq(&dir, "ltr") {
+                return false;
+            }
+        }
+
+        current = element.parent_element();
+    }
+
+    false
+}
+
+// Matches a language tag against a language range as per `:lang()`: the range matches the tag
+// exactly, or matches a prefix of the tag that is followed by a `-`.
+fn lang_matches(tag: &str, range: &str) -> bool {
+    if range.is_empty() {
+        return tag.is_empty();
+    }
+
+    if tag.len() > range.len() && tag.is_char_boundary(range.len()) {
+        case_insensitive_eq(&tag[..range.len()], range) && tag[range.len()..].starts_with('-')
+    } else {
+        case_insensitive_eq(tag, range)
+    }
+}
+
+fn element_lang<E>(element: &E) -> Option<String>
+where
+    E: SelectorElement,
+{
+    let mut current = Some(element.clone());
+
+    while let Some(element) = current {
+        if let Some(lang) = element.attribute("lang") {
+            return Some(lang.into_owned());
+        }
+
+        current = element.parent_element();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestNode {
+        name: &'static str,
+        attributes: Vec<(&'static str, &'static str)>,
+        text: bool,
+        parent: Option<usize>,
+        children: Vec<usize>,
+        states: Vec<ElementState>,
+    }
+
+    #[derive(Default)]
+    struct TestTree {
+        nodes: Vec<TestNode>,
+    }
+
+    impl TestTree {
+        fn add(
+            &mut self,
+            parent: Option<usize>,
+            name: &'static str,
+            attributes: &[(&'static str, &'static str)],
+        ) -> usize {
+            let index = self.nodes.len();
+
+            self.nodes.push(TestNode {
+                name,
+                attributes: attributes.to_vec(),
+                text: false,
+                parent,
+                children: Vec::new(),
+                states: Vec::new(),
+            });
+
+            if let Some(parent) = parent {
+                self.nodes[parent].children.push(index);
+            }
+
+            index
+        }
+
+        fn element(&self, index: usize) -> TestElement<'_> {
+            TestElement { tree: self, index }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct TestElement<'a> {
+        tree: &'a TestTree,
+        index: usize,
+    }
+
+    impl TestElement<'_> {
+        fn node(&self) -> &TestNode {
+            &self.tree.nodes[self.index]
+        }
+
+        fn sibling(&self, offset: isize) -> Option<Self> {
+            let parent = self.node().parent?;
+            let siblings = &self.tree.nodes[parent].children;
+            let position = siblings.iter().position(|i| *i == self.index).unwrap();
+            let index = *siblings.get((position as isize + offset) as usize)?;
+
+            Some(self.tree.element(index))
+        }
+    }
+
+    impl SelectorElement for TestElement<'_> {
+        fn parent_element(&self) -> Option<Self> {
+            self.node().parent.map(|index| self.tree.element(index))
+        }
+
+        fn previous_sibling_element(&self) -> Option<Self> {
+            self.sibling(-1)
+        }
+
+        fn next_sibling_element(&self) -> Option<Self> {
+            self.sibling(1)
+        }
+
+        fn first_child_element(&self) -> Option<Self> {
+            self.node()
+                .children
+                .first()
+                .map(|index| self.tree.element(*index))
+        }
+
+        fn has_children(&self) -> bool {
+            self.node().text || !self.node().children.is_empty()
+        }
+
+        fn local_name(&self) -> Cow<'_, str> {
+            Cow::Borrowed(self.node().name)
+        }
+
+        fn id(&self) -> Option<Cow<'_, str>> {
+            self.attribute("id")
+        }
+
+        fn has_class(&self, class: &str) -> bool {
+            self.attribute("class")
+                .map(|classes| classes.split(' ').any(|c| c == class))
+                .unwrap_or(false)
+        }
+
+        fn attribute(&self, name: &str) -> Option<Cow<'_, str>> {
+            self.node()
+                .attributes
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| Cow::Borrowed(*value))
+        }
+
+        fn is_in_state(&self, state: ElementState) -> bool {
+            self.node().states.contains(&state)
+        }
+    }
+
+    fn matches_str(selector: &str, element: TestElement) -> bool {
+        matches(&Selector::parse(selector).unwrap(), &element)
+    }
+
+    // <html lang="en">
+    //   <body>
+    //     <ul id="list" class="items large">
+    //       <li class="item first" data-x="a b">
+    //       <li class="item" data-lang="en-US">
+    //       <p>
+    //       <li class="item last" dir="rtl">
+    //         <span>
+    //   <div>
+    fn tree() -> TestTree {
+        let mut tree = TestTree::default();
+
+        let html = tree.add(None, "html", &[("lang", "en")]);
+        let body = tree.add(Some(html), "body", &[]);
+        let ul = tree.add(
+            Some(body),
+            "ul",
+            &[("id", "list"), ("class", "items large")],
+        );
+
+        tree.add(
+            Some(ul),
+            "li",
+            &[("class", "item first"), ("data-x", "a b")],
+        );
+        tree.add(Some(ul), "li", &[("class", "item"), ("data-lang", "en-US")]);
+        tree.add(Some(ul), "p", &[]);
+
+        let last = tree.add(Some(ul), "li", &[("class", "item last"), ("dir", "rtl")]);
+
+        tree.add(Some(last), "span", &[]);
+        tree.add(Some(html), "div", &[]);
+
+        tree
+    }
+
+    const HTML: usize = 0;
+    const BODY: usize = 1;
+    const UL: usize = 2;
+    const LI_1: usize = 3;
+    const LI_2: usize = 4;
+    const P: usize = 5;
+    const LI_3: usize = 6;
+    const SPAN: usize = 7;
+    const DIV: usize = 8;
+
+    #[test]
+    fn match_type() {
+        let tree = tree();
+
+        assert!(matches_str("li", tree.element(LI_1)));
+        assert!(matches_str("LI", tree.element(LI_1)));
+        assert!(matches_str("*", tree.element(LI_1)));
+        assert!(!matches_str("ul", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_id_and_class() {
+        let tree = tree();
+
+        assert!(matches_str("#list", tree.element(UL)));
+        assert!(matches_str("ul#list.items.large", tree.element(UL)));
+        assert!(!matches_str("#list.small", tree.element(UL)));
+        assert!(!matches_str("#other", tree.element(UL)));
+        assert!(!matches_str(".item", tree.element(UL)));
+    }
+
+    #[test]
+    fn match_escaped_identifiers() {
+        let mut tree = TestTree::default();
+
+        let div = tree.add(None, "div", &[("id", "x:y"), ("class", "a.b")]);
+
+        assert!(matches_str(".a\\.b", tree.element(div)));
+        assert!(matches_str("#x\\:y", tree.element(div)));
+        assert!(matches_str("#\\78 \\:y", tree.element(div)));
+        assert!(matches_str("[cl\\61ss='a\\.b']", tree.element(div)));
+        assert!(!matches_str(".a", tree.element(div)));
+    }
+
+    #[test]
+    fn match_pseudo_element() {
+        let tree = tree();
+
+        assert!(!matches_str("li::marker", tree.element(LI_1)));
+        assert!(matches_str("li::marker, li", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_selector_list() {
+        let tree = tree();
+
+        assert!(matches_str("div, li", tree.element(LI_1)));
+        assert!(!matches_str("div, p", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_attributes() {
+        let tree = tree();
+
+        assert!(matches_str("[data-x]", tree.element(LI_1)));
+        assert!(matches_str("[DATA-X]", tree.element(LI_1)));
+        assert!(matches_str("[data-x='a b']", tree.element(LI_1)));
+        assert!(matches_str("[data-x~=b]", tree.element(LI_1)));
+        assert!(!matches_str("[data-x~='a b']", tree.element(LI_1)));
+        assert!(matches_str("[data-x^=a]", tree.element(LI_1)));
+        assert!(matches_str("[data-x$=b]", tree.element(LI_1)));
+        assert!(matches_str("[data-x*=' ']", tree.element(LI_1)));
+        assert!(!matches_str("[data-x*='']", tree.element(LI_1)));
+        assert!(matches_str("[data-lang|=en]", tree.element(LI_2)));
+        assert!(!matches_str("[data-lang|=e]", tree.element(LI_2)));
+        assert!(matches_str("[data-lang='EN-us' i]", tree.element(LI_2)));
+        assert!(!matches_str("[data-lang='EN-us']", tree.element(LI_2)));
+        assert!(matches_str("[data-lang=\"\\65 n-US\"]", tree.element(LI_2)));
+        assert!(!matches_str("[data-y]", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_descendant_combinator() {
+        let tree = tree();
+
+        assert!(matches_str("html li", tree.element(LI_1)));
+        assert!(matches_str("body ul li span", tree.element(SPAN)));
+        assert!(!matches_str("div li", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_child_combinator() {
+        let tree = tree();
+
+        assert!(matches_str("ul > li", tree.element(LI_1)));
+        assert!(!matches_str("body > li", tree.element(LI_1)));
+        assert!(matches_str("body > ul li > span", tree.element(SPAN)));
+    }
+
+    #[test]
+    fn match_sibling_combinators() {
+        let tree = tree();
+
+        assert!(matches_str("li + li", tree.element(LI_2)));
+        assert!(!matches_str("li + li", tree.element(LI_3)));
+        assert!(matches_str("p + li", tree.element(LI_3)));
+        assert!(matches_str(".first ~ li", tree.element(LI_3)));
+        assert!(!matches_str(".first ~ li", tree.element(LI_1)));
+        assert!(!matches_str("ul ~ div", tree.element(DIV)));
+        assert!(matches_str("body ~ div", tree.element(DIV)));
+    }
+
+    #[test]
+    fn match_logical_pseudo_classes() {
+        let tree = tree();
+
+        assert!(matches_str(":is(p, li)", tree.element(LI_1)));
+        assert!(matches_str(":where(p, li)", tree.element(LI_1)));
+        assert!(!matches_str(":is(p, div)", tree.element(LI_1)));
+        assert!(matches_str("li:not(.first)", tree.element(LI_2)));
+        assert!(!matches_str("li:not(.first)", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_has() {
+        let tree = tree();
+
+        assert!(matches_str("ul:has(span)", tree.element(UL)));
+        assert!(matches_str("ul:has(> p)", tree.element(UL)));
+        assert!(!matches_str("body:has(> li)", tree.element(BODY)));
+        assert!(matches_str("body:has(li > span)", tree.element(BODY)));
+        assert!(matches_str("li:has(+ p)", tree.element(LI_2)));
+        assert!(!matches_str("li:has(+ p)", tree.element(LI_1)));
+        assert!(matches_str("li:has(~ p)", tree.element(LI_1)));
+        assert!(matches_str("body:has(~ div)", tree.element(BODY)));
+    }
+
+    #[test]
+    fn match_structural_pseudo_classes() {
+        let tree = tree();
+
+        assert!(matches_str(":root", tree.element(HTML)));
+        assert!(!matches_str(":root", tree.element(BODY)));
+        assert!(matches_str(":empty", tree.element(SPAN)));
+        assert!(!matches_str(":empty", tree.element(LI_3)));
+        assert!(matches_str(":first-child", tree.element(LI_1)));
+        assert!(matches_str(":last-child", tree.element(LI_3)));
+        assert!(matches_str(":only-child", tree.element(SPAN)));
+        assert!(!matches_str(":only-child", tree.element(LI_1)));
+        assert!(matches_str("li:first-of-type", tree.element(LI_1)));
+        assert!(matches_str("li:last-of-type", tree.element(LI_3)));
+        assert!(matches_str("p:only-of-type", tree.element(P)));
+        assert!(!matches_str("li:only-of-type", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_nth_child() {
+        let tree = tree();
+
+        assert!(matches_str(":nth-child(1)", tree.element(LI_1)));
+        assert!(matches_str(":nth-child(odd)", tree.element(P)));
+        assert!(matches_str(":nth-child(even)", tree.element(LI_3)));
+        assert!(matches_str(":nth-child(2n+2)", tree.element(LI_2)));
+        assert!(!matches_str(":nth-child(2n+2)", tree.element(LI_1)));
+        assert!(matches_str(":nth-child(-n+2)", tree.element(LI_2)));
+        assert!(!matches_str(":nth-child(-n+2)", tree.element(P)));
+        assert!(matches_str(":nth-last-child(1)", tree.element(LI_3)));
+        assert!(matches_str(":nth-child(3 of li)", tree.element(LI_3)));
+        assert!(!matches_str(":nth-child(3 of li)", tree.element(P)));
+        assert!(matches_str(
+            ":nth-last-child(2 of .item)",
+            tree.element(LI_2)
+        ));
+    }
+
+    #[test]
+    fn match_nth_of_type() {
+        let tree = tree();
+
+        assert!(matches_str(":nth-of-type(3)", tree.element(LI_3)));
+        assert!(matches_str(":nth-of-type(1)", tree.element(P)));
+        assert!(matches_str(":nth-last-of-type(3)", tree.element(LI_1)));
+    }
+
+    #[test]
+    fn match_dir_and_lang() {
+        let tree = tree();
+
+        assert!(matches_str(":dir(ltr)", tree.element(LI_1)));
+        assert!(matches_str(":dir(rtl)", tree.element(LI_3)));
+        assert!(matches_str(":dir(rtl)", tree.element(SPAN)));
+        assert!(matches_str(":lang(en)", tree.element(SPAN)));
+        assert!(matches_str(":lang('EN')", tree.element(SPAN)));
+        assert!(!matches_str(":lang(e)", tree.element(SPAN)));
+        assert!(!matches_str(":lang(fr)", tree.element(SPAN)));
+    }
+
+    #[test]
+    fn match_state_pseudo_classes() {
+        let mut tree = tree();
+
+        tree.nodes[LI_2].states.push(ElementState::Hover);
+
+        assert!(matches_str("li:hover", tree.element(LI_2)));
+        assert!(!matches_str("li:hover", tree.element(LI_1)));
+        assert!(matches_str("ul:has(:hover)", tree.element(UL)));
+        assert!(!matches_str(":checked", tree.element(LI_2)));
+    }
+
+    #[test]
+    fn match_column_never() {
+        let tree = tree();
+
+        assert!(!matches_str(":nth-col(1)", tree.element(LI_1)));
+    }
+}
@@ -2,6 +2,9 @@ use std::error::Error;
 use std::fmt;
 use std::ops::Deref;
 
+mod matching;
+pub use self::matching::*;
+
 mod parse;
 use self::parse::*;
 
@@ -26,6 +29,9 @@ use self::parse_identifier::*;
 mod parse_identifier_or_string;
 use self::parse_identifier_or_string::*;
 
+mod parse_pseudo_element_selector;
+use self::parse_pseudo_element_selector::*;
+
 mod parse_pseudo_selector;
 use self::parse_pseudo_selector::*;
 
@@ -41,6 +47,12 @@ use self::parse_selector_list::*;
 mod parse_string;
 use self::parse_string::*;
 
+mod serialize;
+pub use self::serialize::*;
+
+mod specificity;
+pub use self::specificity::*;
+
 mod util;
 use self::util::*;
 
@@ -103,3 +115,64 @@ impl AsRef<str> for Selector {
         &self.raw
     }
 }
+
+pub struct InvalidPseudoElement {
+    pseudo_element_string: String,
+    error: ParseError,
+}
+
+impl fmt::Display for InvalidPseudoElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid pseudo-element: {} (at offset {})",
+            self.pseudo_element_string, self.error.message, self.error.offset
+        )
+    }
+}
+
+impl fmt::Debug for InvalidPseudoElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for InvalidPseudoElement {}
+
+/// A standalone pseudo-element, e.g. `::before` or `::part(label)`.
+///
+/// Must start with `::`, except for the pseudo-elements that may also be written with a single
+/// colon (`:before`, `:after`, `:first-line` and `:first-letter`).
+#[derive(Clone)]
+pub struct PseudoElement {
+    raw: String,
+    parsed: PseudoElementSelector,
+}
+
+impl PseudoElement {
+    pub fn parse(pseudo_element: &str) -> Result<PseudoElement, InvalidPseudoElement> {
+        parse_pseudo_element(pseudo_element)
+            .map(|parsed| PseudoElement {
+                raw: pseudo_element.to_string(),
+                parsed,
+            })
+            .map_err(|error| InvalidPseudoElement {
+                pseudo_element_string: pseudo_element.to_string(),
+                error,
+            })
+    }
+}
+
+impl Deref for PseudoElement {
+    type Target = PseudoElementSelector;
+
+    fn deref(&self) -> &Self::Target {
+        &self.parsed
+    }
+}
+
+impl AsRef<str> for PseudoElement {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
@@ -1,7 +1,10 @@
 use std::fmt;
 use std::ops::{Add, Deref, Range};
 
-use super::{parse_selector_list, skip_whitespace, SelectorList};
+use super::{
+    parse_legacy_pseudo_element_selector, parse_pseudo_element_selector, parse_selector_list,
+    skip_whitespace, PseudoElementSelector, SelectorList,
+};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct ParseError {
@@ -24,6 +27,40 @@ pub fn parse(selector_string: &str) -> Result<SelectorList, ParseError> {
     }
 }
 
+pub fn parse_pseudo_element(
+    pseudo_element_string: &str,
+) -> Result<PseudoElementSelector, ParseError> {
+    let remainder: Remainder = pseudo_element_string.into();
+
+    let (pseudo_element, remainder) = if remainder.starts_with("::") {
+        parse_pseudo_element_selector(remainder.skip(2))?
+    } else if remainder.starts_with(':') {
+        parse_legacy_pseudo_element_selector(remainder.skip(1)).ok_or_else(|| ParseError {
+            message: "expected a pseudo-element starting with `::`".to_string(),
+            offset: remainder.offset(),
+        })?
+    } else if let Some(c) = remainder.chars().next() {
+        return Err(ParseError {
+            message: format!("expected a pseudo-element but found `{}`", c),
+            offset: remainder.offset(),
+        });
+    } else {
+        return Err(ParseError {
+            message: "unexpected end; expected a pseudo-element".to_string(),
+            offset: remainder.offset(),
+        });
+    };
+
+    if let Some(c) = remainder.chars().next() {
+        Err(ParseError {
+            message: format!("unexpected character `{}`", c),
+            offset: remainder.offset(),
+        })
+    } else {
+        Ok(pseudo_element)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct Offset(usize);
 
@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
 use super::{
-    case_insensitive_eq, is_whitespace, parse_selector_list, ANPlusB, ANPlusBOf, ParseError,
-    Remainder,
+    case_insensitive_eq, is_whitespace, parse_selector_list_argument, ANPlusB, ANPlusBOf,
+    ParseError, Remainder,
 };
 
 pub fn parse_a_n_plus_b(remainder: Remainder) -> Result<(ANPlusB, Remainder), ParseError> {
@@ -135,7 +135,7 @@ pub fn parse_a_n_plus_b_of(remainder: Remainder) -> Result<(ANPlusBOf, Remainder
         if starts_with_of && has_whitespace {
             let remainder = remainder.skip(2);
 
-            let (selector, remainder) = parse_selector_list(remainder)?;
+            let (selector, remainder) = parse_selector_list_argument(remainder)?;
 
             let an_plus_b_of = ANPlusBOf {
                 a_n_plus_b,
@@ -204,7 +204,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         },
                         tail: vec![]
                     }]
@@ -229,7 +230,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         },
                         tail: vec![]
                     }]
@@ -8,7 +8,7 @@ pub fn parse_complex_selector(
 ) -> Result<(ComplexSelector, Remainder), ParseError> {
     let (head, remainder) = parse_compound_selector(remainder)?;
 
-    let mut tail = Vec::new();
+    let mut tail: Vec<CombinedSelector> = Vec::new();
     let mut remainder = remainder;
 
     loop {
@@ -38,6 +38,20 @@ pub fn parse_complex_selector(
             break;
         };
 
+        let last = tail
+            .last()
+            .map(|combined| &combined.selector)
+            .unwrap_or(&head);
+
+        if last.pseudo_element.is_some() {
+            return Err(ParseError {
+                message: "unexpected combinator; a pseudo-element must be in the last compound \
+                selector of a complex selector"
+                    .to_string(),
+                offset: remainder.offset(),
+            });
+        }
+
         let (selector, r) = parse_compound_selector(r)?;
 
         remainder = r;
@@ -70,7 +84,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![]
             }
@@ -90,7 +105,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![]
             }
@@ -110,7 +126,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![CombinedSelector {
                     combinator: Combinator::Descendant,
@@ -119,7 +136,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -139,7 +157,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![CombinedSelector {
                     combinator: Combinator::Child,
@@ -148,7 +167,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -168,7 +188,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![CombinedSelector {
                     combinator: Combinator::Child,
@@ -177,7 +198,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -197,7 +219,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![CombinedSelector {
                     combinator: Combinator::NextSibling,
@@ -206,7 +229,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -226,7 +250,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![CombinedSelector {
                     combinator: Combinator::SubsequentSibling,
@@ -235,7 +260,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -255,7 +281,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![CombinedSelector {
                     combinator: Combinator::Column,
@@ -264,7 +291,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -284,7 +312,8 @@ mod tests {
                     id_selector: None,
                     class_selectors: vec![],
                     attribute_selectors: vec![],
-                    pseudo_class_selectors: vec![]
+                    pseudo_class_selectors: vec![],
+                    pseudo_element: None
                 },
                 tail: vec![
                     CombinedSelector {
@@ -294,7 +323,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     },
                     CombinedSelector {
@@ -304,7 +334,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     }
                 ]
@@ -322,4 +353,11 @@ mod tests {
     fn invalid_first_char() {
         assert!(parse_complex_selector("> *".into()).is_err())
     }
+
+    #[test]
+    fn pseudo_element_in_last_compound() {
+        assert!(parse_complex_selector("ul li::marker".into()).is_ok());
+        assert!(parse_complex_selector("ul::before li".into()).is_err());
+        assert!(parse_complex_selector("p::before > span".into()).is_err());
+    }
 }
@@ -1,5 +1,6 @@
 use super::{
-    is_identifier_start, parse_attribute_selector, parse_identifier, parse_pseudo_class_selector,
+    parse_attribute_selector, parse_identifier, parse_legacy_pseudo_element_selector,
+    parse_pseudo_class_selector, parse_pseudo_element_selector, starts_identifier,
     CompoundSelector, ParseError, Remainder, TypeSelector,
 };
 
@@ -11,11 +12,25 @@ pub fn parse_compound_selector(
     let mut class_selectors = Vec::new();
     let mut attribute_selectors = Vec::new();
     let mut pseudo_class_selectors = Vec::new();
+    let mut pseudo_element = None;
 
     let mut remainder = remainder.skip_whitespace();
     let mut is_empty = true;
 
     loop {
+        if pseudo_element.is_some() {
+            if starts_simple_selector(&remainder) {
+                return Err(ParseError {
+                    message: "unexpected selector; a pseudo-element must be the last selector in \
+                    a compound selector"
+                        .to_string(),
+                    offset: remainder.offset(),
+                });
+            }
+
+            break;
+        }
+
         match remainder.chars().next() {
             Some('*') => {
                 if !is_empty {
@@ -66,12 +81,23 @@ pub fn parse_compound_selector(
                 remainder = r;
             }
             Some(':') => {
-                let (s, r) = parse_pseudo_class_selector(remainder.skip(1))?;
+                if remainder.starts_with("::") {
+                    let (s, r) = parse_pseudo_element_selector(remainder.skip(2))?;
 
-                pseudo_class_selectors.push(s);
-                remainder = r;
+                    pseudo_element = Some(s);
+                    remainder = r;
+                } else if let Some((s, r)) = parse_legacy_pseudo_element_selector(remainder.skip(1))
+                {
+                    pseudo_element = Some(s);
+                    remainder = r;
+                } else {
+                    let (s, r) = parse_pseudo_class_selector(remainder.skip(1))?;
+
+                    pseudo_class_selectors.push(s);
+                    remainder = r;
+                }
             }
-            Some(c) if is_identifier_start(c) => {
+            Some(_) if starts_identifier(&remainder) => {
                 if !is_empty {
                     return Err(ParseError {
                         message: "unexpected type selector; a type selector must be the first \
@@ -118,18 +144,23 @@ pub fn parse_compound_selector(
             class_selectors,
             attribute_selectors,
             pseudo_class_selectors,
+            pseudo_element,
         };
 
         Ok((compound_selector, remainder))
     }
 }
 
+fn starts_simple_selector(remainder: &str) -> bool {
+    remainder.starts_with(['*', '#', '.', '[', ':']) || starts_identifier(remainder)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::selector::{
         AttributeMatcher, AttributeSelector, AttributeWithValue, CaseSensitivity, ComplexSelector,
-        PseudoClassSelector, SelectorList,
+        PseudoClassSelector, PseudoElementSelector, SelectorList,
     };
 
     #[test]
@@ -143,7 +174,8 @@ mod tests {
                 id_selector: None,
                 class_selectors: vec![],
                 attribute_selectors: vec![],
-                pseudo_class_selectors: vec![]
+                pseudo_class_selectors: vec![],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -160,7 +192,8 @@ mod tests {
                 id_selector: None,
                 class_selectors: vec![],
                 attribute_selectors: vec![],
-                pseudo_class_selectors: vec![]
+                pseudo_class_selectors: vec![],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -177,7 +210,8 @@ mod tests {
                 id_selector: Some(1..3),
                 class_selectors: vec![],
                 attribute_selectors: vec![],
-                pseudo_class_selectors: vec![]
+                pseudo_class_selectors: vec![],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -194,7 +228,8 @@ mod tests {
                 id_selector: None,
                 class_selectors: vec![1..6],
                 attribute_selectors: vec![],
-                pseudo_class_selectors: vec![]
+                pseudo_class_selectors: vec![],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -211,7 +246,8 @@ mod tests {
                 id_selector: None,
                 class_selectors: vec![],
                 attribute_selectors: vec![AttributeSelector::Exists(1..5)],
-                pseudo_class_selectors: vec![]
+                pseudo_class_selectors: vec![],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -228,7 +264,8 @@ mod tests {
                 id_selector: None,
                 class_selectors: vec![],
                 attribute_selectors: vec![],
-                pseudo_class_selectors: vec![PseudoClassSelector::Checked]
+                pseudo_class_selectors: vec![PseudoClassSelector::Checked],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -246,7 +283,8 @@ mod tests {
                 id_selector: Some(5..7),
                 class_selectors: vec![8..13],
                 attribute_selectors: vec![AttributeSelector::Exists(14..18)],
-                pseudo_class_selectors: vec![PseudoClassSelector::Checked]
+                pseudo_class_selectors: vec![PseudoClassSelector::Checked],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -264,7 +302,8 @@ mod tests {
                 id_selector: Some(5..7),
                 class_selectors: vec![8..13, 28..39],
                 attribute_selectors: vec![AttributeSelector::Exists(14..18)],
-                pseudo_class_selectors: vec![PseudoClassSelector::Checked]
+                pseudo_class_selectors: vec![PseudoClassSelector::Checked],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -292,7 +331,8 @@ mod tests {
                         case_sensitivity: CaseSensitivity::Default
                     })
                 ],
-                pseudo_class_selectors: vec![PseudoClassSelector::Checked]
+                pseudo_class_selectors: vec![PseudoClassSelector::Checked],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -319,12 +359,14 @@ mod tests {
                                 id_selector: None,
                                 class_selectors: vec![],
                                 attribute_selectors: vec![],
-                                pseudo_class_selectors: vec![]
+                                pseudo_class_selectors: vec![],
+                                pseudo_element: None
                             },
                             tail: vec![]
                         }]
                     })
-                ]
+                ],
+                pseudo_element: None
             }
         );
         assert_eq!(remainder, " rest");
@@ -358,4 +400,47 @@ mod tests {
             parse_compound_selector("type#id.class[attr]:checked#second_id rest".into()).is_err()
         )
     }
+
+    #[test]
+    fn valid_pseudo_element() {
+        let (selector, remainder) = parse_compound_selector("li.a::marker rest".into()).unwrap();
+
+        assert_eq!(
+            selector,
+            CompoundSelector {
+                type_selector: Some(TypeSelector::Identifier(0..2)),
+                id_selector: None,
+                class_selectors: vec![3..4],
+                attribute_selectors: vec![],
+                pseudo_class_selectors: vec![],
+                pseudo_element: Some(PseudoElementSelector::Marker)
+            }
+        );
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_legacy_pseudo_element() {
+        let (selector, remainder) = parse_compound_selector("p:hover:before rest".into()).unwrap();
+
+        assert_eq!(
+            selector,
+            CompoundSelector {
+                type_selector: Some(TypeSelector::Identifier(0..1)),
+                id_selector: None,
+                class_selectors: vec![],
+                attribute_selectors: vec![],
+                pseudo_class_selectors: vec![PseudoClassSelector::Hover],
+                pseudo_element: Some(PseudoElementSelector::Before)
+            }
+        );
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn selector_after_pseudo_element() {
+        assert!(parse_compound_selector("p::before.a".into()).is_err());
+        assert!(parse_compound_selector("p::before:hover".into()).is_err());
+        assert!(parse_compound_selector("p::before::after".into()).is_err());
+    }
 }
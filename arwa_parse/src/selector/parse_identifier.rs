@@ -1,38 +1,92 @@
 use std::ops::Range;
 
-use super::{is_identifier_continue, is_identifier_start, ParseError, Remainder};
+use super::{is_identifier_continue, is_identifier_start, is_whitespace, ParseError, Remainder};
 
 pub fn parse_identifier(remainder: Remainder) -> Result<(Range<usize>, Remainder), ParseError> {
-    let mut char_indices = remainder.char_indices();
-
-    if let Some((_, c)) = char_indices.next() {
-        if !is_identifier_start(c) {
-            return Err(ParseError {
+    if !starts_identifier(&remainder) {
+        return if let Some(c) = remainder.chars().next() {
+            Err(ParseError {
                 message: format!("expected an identifier but found `{}`", c),
                 offset: remainder.offset(),
-            });
-        }
+            })
+        } else {
+            Err(ParseError {
+                message: "unexpected end; expected an identifier".to_string(),
+                offset: remainder.offset(),
+            })
+        };
+    }
 
-        let mut identifier_len = 1;
+    let mut identifier_len = 0;
 
-        for (pos, c) in char_indices {
-            if is_identifier_continue(c) {
-                identifier_len = pos + 1;
-            } else {
-                break;
-            }
+    while let Some(c) = remainder[identifier_len..].chars().next() {
+        if is_identifier_continue(c) {
+            identifier_len += c.len_utf8();
+        } else if let Some(len) = escape_len(&remainder[identifier_len..]) {
+            identifier_len += len;
+        } else {
+            break;
         }
+    }
+
+    Ok((
+        remainder.offset().range(identifier_len),
+        remainder.skip(identifier_len),
+    ))
+}
+
+/// Whether the string starts with an identifier.
+pub fn starts_identifier(s: &str) -> bool {
+    let starts_name = |s: &str| match s.chars().next() {
+        Some(c) if is_identifier_start(c) => true,
+        Some('\\') => escape_len(s).is_some(),
+        _ => false,
+    };
 
-        Ok((
-            remainder.offset().range(identifier_len),
-            remainder.skip(identifier_len),
-        ))
+    if let Some(rest) = s.strip_prefix('-') {
+        rest.starts_with('-') || starts_name(rest)
     } else {
-        Err(ParseError {
-            message: "unexpected end; expected an identifier".to_string(),
-            offset: remainder.offset(),
-        })
+        starts_name(s)
+    }
+}
+
+/// Returns the length in bytes of the escape sequence at the start of the string, or `None` if the
+/// string does not start with a valid escape sequence.
+pub fn escape_len(s: &str) -> Option<usize> {
+    let mut char_indices = s.char_indices();
+
+    if char_indices.next()?.1 != '\\' {
+        return None;
+    }
+
+    let (pos, c) = char_indices.next()?;
+
+    if c == '\n' || c == '\r' || c == '\u{000C}' {
+        return None;
+    }
+
+    if !c.is_ascii_hexdigit() {
+        return Some(pos + c.len_utf8());
+    }
+
+    let mut len = pos + 1;
+    let mut digits = 1;
+
+    for (pos, c) in char_indices {
+        if digits < 6 && c.is_ascii_hexdigit() {
+            len = pos + 1;
+            digits += 1;
+        } else {
+            // A single whitespace character after a hex escape is part of the escape.
+            if is_whitespace(c) {
+                len = pos + c.len_utf8();
+            }
+
+            break;
+        }
     }
+
+    Some(len)
 }
 
 #[cfg(test)]
@@ -64,4 +118,58 @@ mod tests {
     fn invalid_first_char() {
         assert!(parse_identifier("0".into()).is_err());
     }
+
+    #[test]
+    fn valid_leading_hyphen() {
+        let (selector, remainder) = parse_identifier("-a_1 rest".into()).unwrap();
+
+        assert_eq!(selector, 0..4);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_leading_double_hyphen() {
+        let (selector, remainder) = parse_identifier("--1 rest".into()).unwrap();
+
+        assert_eq!(selector, 0..3);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn invalid_leading_hyphen() {
+        assert!(parse_identifier("-".into()).is_err());
+        assert!(parse_identifier("-1".into()).is_err());
+    }
+
+    #[test]
+    fn valid_escapes() {
+        let (selector, remainder) = parse_identifier("\\31 23\\.a\\ b rest".into()).unwrap();
+
+        assert_eq!(selector, 0..12);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_multibyte() {
+        let (selector, remainder) = parse_identifier("éa rest".into()).unwrap();
+
+        assert_eq!(selector, 0..3);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn invalid_escaped_newline() {
+        assert!(parse_identifier("\\\n".into()).is_err());
+        assert!(parse_identifier("\\".into()).is_err());
+    }
+
+    #[test]
+    fn escape_lengths() {
+        assert_eq!(escape_len("\\."), Some(2));
+        assert_eq!(escape_len("\\31 23"), Some(4));
+        assert_eq!(escape_len("\\1234567"), Some(7));
+        assert_eq!(escape_len("\\é"), Some(3));
+        assert_eq!(escape_len("\\"), None);
+        assert_eq!(escape_len("a"), None);
+    }
 }
@@ -0,0 +1,246 @@
+use std::ops::Range;
+
+use super::{
+    case_insensitive_eq, parse_compound_selector, parse_function_invocation, parse_identifier,
+    starts_identifier, ParseError, PseudoElementSelector, Remainder,
+};
+
+pub fn parse_pseudo_element_selector(
+    input_remainder: Remainder,
+) -> Result<(PseudoElementSelector, Remainder), ParseError> {
+    let (identifier, remainder) = parse_identifier(input_remainder)?;
+    let identifier = &input_remainder[..identifier.len()];
+
+    if case_insensitive_eq(identifier, "before") {
+        Ok((PseudoElementSelector::Before, remainder))
+    } else if case_insensitive_eq(identifier, "after") {
+        Ok((PseudoElementSelector::After, remainder))
+    } else if case_insensitive_eq(identifier, "first-line") {
+        Ok((PseudoElementSelector::FirstLine, remainder))
+    } else if case_insensitive_eq(identifier, "first-letter") {
+        Ok((PseudoElementSelector::FirstLetter, remainder))
+    } else if case_insensitive_eq(identifier, "marker") {
+        Ok((PseudoElementSelector::Marker, remainder))
+    } else if case_insensitive_eq(identifier, "placeholder") {
+        Ok((PseudoElementSelector::Placeholder, remainder))
+    } else if case_insensitive_eq(identifier, "selection") {
+        Ok((PseudoElementSelector::Selection, remainder))
+    } else if case_insensitive_eq(identifier, "backdrop") {
+        Ok((PseudoElementSelector::Backdrop, remainder))
+    } else if case_insensitive_eq(identifier, "part") {
+        let (names, remainder) = parse_function_invocation(remainder, parse_part_names)?;
+
+        Ok((PseudoElementSelector::Part(names), remainder))
+    } else if case_insensitive_eq(identifier, "slotted") {
+        let argument_offset = remainder.offset() + 1;
+        let (selector, remainder) = parse_function_invocation(remainder, parse_compound_selector)?;
+
+        if selector.pseudo_element.is_some() {
+            return Err(ParseError {
+                message: "a pseudo-element is not allowed in the argument to `::slotted()`"
+                    .to_string(),
+                offset: argument_offset,
+            });
+        }
+
+        Ok((
+            PseudoElementSelector::Slotted(Box::new(selector)),
+            remainder,
+        ))
+    } else if case_insensitive_eq(identifier, "highlight") {
+        let (name, remainder) = parse_function_invocation(remainder, parse_identifier)?;
+
+        Ok((PseudoElementSelector::Highlight(name), remainder))
+    } else {
+        Err(ParseError {
+            message: format!("unknown pseudo-element identifier `{}`", identifier),
+            offset: remainder.offset(),
+        })
+    }
+}
+
+/// Parses one of the pseudo-elements that may also be written with a single colon for
+/// compatibility with CSS Level 2 (`:before`, `:after`, `:first-line` and `:first-letter`).
+///
+/// Returns `None` if the remainder does not start with one of these pseudo-elements.
+pub fn parse_legacy_pseudo_element_selector(
+    input_remainder: Remainder,
+) -> Option<(PseudoElementSelector, Remainder)> {
+    let (identifier, remainder) = parse_identifier(input_remainder).ok()?;
+    let identifier = &input_remainder[..identifier.len()];
+
+    if case_insensitive_eq(identifier, "before") {
+        Some((PseudoElementSelector::Before, remainder))
+    } else if case_insensitive_eq(identifier, "after") {
+        Some((PseudoElementSelector::After, remainder))
+    } else if case_insensitive_eq(identifier, "first-line") {
+        Some((PseudoElementSelector::FirstLine, remainder))
+    } else if case_insensitive_eq(identifier, "first-letter") {
+        Some((PseudoElementSelector::FirstLetter, remainder))
+    } else {
+        None
+    }
+}
+
+fn parse_part_names(remainder: Remainder) -> Result<(Vec<Range<usize>>, Remainder), ParseError> {
+    let (name, mut remainder) = parse_identifier(remainder)?;
+    let mut names = vec![name];
+
+    loop {
+        let r = remainder.skip_whitespace();
+
+        if !starts_identifier(&r) {
+            break;
+        }
+
+        let (name, r) = parse_identifier(r)?;
+
+        names.push(name);
+        remainder = r;
+    }
+
+    Ok((names, remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selector::{CompoundSelector, TypeSelector};
+
+    #[test]
+    fn valid_before() {
+        let (selector, remainder) = parse_pseudo_element_selector("before rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::Before);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_upper_case_after() {
+        let (selector, remainder) = parse_pseudo_element_selector("AFTER rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::After);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_marker() {
+        let (selector, remainder) = parse_pseudo_element_selector("marker rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::Marker);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_placeholder() {
+        let (selector, remainder) =
+            parse_pseudo_element_selector("placeholder rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::Placeholder);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_part() {
+        let (selector, remainder) =
+            parse_pseudo_element_selector("part(label) rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::Part(vec![5..10]));
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn valid_part_multiple_names() {
+        let (selector, remainder) =
+            parse_pseudo_element_selector("part( label  active ) rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::Part(vec![6..11, 13..19]));
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn part_without_names() {
+        assert!(parse_pseudo_element_selector("part()".into()).is_err());
+    }
+
+    #[test]
+    fn valid_slotted() {
+        let (selector, remainder) =
+            parse_pseudo_element_selector("slotted(span) rest".into()).unwrap();
+
+        assert_eq!(
+            selector,
+            PseudoElementSelector::Slotted(Box::new(CompoundSelector {
+                type_selector: Some(TypeSelector::Identifier(8..12)),
+                id_selector: None,
+                class_selectors: vec![],
+                attribute_selectors: vec![],
+                pseudo_class_selectors: vec![],
+                pseudo_element: None
+            }))
+        );
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn slotted_complex_selector() {
+        assert!(parse_pseudo_element_selector("slotted(div span)".into()).is_err());
+    }
+
+    #[test]
+    fn slotted_pseudo_element() {
+        assert!(parse_pseudo_element_selector("slotted(span::before)".into()).is_err());
+    }
+
+    #[test]
+    fn valid_highlight() {
+        let (selector, remainder) =
+            parse_pseudo_element_selector("highlight(search) rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::Highlight(10..16));
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn unknown_pseudo_element() {
+        assert!(parse_pseudo_element_selector("hover".into()).is_err());
+    }
+
+    #[test]
+    fn valid_legacy() {
+        let (selector, remainder) =
+            parse_legacy_pseudo_element_selector("first-line rest".into()).unwrap();
+
+        assert_eq!(selector, PseudoElementSelector::FirstLine);
+        assert_eq!(remainder, " rest");
+    }
+
+    #[test]
+    fn legacy_not_allowed() {
+        assert!(parse_legacy_pseudo_element_selector("marker".into()).is_none());
+        assert!(parse_legacy_pseudo_element_selector("hover".into()).is_none());
+    }
+
+    #[test]
+    fn standalone() {
+        use crate::selector::PseudoElement;
+
+        assert_eq!(
+            *PseudoElement::parse("::before").unwrap(),
+            PseudoElementSelector::Before
+        );
+        assert_eq!(
+            *PseudoElement::parse(":after").unwrap(),
+            PseudoElementSelector::After
+        );
+        assert_eq!(
+            *PseudoElement::parse("::part(label)").unwrap(),
+            PseudoElementSelector::Part(vec![7..12])
+        );
+        assert!(PseudoElement::parse("").is_err());
+        assert!(PseudoElement::parse("before").is_err());
+        assert!(PseudoElement::parse(":marker").is_err());
+        assert!(PseudoElement::parse("::before ").is_err());
+        assert!(PseudoElement::parse("p::before").is_err());
+    }
+}
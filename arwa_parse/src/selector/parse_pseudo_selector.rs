@@ -1,7 +1,7 @@
 use super::{
     case_insensitive_eq, parse_a_n_plus_b, parse_a_n_plus_b_of, parse_function_invocation,
     parse_identifier, parse_identifier_or_string, parse_relative_selector_list,
-    parse_selector_list, ParseError, PseudoClassSelector, Remainder,
+    parse_selector_list_argument, ParseError, PseudoClassSelector, Remainder,
 };
 
 pub fn parse_pseudo_class_selector(
@@ -11,15 +11,18 @@ pub fn parse_pseudo_class_selector(
     let identifier = &input_remainder[..identifier.len()];
 
     if case_insensitive_eq(identifier, "is") {
-        let (selector, remainder) = parse_function_invocation(remainder, parse_selector_list)?;
+        let (selector, remainder) =
+            parse_function_invocation(remainder, parse_selector_list_argument)?;
 
         Ok((PseudoClassSelector::Is(selector), remainder))
     } else if case_insensitive_eq(identifier, "not") {
-        let (selector, remainder) = parse_function_invocation(remainder, parse_selector_list)?;
+        let (selector, remainder) =
+            parse_function_invocation(remainder, parse_selector_list_argument)?;
 
         Ok((PseudoClassSelector::Not(selector), remainder))
     } else if case_insensitive_eq(identifier, "where") {
-        let (selector, remainder) = parse_function_invocation(remainder, parse_selector_list)?;
+        let (selector, remainder) =
+            parse_function_invocation(remainder, parse_selector_list_argument)?;
 
         Ok((PseudoClassSelector::Where(selector), remainder))
     } else if case_insensitive_eq(identifier, "has") {
@@ -63,7 +66,8 @@ pub fn parse_pseudo_class_selector(
         Ok((PseudoClassSelector::FocusWithin, remainder))
     } else if case_insensitive_eq(identifier, "current") {
         let (selector, remainder) = if remainder.starts_with('(') {
-            let (selector, remainder) = parse_function_invocation(remainder, parse_selector_list)?;
+            let (selector, remainder) =
+                parse_function_invocation(remainder, parse_selector_list_argument)?;
 
             (Some(selector), remainder)
         } else {
@@ -128,7 +132,7 @@ pub fn parse_pseudo_class_selector(
     } else if case_insensitive_eq(identifier, "last-child") {
         Ok((PseudoClassSelector::LastChild, remainder))
     } else if case_insensitive_eq(identifier, "only-child") {
-        Ok((PseudoClassSelector::LastChild, remainder))
+        Ok((PseudoClassSelector::OnlyChild, remainder))
     } else if case_insensitive_eq(identifier, "nth-of-type") {
         let (a_n_plus_b, remainder) = parse_function_invocation(remainder, parse_a_n_plus_b)?;
 
@@ -201,7 +205,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     },
                     tail: vec![]
                 }]
@@ -238,7 +243,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     },
                     tail: vec![]
                 }]
@@ -275,7 +281,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     },
                     tail: vec![]
                 }]
@@ -314,7 +321,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     }]
                 }]
@@ -338,7 +346,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     }]
                 }]
@@ -459,7 +468,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     },
                     tail: vec![]
                 }]
@@ -509,7 +519,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         },
                         tail: vec![]
                     }]
@@ -565,7 +576,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         },
                         tail: vec![]
                     }]
@@ -722,4 +734,12 @@ mod tests {
     fn empty() {
         assert!(parse_pseudo_class_selector("".into()).is_err())
     }
+
+    #[test]
+    fn pseudo_element_in_argument() {
+        assert!(parse_pseudo_class_selector("is(p::before)".into()).is_err());
+        assert!(parse_pseudo_class_selector("not(a, ::after)".into()).is_err());
+        assert!(parse_pseudo_class_selector("nth-child(2n of ::marker)".into()).is_err());
+        assert!(parse_pseudo_class_selector("has(> ::before)".into()).is_err());
+    }
 }
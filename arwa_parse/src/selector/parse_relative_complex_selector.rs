@@ -38,6 +38,13 @@ pub fn parse_relative_complex_selector(
 
         let (selector, r) = parse_compound_selector(r)?;
 
+        if selector.pseudo_element.is_some() {
+            return Err(ParseError {
+                message: "pseudo-elements are not allowed in a relative selector".to_string(),
+                offset: r.offset(),
+            });
+        }
+
         remainder = r;
 
         parts.push(CombinedSelector {
@@ -84,7 +91,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -106,7 +114,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -128,7 +137,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -150,7 +160,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -172,7 +183,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -194,7 +206,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -216,7 +229,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     }
                 }]
             }
@@ -239,7 +253,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     },
                     CombinedSelector {
@@ -249,7 +264,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     }
                 ]
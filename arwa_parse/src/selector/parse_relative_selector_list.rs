@@ -56,7 +56,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         }
                     }]
                 }]
@@ -81,7 +82,8 @@ mod tests {
                                 id_selector: None,
                                 class_selectors: vec![],
                                 attribute_selectors: vec![],
-                                pseudo_class_selectors: vec![]
+                                pseudo_class_selectors: vec![],
+                                pseudo_element: None
                             }
                         }]
                     },
@@ -93,7 +95,8 @@ mod tests {
                                 id_selector: None,
                                 class_selectors: vec![],
                                 attribute_selectors: vec![],
-                                pseudo_class_selectors: vec![]
+                                pseudo_class_selectors: vec![],
+                                pseudo_element: None
                             }
                         }]
                     }
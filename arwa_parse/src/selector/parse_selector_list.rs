@@ -30,6 +30,34 @@ pub fn parse_selector_list(remainder: Remainder) -> Result<(SelectorList, Remain
     Ok((selector, remainder))
 }
 
+/// Parses a selector list that is the argument to a functional pseudo-class, which may not contain
+/// pseudo-elements.
+pub fn parse_selector_list_argument(
+    remainder: Remainder,
+) -> Result<(SelectorList, Remainder), ParseError> {
+    let offset = remainder.offset();
+    let (selector, remainder) = parse_selector_list(remainder)?;
+
+    let has_pseudo_element = selector.iter().any(|complex_selector| {
+        let last = complex_selector
+            .tail
+            .last()
+            .map(|combined| &combined.selector)
+            .unwrap_or(&complex_selector.head);
+
+        last.pseudo_element.is_some()
+    });
+
+    if has_pseudo_element {
+        return Err(ParseError {
+            message: "pseudo-elements are not allowed in a pseudo-class argument".to_string(),
+            offset,
+        });
+    }
+
+    Ok((selector, remainder))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,7 +76,8 @@ mod tests {
                         id_selector: None,
                         class_selectors: vec![],
                         attribute_selectors: vec![],
-                        pseudo_class_selectors: vec![]
+                        pseudo_class_selectors: vec![],
+                        pseudo_element: None
                     },
                     tail: vec![]
                 }]
@@ -71,7 +100,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         },
                         tail: vec![]
                     },
@@ -81,7 +111,8 @@ mod tests {
                             id_selector: None,
                             class_selectors: vec![],
                             attribute_selectors: vec![],
-                            pseudo_class_selectors: vec![]
+                            pseudo_class_selectors: vec![],
+                            pseudo_element: None
                         },
                         tail: vec![]
                     }
@@ -85,6 +85,7 @@ pub struct CompoundSelector {
     pub(super) class_selectors: Vec<Range<usize>>,
     pub(super) attribute_selectors: Vec<AttributeSelector>,
     pub(super) pseudo_class_selectors: Vec<PseudoClassSelector>,
+    pub(super) pseudo_element: Option<PseudoElementSelector>,
 }
 
 impl CompoundSelector {
@@ -107,6 +108,10 @@ impl CompoundSelector {
     pub fn pseudo_class_selectors(&self) -> &[PseudoClassSelector] {
         &self.pseudo_class_selectors
     }
+
+    pub fn pseudo_element(&self) -> Option<&PseudoElementSelector> {
+        self.pseudo_element.as_ref()
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -221,6 +226,25 @@ pub enum PseudoClassSelector {
     NthLastCol(ANPlusB),
 }
 
+/// A pseudo-element selector.
+///
+/// A pseudo-element may only be used in the last compound selector of a complex selector, and
+/// must be the last simple selector in that compound selector.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PseudoElementSelector {
+    Before,
+    After,
+    FirstLine,
+    FirstLetter,
+    Marker,
+    Placeholder,
+    Selection,
+    Backdrop,
+    Part(Vec<Range<usize>>),
+    Slotted(Box<CompoundSelector>),
+    Highlight(Range<usize>),
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ANPlusB {
     Even,
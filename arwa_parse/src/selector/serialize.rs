@@ -0,0 +1,606 @@
+use std::fmt;
+use std::fmt::Write;
+use std::ops::Range;
+
+use super::{
+    unescape, ANPlusB, ANPlusBOf, AttributeMatcher, AttributeSelector, CaseSensitivity, Combinator,
+    ComplexSelector, CompoundSelector, PseudoClassSelector, PseudoElementSelector,
+    RelativeComplexSelector, Selector, SelectorList, TypeSelector,
+};
+
+/// Writes the `identifier` to `dest`, escaping it where necessary so that it parses back into the
+/// same identifier.
+///
+/// Follows the "serialize an identifier" algorithm from CSSOM.
+pub fn serialize_identifier<W: Write>(identifier: &str, dest: &mut W) -> fmt::Result {
+    let first = identifier.chars().next();
+
+    if identifier == "-" {
+        return dest.write_str("\\-");
+    }
+
+    for (index, c) in identifier.chars().enumerate() {
+        match c {
+            '\0' => dest.write_char('\u{FFFD}')?,
+            '\u{0001}'..='\u{001F}' | '\u{007F}' => write!(dest, "\\{:x} ", c as u32)?,
+            '0'..='9' if index == 0 || (index == 1 && first == Some('-')) => {
+                write!(dest, "\\{:x} ", c as u32)?
+            }
+            c if c >= '\u{0080}' || c == '-' || c == '_' || c.is_ascii_alphanumeric() => {
+                dest.write_char(c)?
+            }
+            c => {
+                dest.write_char('\\')?;
+                dest.write_char(c)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the `string` to `dest` as a double-quoted CSS string, escaping it where necessary so that
+/// it parses back into the same string.
+///
+/// Follows the "serialize a string" algorithm from CSSOM.
+pub fn serialize_string<W: Write>(string: &str, dest: &mut W) -> fmt::Result {
+    dest.write_char('"')?;
+
+    for c in string.chars() {
+        match c {
+            '\0' => dest.write_char('\u{FFFD}')?,
+            '\u{0001}'..='\u{001F}' | '\u{007F}' => write!(dest, "\\{:x} ", c as u32)?,
+            '"' | '\\' => {
+                dest.write_char('\\')?;
+                dest.write_char(c)?;
+            }
+            c => dest.write_char(c)?,
+        }
+    }
+
+    dest.write_char('"')
+}
+
+/// A selector AST node along with the source string its ranges index into, see
+/// [SelectorList::serialize].
+pub struct Serialized<'a, T> {
+    node: &'a T,
+    source: &'a str,
+}
+
+impl SelectorList {
+    /// Returns a value that displays this selector list as canonical CSS.
+    ///
+    /// The `source` must be the string this selector list was parsed from. The canonical form
+    /// separates selectors with `, `, surrounds combinators other than the descendant combinator
+    /// with a single space, omits redundant universal selectors, lower-cases pseudo-class names and
+    /// writes attribute values as double-quoted strings. Parsing the canonical form again results
+    /// in an equivalent selector list.
+    pub fn serialize<'a>(&'a self, source: &'a str) -> Serialized<'a, SelectorList> {
+        Serialized { node: self, source }
+    }
+}
+
+impl ComplexSelector {
+    /// Returns a value that displays this complex selector as canonical CSS.
+    ///
+    /// See [SelectorList::serialize] for details.
+    pub fn serialize<'a>(&'a self, source: &'a str) -> Serialized<'a, ComplexSelector> {
+        Serialized { node: self, source }
+    }
+}
+
+impl fmt::Display for Serialized<'_, SelectorList> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_selector_list(self.source, self.node, f)
+    }
+}
+
+impl fmt::Display for Serialized<'_, ComplexSelector> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_complex_selector(self.source, self.node, f)
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_selector_list(&self.raw, &self.parsed, f)
+    }
+}
+
+fn write_selector_list<W: Write>(source: &str, list: &SelectorList, dest: &mut W) -> fmt::Result {
+    for (index, selector) in list.iter().enumerate() {
+        if index > 0 {
+            dest.write_str(", ")?;
+        }
+
+        write_complex_selector(source, selector, dest)?;
+    }
+
+    Ok(())
+}
+
+fn write_complex_selector<W: Write>(
+    source: &str,
+    selector: &ComplexSelector,
+    dest: &mut W,
+) -> fmt::Result {
+    write_compound_selector(source, &selector.head, dest)?;
+
+    for combined in &selector.tail {
+        dest.write_str(combinator_str(combined.combinator))?;
+        write_compound_selector(source, &combined.selector, dest)?;
+    }
+
+    Ok(())
+}
+
+fn write_relative_complex_selector<W: Write>(
+    source: &str,
+    selector: &RelativeComplexSelector,
+    dest: &mut W,
+) -> fmt::Result {
+    for (index, combined) in selector.parts.iter().enumerate() {
+        let combinator = combinator_str(combined.combinator);
+
+        if index == 0 {
+            // A leading descendant combinator is implied; other leading combinators are not
+            // preceded by a compound selector.
+            dest.write_str(combinator.trim_start())?;
+        } else {
+            dest.write_str(combinator)?;
+        }
+
+        write_compound_selector(source, &combined.selector, dest)?;
+    }
+
+    Ok(())
+}
+
+fn combinator_str(combinator: Combinator) -> &'static str {
+    match combinator {
+        Combinator::Descendant => " ",
+        Combinator::Child => " > ",
+        Combinator::NextSibling => " + ",
+        Combinator::SubsequentSibling => " ~ ",
+        Combinator::Column => " || ",
+    }
+}
+
+fn write_compound_selector<W: Write>(
+    source: &str,
+    selector: &CompoundSelector,
+    dest: &mut W,
+) -> fmt::Result {
+    let has_subclass_selectors = selector.id_selector.is_some()
+        || !selector.class_selectors.is_empty()
+        || !selector.attribute_selectors.is_empty()
+        || !selector.pseudo_class_selectors.is_empty()
+        || selector.pseudo_element.is_some();
+
+    match &selector.type_selector {
+        Some(TypeSelector::Identifier(name)) => write_identifier(source, name.clone(), dest)?,
+        // The universal selector is implied if there are other simple selectors.
+        Some(TypeSelector::Universal) | None if !has_subclass_selectors => dest.write_char('*')?,
+        _ => (),
+    }
+
+    if let Some(id) = &selector.id_selector {
+        dest.write_char('#')?;
+        write_identifier(source, id.clone(), dest)?;
+    }
+
+    for class in &selector.class_selectors {
+        dest.write_char('.')?;
+        write_identifier(source, class.clone(), dest)?;
+    }
+
+    for attribute in &selector.attribute_selectors {
+        write_attribute_selector(source, attribute, dest)?;
+    }
+
+    for pseudo_class in &selector.pseudo_class_selectors {
+        write_pseudo_class_selector(source, pseudo_class, dest)?;
+    }
+
+    if let Some(pseudo_element) = &selector.pseudo_element {
+        write_pseudo_element_selector(source, pseudo_element, dest)?;
+    }
+
+    Ok(())
+}
+
+fn write_attribute_selector<W: Write>(
+    source: &str,
+    selector: &AttributeSelector,
+    dest: &mut W,
+) -> fmt::Result {
+    dest.write_char('[')?;
+
+    match selector {
+        AttributeSelector::Exists(name) => write_identifier(source, name.clone(), dest)?,
+        AttributeSelector::WithValue(attribute) => {
+            write_identifier(source, attribute.name.clone(), dest)?;
+
+            dest.write_str(match attribute.matcher {
+                AttributeMatcher::Exact => "=",
+                AttributeMatcher::Includes => "~=",
+                AttributeMatcher::BeginsWith => "^=",
+                AttributeMatcher::EndsWith => "$=",
+                AttributeMatcher::Substring => "*=",
+                AttributeMatcher::HyphenatedBeginsWidth => "|=",
+            })?;
+
+            serialize_string(&unescape(&source[attribute.value.clone()]), dest)?;
+
+            match attribute.case_sensitivity {
+                CaseSensitivity::Sensitive => dest.write_str(" s")?,
+                CaseSensitivity::Insensitive => dest.write_str(" i")?,
+                CaseSensitivity::Default => (),
+            }
+        }
+    }
+
+    dest.write_char(']')
+}
+
+fn write_pseudo_class_selector<W: Write>(
+    source: &str,
+    selector: &PseudoClassSelector,
+    dest: &mut W,
+) -> fmt::Result {
+    let name = match selector {
+        PseudoClassSelector::Is(list) => return write_function(source, "is", list, dest),
+        PseudoClassSelector::Not(list) => return write_function(source, "not", list, dest),
+        PseudoClassSelector::Where(list) => return write_function(source, "where", list, dest),
+        PseudoClassSelector::Has(list) => {
+            dest.write_str(":has(")?;
+
+            for (index, selector) in list.iter().enumerate() {
+                if index > 0 {
+                    dest.write_str(", ")?;
+                }
+
+                write_relative_complex_selector(source, selector, dest)?;
+            }
+
+            return dest.write_char(')');
+        }
+        PseudoClassSelector::Dir(dir) => {
+            dest.write_str(":dir(")?;
+            write_identifier(source, dir.clone(), dest)?;
+
+            return dest.write_char(')');
+        }
+        PseudoClassSelector::Lang(lang) => {
+            let lang = unescape(&source[lang.clone()]);
+
+            dest.write_str(":lang(")?;
+
+            // Prefer the identifier form if the language tag is a valid identifier that needs no
+            // escaping, which is the case for all well-formed language tags.
+            let mut identifier = String::new();
+
+            serialize_identifier(&lang, &mut identifier)?;
+
+            if !lang.is_empty() && identifier == lang {
+                dest.write_str(&identifier)?;
+            } else {
+                serialize_string(&lang, dest)?;
+            }
+
+            return dest.write_char(')');
+        }
+        PseudoClassSelector::Current(Some(list)) => {
+            return write_function(source, "current", list, dest)
+        }
+        PseudoClassSelector::NthChild(a_n_plus_b_of) => {
+            return write_a_n_plus_b_of(source, "nth-child", a_n_plus_b_of, dest)
+        }
+        PseudoClassSelector::NthLastChild(a_n_plus_b_of) => {
+            return write_a_n_plus_b_of(source, "nth-last-child", a_n_plus_b_of, dest)
+        }
+        PseudoClassSelector::NthOfType(a_n_plus_b) => {
+            return write_a_n_plus_b(":nth-of-type(", *a_n_plus_b, dest)
+        }
+        PseudoClassSelector::NthLastOfType(a_n_plus_b) => {
+            return write_a_n_plus_b(":nth-last-of-type(", *a_n_plus_b, dest)
+        }
+        PseudoClassSelector::NthCol(a_n_plus_b) => {
+            return write_a_n_plus_b(":nth-col(", *a_n_plus_b, dest)
+        }
+        PseudoClassSelector::NthLastCol(a_n_plus_b) => {
+            return write_a_n_plus_b(":nth-last-col(", *a_n_plus_b, dest)
+        }
+        PseudoClassSelector::Current(None) => "current",
+        PseudoClassSelector::AnyLink => "any-link",
+        PseudoClassSelector::Link => "link",
+        PseudoClassSelector::Visited => "visited",
+        PseudoClassSelector::LocalLink => "local-link",
+        PseudoClassSelector::Target => "target",
+        PseudoClassSelector::TargetWithin => "target-within",
+        PseudoClassSelector::Scope => "scope",
+        PseudoClassSelector::Hover => "hover",
+        PseudoClassSelector::Active => "active",
+        PseudoClassSelector::Focus => "focus",
+        PseudoClassSelector::FocusVisible => "focus-visible",
+        PseudoClassSelector::FocusWithin => "focus-within",
+        PseudoClassSelector::Past => "past",
+        PseudoClassSelector::Future => "future",
+        PseudoClassSelector::Playing => "playing",
+        PseudoClassSelector::Paused => "paused",
+        PseudoClassSelector::Enabled => "enabled",
+        PseudoClassSelector::Disabled => "disabled",
+        PseudoClassSelector::ReadOnly => "read-only",
+        PseudoClassSelector::ReadWrite => "read-write",
+        PseudoClassSelector::PlaceholderShown => "placeholder-shown",
+        PseudoClassSelector::Default => "default",
+        PseudoClassSelector::Checked => "checked",
+        PseudoClassSelector::Indeterminate => "indeterminate",
+        PseudoClassSelector::Blank => "blank",
+        PseudoClassSelector::Valid => "valid",
+        PseudoClassSelector::Invalid => "invalid",
+        PseudoClassSelector::InRange => "in-range",
+        PseudoClassSelector::OutOfRange => "out-of-range",
+        PseudoClassSelector::Required => "required",
+        PseudoClassSelector::Optional => "optional",
+        PseudoClassSelector::UserInvalid => "user-invalid",
+        PseudoClassSelector::Root => "root",
+        PseudoClassSelector::Empty => "empty",
+        PseudoClassSelector::FirstChild => "first-child",
+        PseudoClassSelector::LastChild => "last-child",
+        PseudoClassSelector::OnlyChild => "only-child",
+        PseudoClassSelector::FirstOfType => "first-of-type",
+        PseudoClassSelector::LastOfType => "last-of-type",
+        PseudoClassSelector::OnlyOfType => "only-of-type",
+    };
+
+    dest.write_char(':')?;
+    dest.write_str(name)
+}
+
+fn write_pseudo_element_selector<W: Write>(
+    source: &str,
+    selector: &PseudoElementSelector,
+    dest: &mut W,
+) -> fmt::Result {
+    let name = match selector {
+        PseudoElementSelector::Part(names) => {
+            dest.write_str("::part(")?;
+
+            for (index, name) in names.iter().enumerate() {
+                if index > 0 {
+                    dest.write_char(' ')?;
+                }
+
+                write_identifier(source, name.clone(), dest)?;
+            }
+
+            return dest.write_char(')');
+        }
+        PseudoElementSelector::Slotted(selector) => {
+            dest.write_str("::slotted(")?;
+            write_compound_selector(source, selector, dest)?;
+
+            return dest.write_char(')');
+        }
+        PseudoElementSelector::Highlight(name) => {
+            dest.write_str("::highlight(")?;
+            write_identifier(source, name.clone(), dest)?;
+
+            return dest.write_char(')');
+        }
+        PseudoElementSelector::Before => "before",
+        PseudoElementSelector::After => "after",
+        PseudoElementSelector::FirstLine => "first-line",
+        PseudoElementSelector::FirstLetter => "first-letter",
+        PseudoElementSelector::Marker => "marker",
+        PseudoElementSelector::Placeholder => "placeholder",
+        PseudoElementSelector::Selection => "selection",
+        PseudoElementSelector::Backdrop => "backdrop",
+    };
+
+    dest.write_str("::")?;
+    dest.write_str(name)
+}
+
+fn write_function<W: Write>(
+    source: &str,
+    name: &str,
+    list: &SelectorList,
+    dest: &mut W,
+) -> fmt::Result {
+    dest.write_char(':')?;
+    dest.write_str(name)?;
+    dest.write_char('(')?;
+    write_selector_list(source, list, dest)?;
+    dest.write_char(')')
+}
+
+fn write_a_n_plus_b_of<W: Write>(
+    source: &str,
+    name: &str,
+    a_n_plus_b_of: &ANPlusBOf,
+    dest: &mut W,
+) -> fmt::Result {
+    dest.write_char(':')?;
+    dest.write_str(name)?;
+    dest.write_char('(')?;
+    write_a_n_plus_b_value(a_n_plus_b_of.a_n_plus_b, dest)?;
+
+    if let Some(of) = &a_n_plus_b_of.of {
+        dest.write_str(" of ")?;
+        write_selector_list(source, of, dest)?;
+    }
+
+    dest.write_char(')')
+}
+
+fn write_a_n_plus_b<W: Write>(prefix: &str, a_n_plus_b: ANPlusB, dest: &mut W) -> fmt::Result {
+    dest.write_str(prefix)?;
+    write_a_n_plus_b_value(a_n_plus_b, dest)?;
+    dest.write_char(')')
+}
+
+fn write_a_n_plus_b_value<W: Write>(a_n_plus_b: ANPlusB, dest: &mut W) -> fmt::Result {
+    match a_n_plus_b {
+        ANPlusB::Even => dest.write_str("even"),
+        ANPlusB::Odd => dest.write_str("odd"),
+        ANPlusB::ANPlusB(0, b) => write!(dest, "{}", b),
+        ANPlusB::ANPlusB(a, b) => {
+            match a {
+                1 => dest.write_char('n')?,
+                -1 => dest.write_str("-n")?,
+                a => write!(dest, "{}n", a)?,
+            }
+
+            match b {
+                0 => Ok(()),
+                b => write!(dest, "{:+}", b),
+            }
+        }
+    }
+}
+
+fn write_identifier<W: Write>(source: &str, range: Range<usize>, dest: &mut W) -> fmt::Result {
+    serialize_identifier(&unescape(&source[range]), dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(identifier: &str) -> String {
+        let mut result = String::new();
+
+        serialize_identifier(identifier, &mut result).unwrap();
+
+        result
+    }
+
+    fn string(string: &str) -> String {
+        let mut result = String::new();
+
+        serialize_string(string, &mut result).unwrap();
+
+        result
+    }
+
+    fn canonical(selector: &str) -> String {
+        let canonical = Selector::parse(selector).unwrap().to_string();
+
+        // The canonical form must parse, and must serialize to itself.
+        assert_eq!(Selector::parse(&canonical).unwrap().to_string(), canonical);
+
+        canonical
+    }
+
+    #[test]
+    fn serialize_identifiers() {
+        assert_eq!(identifier("foo-bar_1"), "foo-bar_1");
+        assert_eq!(identifier("-"), "\\-");
+        assert_eq!(identifier("--x"), "--x");
+        assert_eq!(identifier("1a"), "\\31 a");
+        assert_eq!(identifier("-1a"), "-\\31 a");
+        assert_eq!(identifier("a.b:c"), "a\\.b\\:c");
+        assert_eq!(identifier("a b"), "a\\ b");
+        assert_eq!(identifier("a\u{7}b"), "a\\7 b");
+        assert_eq!(identifier("a\0"), "a\u{FFFD}");
+        assert_eq!(identifier("caf\u{E9}"), "caf\u{E9}");
+    }
+
+    #[test]
+    fn serialize_strings() {
+        assert_eq!(string("foo"), "\"foo\"");
+        assert_eq!(string(""), "\"\"");
+        assert_eq!(string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(string("a\nb"), "\"a\\a b\"");
+        assert_eq!(string("it's"), "\"it's\"");
+    }
+
+    #[test]
+    fn canonical_whitespace_and_combinators() {
+        assert_eq!(canonical("  ul   li  "), "ul li");
+        assert_eq!(canonical("ul>li+li~li"), "ul > li + li ~ li");
+        assert_eq!(canonical("col||td"), "col || td");
+        assert_eq!(canonical("a ,b,  c"), "a, b, c");
+    }
+
+    #[test]
+    fn canonical_universal() {
+        assert_eq!(canonical("*"), "*");
+        assert_eq!(canonical("*.a"), ".a");
+        assert_eq!(canonical("* > *:hover"), "* > :hover");
+    }
+
+    #[test]
+    fn canonical_simple_selectors() {
+        assert_eq!(canonical("li#id.a.b"), "li#id.a.b");
+        assert_eq!(canonical("[attr]"), "[attr]");
+        assert_eq!(canonical("[ attr = value ]"), "[attr=\"value\"]");
+        assert_eq!(canonical("[attr~='a b' i]"), "[attr~=\"a b\" i]");
+        assert_eq!(canonical("[attr|=en S]"), "[attr|=\"en\" s]");
+        assert_eq!(
+            canonical("[a^=x][b$=x][c*=x]"),
+            "[a^=\"x\"][b$=\"x\"][c*=\"x\"]"
+        );
+    }
+
+    #[test]
+    fn canonical_escapes() {
+        assert_eq!(canonical(".a\\.b"), ".a\\.b");
+        assert_eq!(canonical(".\\61 bc"), ".abc");
+        assert_eq!(canonical("#\\31 23"), "#\\31 23");
+        assert_eq!(canonical("[data-x='a\\'b']"), "[data-x=\"a'b\"]");
+        assert_eq!(canonical("[data-x=\"a\\\"b\"]"), "[data-x=\"a\\\"b\"]");
+    }
+
+    #[test]
+    fn canonical_pseudo_classes() {
+        assert_eq!(canonical("A:HOVER"), "A:hover");
+        assert_eq!(canonical(":NOT(.a ,.b)"), ":not(.a, .b)");
+        assert_eq!(canonical(":is(a,b):where(c)"), ":is(a, b):where(c)");
+        assert_eq!(canonical(":has(> a, b c, + d)"), ":has(> a, b c, + d)");
+        assert_eq!(canonical(":dir(rtl)"), ":dir(rtl)");
+        assert_eq!(canonical(":lang('en')"), ":lang(en)");
+        assert_eq!(canonical(":lang('*-CH')"), ":lang(\"*-CH\")");
+        assert_eq!(canonical(":current"), ":current");
+        assert_eq!(canonical(":current(p, li)"), ":current(p, li)");
+    }
+
+    #[test]
+    fn canonical_pseudo_elements() {
+        assert_eq!(canonical("*::BEFORE"), "::before");
+        assert_eq!(canonical("p:first-line"), "p::first-line");
+        assert_eq!(canonical("li:hover::marker"), "li:hover::marker");
+        assert_eq!(canonical("::part( a  b )"), "::part(a b)");
+        assert_eq!(canonical("::slotted(*.a)"), "::slotted(.a)");
+        assert_eq!(canonical("::highlight(x\\.y)"), "::highlight(x\\.y)");
+    }
+
+    #[test]
+    fn canonical_a_n_plus_b() {
+        assert_eq!(canonical(":nth-child(even)"), ":nth-child(even)");
+        assert_eq!(canonical(":nth-child(ODD)"), ":nth-child(odd)");
+        assert_eq!(canonical(":nth-child( 3 )"), ":nth-child(3)");
+        assert_eq!(canonical(":nth-child(+n)"), ":nth-child(n)");
+        assert_eq!(canonical(":nth-child(-n + 3)"), ":nth-child(-n+3)");
+        assert_eq!(
+            canonical(":nth-last-child(2n - 1)"),
+            ":nth-last-child(2n-1)"
+        );
+        assert_eq!(canonical(":nth-of-type(3n+0)"), ":nth-of-type(3n)");
+        assert_eq!(
+            canonical(":nth-child(2n+1 of li.a,#id)"),
+            ":nth-child(2n+1 of li.a, #id)"
+        );
+    }
+
+    #[test]
+    fn serialize_with_source() {
+        let source = "ul>li";
+        let selector = Selector::parse(source).unwrap();
+
+        assert_eq!(selector[0].serialize(source).to_string(), "ul > li");
+    }
+}
@@ -0,0 +1,218 @@
+use std::ops::Add;
+
+use super::{
+    ComplexSelector, CompoundSelector, PseudoClassSelector, PseudoElementSelector,
+    RelativeComplexSelector, SelectorList, TypeSelector,
+};
+
+/// The specificity of a selector, as defined by Selectors Level 4.
+///
+/// `a` counts ID selectors; `b` counts class selectors, attribute selectors and pseudo-classes; `c`
+/// counts type selectors and pseudo-elements. Specificities are ordered by comparing `a`, then `b`,
+/// then `c`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Specificity {
+    pub a: u32,
+    pub b: u32,
+    pub c: u32,
+}
+
+impl Specificity {
+    pub const fn new(a: u32, b: u32, c: u32) -> Self {
+        Specificity { a, b, c }
+    }
+}
+
+impl Add for Specificity {
+    type Output = Specificity;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Specificity {
+            a: self.a + rhs.a,
+            b: self.b + rhs.b,
+            c: self.c + rhs.c,
+        }
+    }
+}
+
+impl From<(u32, u32, u32)> for Specificity {
+    fn from((a, b, c): (u32, u32, u32)) -> Self {
+        Specificity { a, b, c }
+    }
+}
+
+impl From<Specificity> for (u32, u32, u32) {
+    fn from(specificity: Specificity) -> Self {
+        (specificity.a, specificity.b, specificity.c)
+    }
+}
+
+impl ComplexSelector {
+    pub fn specificity(&self) -> Specificity {
+        self.tail
+            .iter()
+            .fold(compound_specificity(&self.head), |acc, combined| {
+                acc + compound_specificity(&combined.selector)
+            })
+    }
+}
+
+impl RelativeComplexSelector {
+    pub fn specificity(&self) -> Specificity {
+        self.parts
+            .iter()
+            .fold(Specificity::default(), |acc, combined| {
+                acc + compound_specificity(&combined.selector)
+            })
+    }
+}
+
+fn compound_specificity(selector: &CompoundSelector) -> Specificity {
+    let mut specificity = Specificity::default();
+
+    if let Some(TypeSelector::Identifier(_)) = selector.type_selector {
+        specificity.c += 1;
+    }
+
+    if selector.id_selector.is_some() {
+        specificity.a += 1;
+    }
+
+    specificity.b += selector.class_selectors.len() as u32;
+    specificity.b += selector.attribute_selectors.len() as u32;
+
+    for pseudo_class in &selector.pseudo_class_selectors {
+        specificity = specificity + pseudo_class_specificity(pseudo_class);
+    }
+
+    match &selector.pseudo_element {
+        // The specificity of `::slotted()` is that of a pseudo-element, plus the specificity of its
+        // argument.
+        Some(PseudoElementSelector::Slotted(argument)) => {
+            specificity = specificity + Specificity::new(0, 0, 1) + compound_specificity(argument);
+        }
+        Some(_) => specificity.c += 1,
+        None => (),
+    }
+
+    specificity
+}
+
+fn pseudo_class_specificity(selector: &PseudoClassSelector) -> Specificity {
+    match selector {
+        // The specificity of `:is()`, `:not()` and `:has()` is that of the most specific complex
+        // selector in the argument list.
+        PseudoClassSelector::Is(list) | PseudoClassSelector::Not(list) => max_specificity(list),
+        PseudoClassSelector::Has(list) => list
+            .iter()
+            .map(|selector| selector.specificity())
+            .max()
+            .unwrap_or_default(),
+        // The specificity of `:where()` is always zero.
+        PseudoClassSelector::Where(_) => Specificity::default(),
+        // The specificity of `:nth-child(An+B of S)` is that of a single pseudo-class, plus the
+        // specificity of the most specific complex selector in `S`.
+        PseudoClassSelector::NthChild(a_n_plus_b_of)
+        | PseudoClassSelector::NthLastChild(a_n_plus_b_of) => {
+            let of = a_n_plus_b_of.of().map(max_specificity).unwrap_or_default();
+
+            Specificity::new(0, 1, 0) + of
+        }
+        _ => Specificity::new(0, 1, 0),
+    }
+}
+
+fn max_specificity(selector_list: &SelectorList) -> Specificity {
+    selector_list
+        .iter()
+        .map(|selector| selector.specificity())
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selector::Selector;
+
+    fn specificity(selector: &str) -> (u32, u32, u32) {
+        let selector = Selector::parse(selector).unwrap();
+
+        assert_eq!(selector.len(), 1);
+
+        selector[0].specificity().into()
+    }
+
+    #[test]
+    fn universal() {
+        assert_eq!(specificity("*"), (0, 0, 0));
+    }
+
+    #[test]
+    fn type_selector() {
+        assert_eq!(specificity("li"), (0, 0, 1));
+        assert_eq!(specificity("ul li"), (0, 0, 2));
+        assert_eq!(specificity("ul > li + li"), (0, 0, 3));
+    }
+
+    #[test]
+    fn id_class_attribute() {
+        assert_eq!(specificity("#id"), (1, 0, 0));
+        assert_eq!(specificity(".a.b"), (0, 2, 0));
+        assert_eq!(specificity("[attr]"), (0, 1, 0));
+        assert_eq!(specificity("li#id.a[attr=value]"), (1, 2, 1));
+        assert_eq!(specificity("*.a"), (0, 1, 0));
+    }
+
+    #[test]
+    fn simple_pseudo_classes() {
+        assert_eq!(specificity("a:hover"), (0, 1, 1));
+        assert_eq!(specificity("li:first-child:last-child"), (0, 2, 1));
+        assert_eq!(specificity("li:nth-of-type(2n)"), (0, 1, 1));
+    }
+
+    #[test]
+    fn is_and_not() {
+        assert_eq!(specificity(":is(#id, .class, li)"), (1, 0, 0));
+        assert_eq!(specificity("li:not(.a, ul li)"), (0, 1, 1));
+        assert_eq!(specificity(":not(:is(#id))"), (1, 0, 0));
+    }
+
+    #[test]
+    fn where_is_zero() {
+        assert_eq!(specificity(":where(#id, .class)"), (0, 0, 0));
+        assert_eq!(specificity("li:where(#id)"), (0, 0, 1));
+    }
+
+    #[test]
+    fn has() {
+        assert_eq!(specificity("ul:has(> li.a, #id)"), (1, 0, 1));
+        assert_eq!(specificity(":has(+ li.a span)"), (0, 1, 2));
+    }
+
+    #[test]
+    fn nth_child_of() {
+        assert_eq!(specificity(":nth-child(2n+1)"), (0, 1, 0));
+        assert_eq!(specificity(":nth-child(2n+1 of li.a, #id)"), (1, 1, 0));
+        assert_eq!(specificity(":nth-last-child(1 of .a.b)"), (0, 3, 0));
+    }
+
+    #[test]
+    fn pseudo_elements() {
+        assert_eq!(specificity("::before"), (0, 0, 1));
+        assert_eq!(specificity("li.a::marker"), (0, 1, 2));
+        assert_eq!(specificity("::part(label)"), (0, 0, 1));
+        assert_eq!(specificity("::slotted(span.a)"), (0, 1, 2));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Specificity::new(1, 0, 0) > Specificity::new(0, 10, 10));
+        assert!(Specificity::new(0, 1, 0) > Specificity::new(0, 0, 10));
+        assert!(Specificity::new(0, 1, 1) > Specificity::new(0, 1, 0));
+        assert_eq!(
+            Specificity::new(0, 1, 1).cmp(&Specificity::new(0, 1, 1)),
+            std::cmp::Ordering::Equal
+        );
+    }
+}
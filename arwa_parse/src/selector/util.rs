@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 const WHITESPACE_PATTERN: &'static [char] =
     &['\u{0020}', '\u{0009}', '\u{000A}', '\u{000D}', '\u{000C}'];
 
@@ -10,7 +12,7 @@ pub fn is_whitespace(c: char) -> bool {
 }
 
 pub fn is_identifier_start(c: char) -> bool {
-    c > '\u{0080}' || c.is_ascii_alphabetic() || c == '_'
+    c >= '\u{0080}' || c.is_ascii_alphabetic() || c == '_'
 }
 
 pub fn is_identifier_continue(c: char) -> bool {
@@ -33,3 +35,84 @@ pub fn case_insensitive_eq(a: &str, b: &str) -> bool {
 
     true
 }
+
+// Resolves an identifier or a (quoted) string to its value.
+pub fn unescape(raw: &str) -> Cow<'_, str> {
+    let inner = if raw.starts_with(['\'', '"']) {
+        &raw[1..raw.len() - 1]
+    } else {
+        raw
+    };
+
+    if !inner.contains('\\') {
+        return Cow::Borrowed(inner);
+    }
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+
+            continue;
+        }
+
+        match chars.peek().copied() {
+            // An escaped newline is a line continuation.
+            Some('\n') => {
+                chars.next();
+            }
+            Some(c) if c.is_ascii_hexdigit() => {
+                let mut code_point = 0;
+                let mut digits = 0;
+
+                while let Some(c) = chars.peek().copied() {
+                    if digits == 6 || !c.is_ascii_hexdigit() {
+                        break;
+                    }
+
+                    code_point = code_point * 16 + c.to_digit(16).unwrap();
+                    digits += 1;
+                    chars.next();
+                }
+
+                // A single whitespace character after a hex escape is part of the escape.
+                if chars.peek().copied().map(is_whitespace).unwrap_or(false) {
+                    chars.next();
+                }
+
+                let c = if code_point == 0 {
+                    '\u{FFFD}'
+                } else {
+                    char::from_u32(code_point).unwrap_or('\u{FFFD}')
+                };
+
+                result.push(c);
+            }
+            Some(c) => {
+                result.push(c);
+                chars.next();
+            }
+            None => (),
+        }
+    }
+
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_identifiers_and_strings() {
+        assert_eq!(unescape("ident"), "ident");
+        assert_eq!(unescape("a\\.b"), "a.b");
+        assert_eq!(unescape("'quoted'"), "quoted");
+        assert_eq!(unescape("\"a\\\"b\""), "a\"b");
+        assert_eq!(unescape("'\\31 23'"), "123");
+        assert_eq!(unescape("'\\1F600'"), "\u{1F600}");
+        assert_eq!(unescape("'a\\\nb'"), "ab");
+    }
+}
@@ -2,13 +2,11 @@
 
 use std::error::Error;
 
-use arwa::indexed_db::{
-    key_path, DatabaseVersion, IndexedDbContext, KeyConfig, OpenDataBaseRequest, UpgradeTransaction,
-};
+use arwa::indexed_db::{key_path, IndexedDbContext, KeyConfig, Migration, Schema};
 use arwa::window::window;
 use arwa::{console, spawn_local};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::JsValue;
 
 #[derive(Serialize, Deserialize)]
 pub struct Book {
@@ -31,31 +29,24 @@ async fn example() -> Result<(), Box<dyn Error>> {
     // Delete database if it exists, so we can start this example with a clean slate
     indexed_db.delete_database("book-store")?.await?;
 
-    // Open a connection to the database. If it did not yet exist, set it up in the `upgrade_needed`
-    // callback.
-    let database = indexed_db
-        .open_database(OpenDataBaseRequest {
-            name: "book-store",
-            version: DatabaseVersion::Number(1),
-            upgrade_needed: |transaction: UpgradeTransaction, old_version, _new_version| {
-                if old_version < 1 {
-                    let store = transaction
-                        .create_object_store(
-                            "books",
-                            Some(KeyConfig {
-                                path: key_path!("id"),
-                                auto_increment: false,
-                            }),
-                        )
-                        .unwrap();
-
-                    store
-                        .create_index("author", &key_path!("author"), &Default::default())
-                        .unwrap();
-                }
-            },
-            blocked: || (),
-        })?
+    // Declare the database schema. When the database is opened, the migrations for all versions
+    // after the database's current version are applied.
+    let schema = Schema::new().version(
+        1,
+        Migration::new()
+            .create_object_store(
+                "books",
+                Some(KeyConfig {
+                    path: key_path!("id"),
+                    auto_increment: false,
+                }),
+            )
+            .create_index("books", "author", &key_path!("author"), &Default::default()),
+    );
+
+    // Open a connection to the database.
+    let database = schema
+        .open_database(&indexed_db, "book-store", || ())?
         .await?;
 
     // Run a read-write transaction to populate the database with an initial book list.
@@ -120,20 +111,15 @@ async fn example() -> Result<(), Box<dyn Error>> {
     let books_store = transaction.object_store("books")?;
     let author_index = books_store.index("author")?;
 
-    let mut next = author_index
-        .open_cursor_with_query(&JsValue::from_str("Charles Dickens"), Default::default())?
-        .await?;
-
-    while let Some(mut cursor) = next.take() {
-        if let Some(value) = cursor.value() {
-            let book: Book = serde_wasm_bindgen::from_value(value)?;
+    let mut dickens_books = author_index.iter("Charles Dickens", Default::default());
 
-            console::log!("- Deleting %s...", book.title);
+    while let Some(entry) = dickens_books.next().await {
+        let entry = entry?;
+        let book: Book = serde_wasm_bindgen::from_value(entry.value().clone())?;
 
-            cursor = cursor.delete()?.await?;
-        }
+        console::log!("- Deleting %s...", book.title);
 
-        next = cursor.advance(1)?.await?;
+        entry.delete()?.await?;
     }
 
     transaction.commit();